@@ -11,7 +11,7 @@ use std::{fmt, mem, ops};
 
 use cfg::{CfgOptions, HashableCfgOptions};
 use dashmap::mapref::entry::Entry;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use intern::Symbol;
 use la_arena::{Arena, Idx, RawIdx};
 use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
@@ -92,6 +92,7 @@ pub struct CrateBuilder {
     pub cfg_options: Arc<CfgOptions>,
     pub env: Env,
     ws_data: Arc<CrateWorkspaceData>,
+    durability: Durability,
 }
 
 impl fmt::Debug for CrateGraphBuilder {
@@ -414,7 +415,11 @@ pub struct Crate {
 
 /// The mapping from [`UniqueCrateData`] to their [`Crate`] input.
 #[derive(Debug, Default)]
-pub struct CratesMap(DashMap<UniqueCrateData, Crate, BuildHasherDefault<FxHasher>>);
+pub struct CratesMap {
+    crates: DashMap<UniqueCrateData, Crate, BuildHasherDefault<FxHasher>>,
+    /// The crates whose inputs are stored with a durability below [`Durability::HIGH`].
+    low_durability: DashSet<Crate, BuildHasherDefault<FxHasher>>,
+}
 
 impl CrateGraphBuilder {
     pub fn add_crate_root(
@@ -445,9 +450,19 @@ impl CrateGraphBuilder {
             cfg_options,
             env,
             ws_data,
+            durability: Durability::HIGH,
         })
     }
 
+    /// Sets the durability the inputs of `krate` are stored with, crates default to
+    /// [`Durability::HIGH`].
+    ///
+    /// Ad hoc crates, like the ones created for files that are opened in the editor but belong to
+    /// no crate, come and go with the user's actions and should use [`Durability::LOW`].
+    pub fn set_durability(&mut self, krate: CrateBuilderId, durability: Durability) {
+        self.arena[krate].durability = durability;
+    }
+
     pub fn add_dep(
         &mut self,
         from: CrateBuilderId,
@@ -478,6 +493,9 @@ impl CrateGraphBuilder {
         let old_all_crates = db.all_crates();
 
         let crates_map = db.crates_map();
+        let is_durable = |krate: &&Crate| !crates_map.low_durability.contains(*krate);
+        let old_durable_crates =
+            old_all_crates.iter().filter(is_durable).copied().collect::<Vec<_>>();
         // salsa doesn't compare new input to old input to see if they are the same, so here we are doing all the work ourselves.
         for krate in self.iter() {
             go(
@@ -492,10 +510,14 @@ impl CrateGraphBuilder {
         }
 
         if **old_all_crates != *all_crates {
-            db.set_all_crates_with_durability(
-                Arc::new(all_crates.into_boxed_slice()),
-                Durability::HIGH,
-            );
+            // Adding or removing ad hoc crates shouldn't invalidate the queries of all the other
+            // crates, which only depend on high durability inputs.
+            let durability = if all_crates.iter().filter(is_durable).eq(&old_durable_crates) {
+                Durability::LOW
+            } else {
+                Durability::HIGH
+            };
+            db.set_all_crates_with_durability(Arc::new(all_crates.into_boxed_slice()), durability);
         }
 
         return visited;
@@ -548,34 +570,34 @@ impl CrateGraphBuilder {
 
             let unique_crate_data =
                 UniqueCrateData { root_file_id: krate.basic.root_file_id, disambiguator };
-            let crate_input = match crates_map.0.entry(unique_crate_data) {
+            let crate_input = match crates_map.crates.entry(unique_crate_data) {
                 Entry::Occupied(entry) => {
                     let old_crate = *entry.get();
                     if crate_data != *old_crate.data(db) {
-                        old_crate.set_data(db).with_durability(Durability::HIGH).to(crate_data);
+                        old_crate.set_data(db).with_durability(krate.durability).to(crate_data);
                     }
                     if krate.extra != *old_crate.extra_data(db) {
                         old_crate
                             .set_extra_data(db)
-                            .with_durability(Durability::HIGH)
+                            .with_durability(krate.durability)
                             .to(krate.extra.clone());
                     }
                     if krate.cfg_options != *old_crate.cfg_options(db) {
                         old_crate
                             .set_cfg_options(db)
-                            .with_durability(Durability::HIGH)
+                            .with_durability(krate.durability)
                             .to(krate.cfg_options.clone());
                     }
                     if krate.env != *old_crate.env(db) {
                         old_crate
                             .set_env(db)
-                            .with_durability(Durability::HIGH)
+                            .with_durability(krate.durability)
                             .to(krate.env.clone());
                     }
                     if krate.ws_data != *old_crate.workspace_data(db) {
                         old_crate
                             .set_workspace_data(db)
-                            .with_durability(Durability::HIGH)
+                            .with_durability(krate.durability)
                             .to(krate.ws_data.clone());
                     }
                     old_crate
@@ -588,12 +610,17 @@ impl CrateGraphBuilder {
                         krate.cfg_options.clone(),
                         krate.env.clone(),
                     )
-                    .durability(Durability::HIGH)
+                    .durability(krate.durability)
                     .new(db);
                     entry.insert(input);
                     input
                 }
             };
+            if krate.durability == Durability::HIGH {
+                crates_map.low_durability.remove(&crate_input);
+            } else {
+                crates_map.low_durability.insert(crate_input);
+            }
             all_crates.push(crate_input);
             visited.insert(source, crate_input);
             crate_input
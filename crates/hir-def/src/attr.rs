@@ -214,6 +214,10 @@ impl Attrs {
         self.by_key(&sym::ignore).exists()
     }
 
+    pub fn is_should_panic(&self) -> bool {
+        self.by_key(&sym::should_panic).exists()
+    }
+
     pub fn is_bench(&self) -> bool {
         self.by_key(&sym::bench).exists()
     }
@@ -708,6 +708,14 @@ impl Resolver {
         })
     }
 
+    /// The body and the innermost expression scope in it this resolver resolves in.
+    pub fn expr_scope(&self) -> Option<(DefWithBodyId, ScopeId)> {
+        self.scopes().find_map(|scope| match scope {
+            Scope::ExprScope(it) => Some((it.owner, it.scope_id)),
+            _ => None,
+        })
+    }
+
     pub fn type_owner(&self) -> Option<TypeOwnerId> {
         self.scopes().find_map(|scope| match scope {
             Scope::BlockScope(_) | Scope::MacroDefScope(_) => None,
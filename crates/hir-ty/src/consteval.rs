@@ -8,7 +8,7 @@ use hir_def::{
     path::Path,
     resolver::{Resolver, ValueNs},
     type_ref::LiteralConstRef,
-    ConstBlockLoc, EnumVariantId, GeneralConstId, HasModule as _, StaticId,
+    ConstBlockLoc, ConstId, DefWithBodyId, EnumVariantId, GeneralConstId, HasModule as _, StaticId,
 };
 use hir_expand::Lookup;
 use salsa::Cycle;
@@ -26,7 +26,10 @@ use crate::{
     Substitution, TraitEnvironment, Ty, TyBuilder,
 };
 
-use super::mir::{interpret_mir, lower_to_mir, pad16, MirEvalError, MirLowerError};
+use super::mir::{
+    interpret_mir, interpret_mir_with_limit, lower_expr_to_mir, lower_to_mir, pad16, MirEvalError,
+    MirLowerError,
+};
 
 /// Extension trait for [`Const`]
 pub trait ConstExt {
@@ -237,6 +240,16 @@ pub(crate) fn const_eval_recover(
     Err(ConstEvalError::MirLowerError(MirLowerError::Loop))
 }
 
+pub(crate) fn const_eval_with_limit_recover(
+    _: &dyn HirDatabase,
+    _: &Cycle,
+    _: HirDatabaseData,
+    _: ConstId,
+    _: usize,
+) -> Result<Const, ConstEvalError> {
+    Err(ConstEvalError::MirLowerError(MirLowerError::Loop))
+}
+
 pub(crate) fn const_eval_static_recover(
     _: &dyn HirDatabase,
     _: &Cycle,
@@ -284,6 +297,35 @@ pub(crate) fn const_eval_query(
     Ok(c)
 }
 
+pub(crate) fn const_eval_with_limit_query(
+    db: &dyn HirDatabase,
+    def: ConstId,
+    execution_limit: usize,
+) -> Result<Const, ConstEvalError> {
+    let body = db.monomorphized_mir_body(
+        def.into(),
+        Substitution::empty(Interner),
+        db.trait_environment(def.into()),
+    )?;
+    let c = interpret_mir_with_limit(db, body, execution_limit)?.0?;
+    Ok(c)
+}
+
+/// Evaluates `expr`, an expression inside the body of `owner`, on its own. This only works if the
+/// expression doesn't use locals declared outside of it.
+pub fn eval_expr_in_body(
+    db: &dyn HirDatabase,
+    owner: DefWithBodyId,
+    expr: ExprId,
+) -> Result<Const, ConstEvalError> {
+    let body = db.body(owner);
+    let infer = db.infer(owner);
+    let mir_body = lower_expr_to_mir(db, owner, &body, &infer, expr)?;
+    let trait_env = db.trait_environment_for_body(owner);
+    let c = interpret_mir(db, Arc::new(mir_body), false, Some(trait_env))?.0?;
+    Ok(c)
+}
+
 pub(crate) fn const_eval_static_query(
     db: &dyn HirDatabase,
     def: StaticId,
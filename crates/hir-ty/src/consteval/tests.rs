@@ -59,8 +59,8 @@ fn check_number(#[rust_analyzer::rust_fixture] ra_fixture: &str, answer: i128) {
 #[track_caller]
 fn check_str(#[rust_analyzer::rust_fixture] ra_fixture: &str, answer: &str) {
     check_answer(ra_fixture, |b, mm| {
-        let addr = usize::from_le_bytes(b[0..b.len() / 2].try_into().unwrap());
-        let size = usize::from_le_bytes(b[b.len() / 2..].try_into().unwrap());
+        let addr = u128::from_le_bytes(pad16(&b[0..b.len() / 2], false)) as usize;
+        let size = u128::from_le_bytes(pad16(&b[b.len() / 2..], false)) as usize;
         let Some(bytes) = mm.get(addr, size) else {
             panic!("string data missed in the memory map");
         };
@@ -278,6 +278,52 @@ fn floating_point_casts() {
     check_number(r#"const GOAL: i8 = (1./0.) as i8"#, 127);
     check_number(r#"const GOAL: i8 = (-1./0.) as i8"#, -128);
     check_number(r#"const GOAL: i64 = 1e18f64 as f32 as i64"#, 999999984306749440);
+    check_number(r#"const GOAL: u64 = 1e30 as u64"#, u64::MAX as i128);
+    check_number(r#"const GOAL: u128 = -1. as u128"#, 0);
+    check_number(
+        r#"const GOAL: f32 = !0u128 as f32"#,
+        i128::from_le_bytes(pad16(&f32::INFINITY.to_le_bytes(), true)),
+    );
+    check_number(
+        r#"const GOAL: f64 = 9007199254740993u64 as f64"#,
+        i128::from_le_bytes(pad16(&9007199254740992f64.to_le_bytes(), true)),
+    );
+}
+
+#[test]
+fn floating_point_casts_f16_f128() {
+    check_number(r#"const GOAL: i32 = 12.75f16 as i32"#, 12);
+    check_number(r#"const GOAL: i8 = -12.75f128 as i8"#, -12);
+    check_number(r#"const GOAL: u8 = 300f128 as u8"#, 255);
+    check_number(r#"const GOAL: i16 = (0f16 / 0f16) as i16"#, 0);
+    check_number(r#"const GOAL: u128 = 1e38f128 as u128"#, 100000000000000000000000000000000000000);
+    check_number(r#"const GOAL: i64 = 1.5f16 as f128 as f32 as i64"#, 1);
+    check_number(
+        r#"const GOAL: f16 = 70000 as f16"#,
+        i128::from_le_bytes(pad16(
+            &u16::try_from(f16::INFINITY.to_bits()).unwrap().to_le_bytes(),
+            true,
+        )),
+    );
+    check_number(
+        r#"const GOAL: f16 = 0.1f64 as f16"#,
+        i128::from_le_bytes(pad16(
+            &u16::try_from("0.1".parse::<f16>().unwrap().to_bits()).unwrap().to_le_bytes(),
+            true,
+        )),
+    );
+    check_number(
+        r#"const GOAL: f128 = 0.1f32 as f128"#,
+        "0.100000001490116119384765625".parse::<f128>().unwrap().to_bits() as i128,
+    );
+    check_number(
+        r#"const GOAL: f64 = 0.1f128 as f64"#,
+        i128::from_le_bytes(pad16(&0.1f64.to_le_bytes(), true)),
+    );
+    check_number(
+        r#"const GOAL: f128 = !0u128 as f128"#,
+        f128::from_u128(u128::MAX).value.to_bits() as i128,
+    );
 }
 
 #[test]
@@ -1905,6 +1951,26 @@ fn function_pointer_and_niche_optimization() {
     );
 }
 
+#[test]
+fn function_pointer_to_raw_pointer() {
+    check_number(
+        r#"
+    //- minicore: transmute
+    use core::mem::transmute;
+    fn add2(x: u8) -> u8 {
+        x + 2
+    }
+    const GOAL: u8 = {
+        let f: fn(u8) -> u8 = add2;
+        let p = f as *const ();
+        let g: fn(u8) -> u8 = unsafe { transmute(p) };
+        g(3)
+    };
+        "#,
+        5,
+    );
+}
+
 #[test]
 fn function_pointer() {
     check_number(
@@ -2189,6 +2255,44 @@ const GOAL: usize = {
         "#,
         3,
     );
+    check_number(
+        r#"
+//- minicore: coerce_unsized, deref_mut, slice, index, transmute, non_null, phantom_data
+use core::ops::CoerceUnsized;
+use core::{marker::{PhantomData, Unsize}, mem::transmute, ptr::NonNull};
+
+struct RcBox<T: ?Sized> {
+    strong: usize,
+    value: T,
+}
+
+pub struct Rc<T: ?Sized> {
+    ptr: NonNull<RcBox<T>>,
+    phantom: PhantomData<RcBox<T>>,
+}
+
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<Rc<U>> for Rc<T> {}
+
+const GOAL: usize = {
+    let x = transmute::<usize, Rc<[i32; 5]>>(12);
+    let y: Rc<[i32]> = x;
+    let z = transmute::<Rc<[i32]>, (usize, usize)>(y);
+    z.1
+};
+        "#,
+        5,
+    );
+    check_number(
+        r#"
+//- minicore: coerce_unsized, slice, index
+const GOAL: i32 = {
+    let x: &(u8, [i32; 4]) = &(1, [2, 3, 4, 5]);
+    let y: &(u8, [i32]) = x;
+    y.1.len() as i32 + y.1[3]
+};
+        "#,
+        9,
+    );
 }
 
 #[test]
@@ -2456,6 +2560,98 @@ fn extern_weak_statics() {
     );
 }
 
+#[test]
+fn target_with_smaller_pointer_width() {
+    // thumbv7em-none-eabihf
+    const LAYOUT: &str =
+        "//- target_data_layout: e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64";
+    check_number(
+        &format!(
+            r#"{LAYOUT}
+//- minicore: size_of
+use core::mem::size_of;
+const GOAL: usize = size_of::<usize>() + size_of::<&str>() + size_of::<&[u64]>();
+"#
+        ),
+        20,
+    );
+    check_number(
+        &format!(
+            r#"{LAYOUT}
+//- minicore: coerce_unsized, index, slice, dispatch_from_dyn
+trait Foo {{
+    fn foo(&self) -> usize;
+}}
+impl Foo for u8 {{
+    fn foo(&self) -> usize {{ *self as usize }}
+}}
+const GOAL: usize = {{
+    let x: &[&dyn Foo] = &[&1u8, &2u8, &3u8];
+    let [first, rest @ ..] = x else {{ return 0 }};
+    first.foo() + rest[1].foo() * rest.len()
+}};
+"#
+        ),
+        7,
+    );
+    check_number(
+        &format!(
+            r#"{LAYOUT}
+//- minicore: index, slice
+const GOAL: usize = {{
+    let x = [10u16, 20, 30];
+    let p = &x as *const [u16; 3] as *const u16;
+    let q = &x[2] as *const u16;
+    (q as usize - p as usize) + unsafe {{ *p }} as usize
+}};
+"#
+        ),
+        14,
+    );
+    check_str(
+        &format!(
+            r#"{LAYOUT}
+const GOAL: &str = {{
+    let x: &str = "hello world";
+    x
+}};
+"#
+        ),
+        "hello world",
+    );
+    check_number(
+        &format!(
+            r#"{LAYOUT}
+extern "rust-intrinsic" {{
+    pub fn copy_nonoverlapping<T>(src: *const T, dst: *mut T, count: usize);
+}}
+const GOAL: u32 = unsafe {{
+    let mut x = 2;
+    let y = 5;
+    copy_nonoverlapping(&y, &mut x, 1);
+    x
+}};
+"#
+        ),
+        5,
+    );
+}
+
+#[test]
+fn big_endian_target_is_not_supported() {
+    // powerpc64-unknown-linux-gnu
+    check_fail(
+        r#"
+//- target_data_layout: E-m:e-Fi64-i64:64-n32:64-S128-v256:256:256-v512:512:512
+const GOAL: u32 = {
+    let x = 1u32;
+    x + 1
+};
+"#,
+        |e| matches!(e, ConstEvalError::MirEvalError(MirEvalError::NotSupported(_))),
+    );
+}
+
 #[test]
 fn from_ne_bytes() {
     check_number(
@@ -436,7 +436,6 @@ fn likely() {
 
 #[test]
 fn floating_point() {
-    // FIXME(#17451): Add `f16` and `f128` tests once intrinsics are added.
     check_number(
         r#"
         extern "rust-intrinsic" {
@@ -468,6 +467,36 @@ fn floating_point() {
             true,
         )),
     );
+    check_number(
+        r#"
+        extern "rust-intrinsic" {
+            pub fn sqrtf16(x: f16) -> f16;
+            pub fn floorf16(x: f16) -> f16;
+            pub fn fmaf16(a: f16, b: f16, c: f16) -> f16;
+        }
+
+        const GOAL: f16 = sqrtf16(6.25) + floorf16(-2.5) + fmaf16(1.5, 2.0, 0.25);
+        "#,
+        i128::from_le_bytes(pad16(
+            &u16::try_from("2.75".parse::<f16>().unwrap().to_bits()).unwrap().to_le_bytes(),
+            true,
+        )),
+    );
+    check_number(
+        r#"
+        extern "rust-intrinsic" {
+            pub fn fabsf128(x: f128) -> f128;
+            pub fn roundf128(x: f128) -> f128;
+            pub fn roundevenf128(x: f128) -> f128;
+            pub fn copysignf128(x: f128, y: f128) -> f128;
+            pub fn maxnumf128(x: f128, y: f128) -> f128;
+        }
+
+        const GOAL: f128 = fabsf128(-1.25) + roundf128(2.5) + roundevenf128(2.5)
+            + copysignf128(4.0, -0.0) + maxnumf128(0.0 / 0.0, 0.125);
+        "#,
+        "2.375".parse::<f128>().unwrap().to_bits() as i128,
+    );
 }
 
 #[test]
@@ -5,7 +5,7 @@ use std::sync;
 
 use base_db::{impl_intern_key, Crate, Upcast};
 use hir_def::{
-    db::DefDatabase, hir::ExprId, layout::TargetDataLayout, AdtId, BlockId, CallableDefId,
+    db::DefDatabase, hir::ExprId, layout::TargetDataLayout, AdtId, BlockId, CallableDefId, ConstId,
     ConstParamId, DefWithBodyId, EnumVariantId, FunctionId, GeneralConstId, GenericDefId, ImplId,
     LifetimeParamId, LocalFieldId, StaticId, TraitId, TypeAliasId, TypeOrConstParamId, VariantId,
 };
@@ -24,8 +24,8 @@ use crate::{
     lower::{Diagnostics, GenericDefaults, GenericPredicates},
     method_resolution::{InherentImpls, TraitImpls, TyFingerprint},
     mir::{BorrowckResult, MirBody, MirLowerError},
-    Binders, ClosureId, Const, FnDefId, ImplTraitId, ImplTraits, InferenceResult, Interner,
-    PolyFnSig, Substitution, TraitEnvironment, TraitRef, Ty, TyDefId, ValueTyDefId,
+    Binders, ClosureId, CoercionSources, Const, FnDefId, ImplTraitId, ImplTraits, InferenceResult,
+    Interner, PolyFnSig, Substitution, TraitEnvironment, TraitRef, Ty, TyDefId, ValueTyDefId,
 };
 
 #[query_group::query_group]
@@ -33,6 +33,9 @@ pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> + std::fmt::Debug {
     #[salsa::invoke_actual(crate::infer::infer_query)]
     fn infer(&self, def: DefWithBodyId) -> Arc<InferenceResult>;
 
+    #[salsa::invoke_actual(crate::infer::coercion_sources_query)]
+    fn coercion_sources(&self, def: DefWithBodyId) -> Arc<CoercionSources>;
+
     // region:mir
 
     #[salsa::invoke_actual(crate::mir::mir_body_query)]
@@ -72,6 +75,15 @@ pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> + std::fmt::Debug {
         trait_env: Option<Arc<TraitEnvironment>>,
     ) -> Result<Const, ConstEvalError>;
 
+    /// Like [`HirDatabase::const_eval`], but gives up after `execution_limit` steps.
+    #[salsa::invoke(crate::consteval::const_eval_with_limit_query)]
+    #[salsa::cycle(crate::consteval::const_eval_with_limit_recover)]
+    fn const_eval_with_limit(
+        &self,
+        def: ConstId,
+        execution_limit: usize,
+    ) -> Result<Const, ConstEvalError>;
+
     #[salsa::invoke_actual(crate::consteval::const_eval_static_query)]
     #[salsa::cycle(crate::consteval::const_eval_static_recover)]
     fn const_eval_static(&self, def: StaticId) -> Result<Const, ConstEvalError>;
@@ -674,6 +674,16 @@ impl HirDisplay for Const {
     }
 }
 
+/// `usize` values and addresses in consts are stored in the target's pointer width.
+fn target_ptr_size(f: &HirFormatter<'_>) -> usize {
+    f.db.target_data_layout(f.krate())
+        .map_or(size_of::<usize>(), |it| it.pointer_size.bytes_usize())
+}
+
+fn usize_from_bytes(b: &[u8]) -> usize {
+    u128::from_le_bytes(pad16(b, false)) as usize
+}
+
 fn render_const_scalar(
     f: &mut HirFormatter<'_>,
     b: &[u8],
@@ -735,8 +745,8 @@ fn render_const_scalar(
         },
         TyKind::Ref(_, _, t) => match t.kind(Interner) {
             TyKind::Str => {
-                let addr = usize::from_le_bytes(b[0..b.len() / 2].try_into().unwrap());
-                let size = usize::from_le_bytes(b[b.len() / 2..].try_into().unwrap());
+                let addr = usize_from_bytes(&b[0..b.len() / 2]);
+                let size = usize_from_bytes(&b[b.len() / 2..]);
                 let Some(bytes) = memory_map.get(addr, size) else {
                     return f.write_str("<ref-data-not-available>");
                 };
@@ -744,8 +754,8 @@ fn render_const_scalar(
                 write!(f, "{s:?}")
             }
             TyKind::Slice(ty) => {
-                let addr = usize::from_le_bytes(b[0..b.len() / 2].try_into().unwrap());
-                let count = usize::from_le_bytes(b[b.len() / 2..].try_into().unwrap());
+                let addr = usize_from_bytes(&b[0..b.len() / 2]);
+                let count = usize_from_bytes(&b[b.len() / 2..]);
                 let Ok(layout) = f.db.layout_of_ty(ty.clone(), trait_env) else {
                     return f.write_str("<layout-error>");
                 };
@@ -767,8 +777,8 @@ fn render_const_scalar(
                 f.write_str("]")
             }
             TyKind::Dyn(_) => {
-                let addr = usize::from_le_bytes(b[0..b.len() / 2].try_into().unwrap());
-                let ty_id = usize::from_le_bytes(b[b.len() / 2..].try_into().unwrap());
+                let addr = usize_from_bytes(&b[0..b.len() / 2]);
+                let ty_id = usize_from_bytes(&b[b.len() / 2..]);
                 let Ok(t) = memory_map.vtable_ty(ty_id) else {
                     return f.write_str("<ty-missing-in-vtable-map>");
                 };
@@ -782,7 +792,7 @@ fn render_const_scalar(
                 f.write_str("&")?;
                 render_const_scalar(f, bytes, memory_map, t)
             }
-            TyKind::Adt(adt, _) if b.len() == 2 * size_of::<usize>() => match adt.0 {
+            TyKind::Adt(adt, _) if b.len() == 2 * target_ptr_size(f) => match adt.0 {
                 hir_def::AdtId::StructId(s) => {
                     let data = f.db.struct_data(s);
                     write!(f, "&{}", data.name.display(f.db.upcast(), f.edition()))?;
@@ -791,17 +801,15 @@ fn render_const_scalar(
                 _ => f.write_str("<unsized-enum-or-union>"),
             },
             _ => {
-                let addr = usize::from_le_bytes(match b.try_into() {
-                    Ok(b) => b,
-                    Err(_) => {
-                        never!(
-                            "tried rendering ty {:?} in const ref with incorrect byte count {}",
-                            t,
-                            b.len()
-                        );
-                        return f.write_str("<layout-error>");
-                    }
-                });
+                if b.len() != target_ptr_size(f) {
+                    never!(
+                        "tried rendering ty {:?} in const ref with incorrect byte count {}",
+                        t,
+                        b.len()
+                    );
+                    return f.write_str("<layout-error>");
+                }
+                let addr = usize_from_bytes(b);
                 let Ok(layout) = f.db.layout_of_ty(t.clone(), trait_env) else {
                     return f.write_str("<layout-error>");
                 };
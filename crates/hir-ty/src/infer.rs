@@ -88,9 +88,31 @@ pub(crate) use closure::{CaptureKind, CapturedItem, CapturedItemWithoutTy};
 /// The entry point of type inference.
 pub(crate) fn infer_query(db: &dyn HirDatabase, def: DefWithBodyId) -> Arc<InferenceResult> {
     let _p = tracing::info_span!("infer_query").entered();
+    Arc::new(infer_body(db, def, false).0)
+}
+
+/// Infers the body again, recording where the expected types of coerced expressions come from.
+///
+/// This is only needed to explain types to the user, so it is kept out of [`InferenceResult`].
+pub(crate) fn coercion_sources_query(
+    db: &dyn HirDatabase,
+    def: DefWithBodyId,
+) -> Arc<CoercionSources> {
+    let _p = tracing::info_span!("coercion_sources_query").entered();
+    Arc::new(infer_body(db, def, true).1)
+}
+
+fn infer_body(
+    db: &dyn HirDatabase,
+    def: DefWithBodyId,
+    record_coercion_sources: bool,
+) -> (InferenceResult, CoercionSources) {
     let resolver = def.resolver(db.upcast());
     let body = db.body(def);
     let mut ctx = InferenceContext::new(db, def, &body, resolver);
+    if record_coercion_sources {
+        ctx.coercion_sources = Some(FxHashMap::default());
+    }
 
     match def {
         DefWithBodyId::FunctionId(f) => {
@@ -142,7 +164,7 @@ pub(crate) fn infer_query(db: &dyn HirDatabase, def: DefWithBodyId) -> Arc<Infer
 
     ctx.infer_closures();
 
-    Arc::new(ctx.resolve_all())
+    ctx.resolve_all_with_coercion_sources()
 }
 
 /// Fully normalize all the types found within `ty` in context of `owner` body definition.
@@ -295,6 +317,32 @@ pub struct TypeMismatch {
     pub actual: Ty,
 }
 
+/// Where the type an expression was coerced or unified to came from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum CoercionSource {
+    /// The type annotation of the `let` statement the expression initializes.
+    LetAnnotation,
+    /// The parameter at `index` of the function called by `call`, not counting the receiver.
+    Argument { call: ExprId, index: usize },
+    /// The return type of the enclosing function, closure or async block.
+    Return,
+    /// The other branches or elements of the `if`, `match` or array expression.
+    Branches(ExprId),
+    /// The field of a record literal.
+    Field(FieldId),
+}
+
+/// For each expression of a body that was coerced or unified with an expected type, that type and
+/// where it came from.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CoercionSources(FxHashMap<ExprId, (CoercionSource, Ty)>);
+
+impl CoercionSources {
+    pub fn get(&self, expr: ExprId) -> Option<(CoercionSource, &Ty)> {
+        self.0.get(&expr).map(|(source, ty)| (*source, ty))
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 struct InternedStandardTypes {
     unknown: Ty,
@@ -637,6 +685,8 @@ pub(crate) struct InferenceContext<'a> {
     /// comment on `InferenceContext::sort_closures`
     closure_dependencies: FxHashMap<ClosureId, Vec<ClosureId>>,
     deferred_closures: FxHashMap<ClosureId, Vec<(Ty, Ty, Vec<Ty>, ExprId)>>,
+    /// Where the expected types of coerced expressions come from, only recorded when requested.
+    coercion_sources: Option<FxHashMap<ExprId, (CoercionSource, Ty)>>,
 
     diagnostics: Diagnostics,
 }
@@ -720,6 +770,7 @@ impl<'a> InferenceContext<'a> {
             deferred_closures: FxHashMap::default(),
             closure_dependencies: FxHashMap::default(),
             inside_assignment: false,
+            coercion_sources: None,
             diagnostics: Diagnostics::default(),
         }
     }
@@ -739,11 +790,16 @@ impl<'a> InferenceContext<'a> {
     // used this function for another workaround, mention it here. If you really need this function and believe that
     // there is no problem in it being `pub(crate)`, remove this comment.
     pub(crate) fn resolve_all(self) -> InferenceResult {
+        self.resolve_all_with_coercion_sources().0
+    }
+
+    fn resolve_all_with_coercion_sources(self) -> (InferenceResult, CoercionSources) {
         let InferenceContext {
             mut table,
             mut result,
             mut deferred_cast_checks,
             tuple_field_accesses_rev,
+            coercion_sources,
             diagnostics,
             ..
         } = self;
@@ -875,6 +931,10 @@ impl<'a> InferenceContext<'a> {
             adjustment.target = table.resolve_completely(adjustment.target.clone());
             *has_errors = *has_errors || adjustment.target.contains_unknown();
         }
+        let mut coercion_sources = coercion_sources.unwrap_or_default();
+        for (_, ty) in coercion_sources.values_mut() {
+            *ty = table.resolve_completely(ty.clone());
+        }
         for adjustment in pat_adjustments.values_mut().flatten() {
             *adjustment = table.resolve_completely(adjustment.clone());
             *has_errors = *has_errors || adjustment.contains_unknown();
@@ -891,7 +951,7 @@ impl<'a> InferenceContext<'a> {
 
         result.diagnostics = diagnostics;
 
-        result
+        (result, CoercionSources(coercion_sources))
     }
 
     fn collect_const(&mut self, data: &ConstData) {
@@ -988,7 +1048,8 @@ impl<'a> InferenceContext<'a> {
         };
 
         self.return_ty = self.normalize_associated_types_in(return_ty);
-        self.return_coercion = Some(CoerceMany::new(self.return_ty.clone()));
+        self.return_coercion =
+            Some(CoerceMany::new(self.return_ty.clone()).with_source(CoercionSource::Return));
 
         // Functions might be defining usage sites of TAITs.
         // To define an TAITs, that TAIT must appear in the function's signatures.
@@ -1235,6 +1296,12 @@ impl<'a> InferenceContext<'a> {
         }
     }
 
+    fn write_coercion_source(&mut self, expr: ExprId, source: CoercionSource, target: Ty) {
+        if let Some(coercion_sources) = &mut self.coercion_sources {
+            coercion_sources.insert(expr, (source, target));
+        }
+    }
+
     fn write_expr_ty(&mut self, expr: ExprId, ty: Ty) {
         self.result.type_of_expr.insert(expr, ty);
     }
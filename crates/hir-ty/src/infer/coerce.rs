@@ -19,8 +19,8 @@ use crate::{
     autoderef::{Autoderef, AutoderefKind},
     db::HirDatabase,
     infer::{
-        Adjust, Adjustment, AutoBorrow, InferOk, InferenceContext, OverloadedDeref, PointerCast,
-        TypeError, TypeMismatch,
+        Adjust, Adjustment, AutoBorrow, CoercionSource, InferOk, InferenceContext, OverloadedDeref,
+        PointerCast, TypeError, TypeMismatch,
     },
     utils::ClosureSubst,
     Canonical, DomainGoal, FnAbi, FnPointer, FnSig, Guidance, InEnvironment, Interner, Lifetime,
@@ -61,11 +61,17 @@ pub(super) struct CoerceMany {
     expected_ty: Ty,
     final_ty: Option<Ty>,
     expressions: Vec<ExprId>,
+    source: Option<CoercionSource>,
 }
 
 impl CoerceMany {
     pub(super) fn new(expected: Ty) -> Self {
-        CoerceMany { expected_ty: expected, final_ty: None, expressions: vec![] }
+        CoerceMany { expected_ty: expected, final_ty: None, expressions: vec![], source: None }
+    }
+
+    /// Records `source` as where the type of every expression coerced by this came from.
+    pub(super) fn with_source(self, source: CoercionSource) -> Self {
+        CoerceMany { source: Some(source), ..self }
     }
 
     /// Returns the "expected type" with which this coercion was
@@ -171,6 +177,7 @@ impl CoerceMany {
             if let Ok(res) = ctx.coerce(expr, &expr_ty, &self.merged_ty(), CoerceNever::Yes) {
                 self.final_ty = Some(res);
                 if let Some(expr) = expr {
+                    self.write_source(ctx, expr);
                     self.expressions.push(expr);
                 }
                 return;
@@ -196,9 +203,16 @@ impl CoerceMany {
             cov_mark::hit!(coerce_merge_fail_fallback);
         }
         if let Some(expr) = expr {
+            self.write_source(ctx, expr);
             self.expressions.push(expr);
         }
     }
+
+    fn write_source(&self, ctx: &mut InferenceContext<'_>, expr: ExprId) {
+        if let Some(source) = self.source {
+            ctx.write_coercion_source(expr, source, self.merged_ty());
+        }
+    }
 }
 
 pub fn could_coerce(
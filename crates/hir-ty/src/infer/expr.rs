@@ -32,7 +32,7 @@ use crate::{
         coerce::{CoerceMany, CoerceNever, CoercionCause},
         find_continuable,
         pat::contains_explicit_ref_binding,
-        BreakableKind,
+        BreakableKind, CoercionSource,
     },
     lang_items::lang_items_for_bin_op,
     lower::{
@@ -305,7 +305,8 @@ impl InferenceContext<'_> {
 
                 let then_ty = self.infer_expr_inner(then_branch, expected, ExprIsRead::Yes);
                 let then_diverges = mem::replace(&mut self.diverges, Diverges::Maybe);
-                let mut coerce = CoerceMany::new(expected.coercion_target_type(&mut self.table));
+                let mut coerce = CoerceMany::new(expected.coercion_target_type(&mut self.table))
+                    .with_source(CoercionSource::Branches(tgt_expr));
                 coerce.coerce(self, Some(then_branch), &then_ty, CoercionCause::Expr(then_branch));
                 match else_branch {
                     Some(else_branch) => {
@@ -472,8 +473,10 @@ impl InferenceContext<'_> {
                 let prev_diverges = mem::replace(&mut self.diverges, Diverges::Maybe);
                 let prev_closure = mem::replace(&mut self.current_closure, id);
                 let prev_ret_ty = mem::replace(&mut self.return_ty, ret_ty.clone());
-                let prev_ret_coercion =
-                    mem::replace(&mut self.return_coercion, Some(CoerceMany::new(ret_ty)));
+                let prev_ret_coercion = mem::replace(
+                    &mut self.return_coercion,
+                    Some(CoerceMany::new(ret_ty).with_source(CoercionSource::Return)),
+                );
                 let prev_resume_yield_tys =
                     mem::replace(&mut self.resume_yield_tys, resume_yield_tys);
 
@@ -527,7 +530,8 @@ impl InferenceContext<'_> {
                         }
                         _ => self.table.new_type_var(),
                     };
-                    let mut coerce = CoerceMany::new(result_ty);
+                    let mut coerce =
+                        CoerceMany::new(result_ty).with_source(CoercionSource::Branches(tgt_expr));
 
                     for arm in arms.iter() {
                         if let Some(guard_expr) = arm.guard {
@@ -694,6 +698,13 @@ impl InferenceContext<'_> {
                             // Field type might have some unknown types
                             // FIXME: we may want to emit a single type variable for all instance of type fields?
                             let field_ty = self.insert_type_vars(field_ty);
+                            if let Some(local_id) = field_def {
+                                self.write_coercion_source(
+                                    field.expr,
+                                    CoercionSource::Field(FieldId { parent: def, local_id }),
+                                    field_ty.clone(),
+                                );
+                            }
                             self.infer_expr_coerce(
                                 field.expr,
                                 &Expectation::has_type(field_ty),
@@ -975,7 +986,7 @@ impl InferenceContext<'_> {
 
                 TyKind::Tuple(tys.len(), Substitution::from_iter(Interner, tys)).intern(Interner)
             }
-            Expr::Array(array) => self.infer_expr_array(array, expected),
+            Expr::Array(array) => self.infer_expr_array(tgt_expr, array, expected),
             Expr::Literal(lit) => match lit {
                 Literal::Bool(..) => self.result.standard_types.bool_.clone(),
                 Literal::String(..) => {
@@ -1168,8 +1179,10 @@ impl InferenceContext<'_> {
         let ret_ty = self.table.new_type_var();
         let prev_diverges = mem::replace(&mut self.diverges, Diverges::Maybe);
         let prev_ret_ty = mem::replace(&mut self.return_ty, ret_ty.clone());
-        let prev_ret_coercion =
-            mem::replace(&mut self.return_coercion, Some(CoerceMany::new(ret_ty.clone())));
+        let prev_ret_coercion = mem::replace(
+            &mut self.return_coercion,
+            Some(CoerceMany::new(ret_ty.clone()).with_source(CoercionSource::Return)),
+        );
 
         // FIXME: We should handle async blocks like we handle closures
         let expected = &Expectation::has_type(ret_ty);
@@ -1270,6 +1283,7 @@ impl InferenceContext<'_> {
 
     fn infer_expr_array(
         &mut self,
+        tgt_expr: ExprId,
         array: &Array,
         expected: &Expectation,
     ) -> chalk_ir::Ty<Interner> {
@@ -1286,7 +1300,8 @@ impl InferenceContext<'_> {
                 (elem_ty, consteval::usize_const(self.db, Some(0), krate))
             }
             Array::ElementList { elements, .. } => {
-                let mut coerce = CoerceMany::new(elem_ty);
+                let mut coerce =
+                    CoerceMany::new(elem_ty).with_source(CoercionSource::Branches(tgt_expr));
                 for &expr in elements.iter() {
                     let cur_elem_ty = self.infer_expr_inner(expr, &expected, ExprIsRead::Yes);
                     coerce.coerce(self, Some(expr), &cur_elem_ty, CoercionCause::Expr(expr));
@@ -1556,6 +1571,13 @@ impl InferenceContext<'_> {
                                         target_is_read,
                                     )
                                 };
+                                if type_ref.is_some() {
+                                    this.write_coercion_source(
+                                        *expr,
+                                        CoercionSource::LetAnnotation,
+                                        decl_ty.clone(),
+                                    );
+                                }
                                 if type_ref.is_some() {
                                     decl_ty
                                 } else {
@@ -2162,6 +2184,11 @@ impl InferenceContext<'_> {
                     } else {
                         CoerceNever::No
                     };
+                self.write_coercion_source(
+                    arg,
+                    CoercionSource::Argument { call: expr, index: idx },
+                    coercion_target.clone(),
+                );
                 if self.coerce(Some(arg), &ty, &coercion_target, coerce_never).is_err()
                     && !arg_count_mismatch
                 {
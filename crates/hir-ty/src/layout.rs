@@ -388,6 +388,10 @@ fn struct_tail_erasing_lifetimes(db: &dyn HirDatabase, pointee: Ty) -> Ty {
                 None => pointee,
             }
         }
+        TyKind::Tuple(_, subst) => match subst.as_slice(Interner).last() {
+            Some(last) => struct_tail_erasing_lifetimes(db, last.assert_ty_ref(Interner).clone()),
+            None => pointee,
+        },
         _ => pointee,
     }
 }
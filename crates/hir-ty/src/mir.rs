@@ -30,9 +30,13 @@ mod pretty;
 
 pub use borrowck::{borrowck_query, BorrowckResult, MutabilityReason};
 pub use eval::{
-    interpret_mir, pad16, render_const_using_debug_impl, Evaluator, MirEvalError, VTableMap,
+    debug_mir, interpret_mir, interpret_mir_with, interpret_mir_with_limit, pad16,
+    render_const_using_debug_impl, DebugFrame, DebugLocal, DebugState, Evaluator, MirDebugger,
+    MirEvalError, VTableMap,
+};
+pub use lower::{
+    lower_expr_to_mir, lower_to_mir, mir_body_for_closure_query, mir_body_query, MirLowerError,
 };
-pub use lower::{lower_to_mir, mir_body_for_closure_query, mir_body_query, MirLowerError};
 pub use monomorphization::{
     monomorphize_mir_body_bad, monomorphized_mir_body_for_closure_query,
     monomorphized_mir_body_query,
@@ -18,10 +18,10 @@ use hir_def::{
 use hir_expand::{mod_path::path, name::Name, HirFileIdExt, InFile};
 use intern::sym;
 use la_arena::ArenaMap;
-use rustc_abi::TargetDataLayout;
+use rustc_abi::{Endian, TargetDataLayout};
 use rustc_apfloat::{
-    ieee::{Half as f16, Quad as f128},
-    Float,
+    ieee::{Double, Half as f16, Quad as f128, Single},
+    Float, FloatConvert,
 };
 use rustc_hash::{FxHashMap, FxHashSet};
 use span::FileId;
@@ -50,11 +50,19 @@ use super::{
     TerminatorKind, UnOp,
 };
 
+mod debugger;
 mod shim;
+mod ub_checks;
+
+pub use debugger::{DebugFrame, DebugLocal, DebugState, MirDebugger};
+use ub_checks::UbChecks;
 #[cfg(test)]
 mod tests;
 
 macro_rules! from_bytes {
+    (usize, $value:expr) => {
+        usize_from_bytes(&($value))?
+    };
     ($ty:tt, $value:expr) => {
         ($ty::from_le_bytes(match ($value).try_into() {
             Ok(it) => it,
@@ -70,6 +78,84 @@ macro_rules! from_bytes {
     };
 }
 
+/// Reads a `usize` stored in the target's pointer width, which may differ from the host's.
+fn usize_from_bytes(it: &[u8]) -> Result<usize> {
+    if it.len() > 8 {
+        return Err(MirEvalError::InternalError("mismatched size in constructing usize".into()));
+    }
+    usize::try_from(u128::from_le_bytes(pad16(it, false)))
+        .map_err(|_| MirEvalError::InternalError("target usize does not fit in host usize".into()))
+}
+
+/// Finds the syntax node a `MirSpan` of the body `owner` points to.
+fn mir_span_syntax(
+    db: &dyn HirDatabase,
+    owner: DefWithBodyId,
+    span: MirSpan,
+) -> Option<InFile<SyntaxNodePtr>> {
+    let source_map = db.body_with_source_map(owner).1;
+    match span {
+        MirSpan::ExprId(e) => source_map.expr_syntax(e).ok().map(|s| s.map(|it| it.into())),
+        MirSpan::PatId(p) => {
+            source_map.pat_syntax(p).ok().map(|s| s.map(|it| it.syntax_node_ptr()))
+        }
+        MirSpan::BindingId(b) => source_map
+            .patterns_for_binding(b)
+            .iter()
+            .find_map(|p| source_map.pat_syntax(*p).ok())
+            .map(|s| s.map(|it| it.syntax_node_ptr())),
+        MirSpan::SelfParam => {
+            source_map.self_param_syntax().map(|s| s.map(|it| it.syntax_node_ptr()))
+        }
+        MirSpan::Unknown => None,
+    }
+}
+
+/// Reads a float of type `ty` as a quad, which represents every narrower float format exactly.
+fn quad_from_bytes(ty: chalk_ir::FloatTy, it: &[u8]) -> Result<f128> {
+    fn widen<F: FloatConvert<f128>>(it: F) -> f128 {
+        it.convert(&mut false).value
+    }
+    Ok(match ty {
+        chalk_ir::FloatTy::F16 => widen(from_bytes!(f16, u16, it)),
+        chalk_ir::FloatTy::F32 => widen(from_bytes!(Single, u32, it)),
+        chalk_ir::FloatTy::F64 => widen(from_bytes!(Double, u64, it)),
+        chalk_ir::FloatTy::F128 => from_bytes!(f128, u128, it),
+    })
+}
+
+/// Rounds a quad to the float type `ty`, returning its bytes.
+fn quad_to_bytes(ty: chalk_ir::FloatTy, it: f128) -> Vec<u8> {
+    fn narrow<F: Float>(it: f128) -> u128
+    where
+        f128: FloatConvert<F>,
+    {
+        it.convert(&mut false).value.to_bits()
+    }
+    let (bits, size) = match ty {
+        chalk_ir::FloatTy::F16 => (narrow::<f16>(it), 2),
+        chalk_ir::FloatTy::F32 => (narrow::<Single>(it), 4),
+        chalk_ir::FloatTy::F64 => (narrow::<Double>(it), 8),
+        chalk_ir::FloatTy::F128 => (it.to_bits(), 16),
+    };
+    bits.to_le_bytes()[0..size].to_vec()
+}
+
+/// Converts an integer to the float type `ty` with a single rounding, returning its bytes.
+fn int_to_float_bytes(ty: chalk_ir::FloatTy, it: i128, is_signed: bool) -> Vec<u8> {
+    fn convert<F: Float>(it: i128, is_signed: bool) -> u128 {
+        let it = if is_signed { F::from_i128(it) } else { F::from_u128(it as u128) };
+        it.value.to_bits()
+    }
+    let (bits, size) = match ty {
+        chalk_ir::FloatTy::F16 => (convert::<f16>(it, is_signed), 2),
+        chalk_ir::FloatTy::F32 => (convert::<Single>(it, is_signed), 4),
+        chalk_ir::FloatTy::F64 => (convert::<Double>(it, is_signed), 8),
+        chalk_ir::FloatTy::F128 => (convert::<f128>(it, is_signed), 16),
+    };
+    bits.to_le_bytes()[0..size].to_vec()
+}
+
 macro_rules! not_supported {
     ($it: expr) => {
         return Err(MirEvalError::NotSupported(format!($it)))
@@ -183,6 +269,7 @@ pub struct Evaluator<'a> {
     /// later.
     unused_locals_store: RefCell<FxHashMap<DefWithBodyId, Vec<Locals>>>,
     cached_ptr_size: usize,
+    address_space: AddressSpace,
     cached_fn_trait_func: Option<FunctionId>,
     cached_fn_mut_trait_func: Option<FunctionId>,
     cached_fn_once_trait_func: Option<FunctionId>,
@@ -195,6 +282,10 @@ pub struct Evaluator<'a> {
     stack_depth_limit: usize,
     /// Maximum count of bytes that heap and stack can grow
     memory_limit: usize,
+    /// Gets control before each step, if we are running under a debugger
+    debugger: Option<Box<dyn MirDebugger + 'a>>,
+    /// The state of the undefined behavior checks, if they are enabled
+    ub_checks: Option<UbChecks>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -276,41 +367,46 @@ impl IntervalOrOwned {
     }
 }
 
-#[cfg(target_pointer_width = "64")]
-const STACK_OFFSET: usize = 1 << 60;
-#[cfg(target_pointer_width = "64")]
-const HEAP_OFFSET: usize = 1 << 59;
+/// Where the stack and the heap start in the target's address space. Addresses below the heap are
+/// invalid, they come from casting integers to pointers.
+#[derive(Debug, Clone, Copy)]
+struct AddressSpace {
+    stack_offset: usize,
+    heap_offset: usize,
+}
 
-#[cfg(target_pointer_width = "32")]
-const STACK_OFFSET: usize = 1 << 30;
-#[cfg(target_pointer_width = "32")]
-const HEAP_OFFSET: usize = 1 << 29;
+impl AddressSpace {
+    fn new(ptr_size: usize) -> AddressSpace {
+        let bits = ptr_size.min(size_of::<usize>()) * 8;
+        if bits == 64 {
+            AddressSpace { stack_offset: 1 << 60, heap_offset: 1 << 59 }
+        } else {
+            AddressSpace { stack_offset: 1 << (bits - 2), heap_offset: 1 << (bits - 3) }
+        }
+    }
 
-impl Address {
-    #[allow(clippy::double_parens)]
-    fn from_bytes(it: &[u8]) -> Result<Self> {
-        Ok(Address::from_usize(from_bytes!(usize, it)))
+    /// The heap may not grow into the stack.
+    fn heap_size(&self) -> usize {
+        self.stack_offset - self.heap_offset
     }
+}
 
-    fn from_usize(it: usize) -> Self {
-        if it > STACK_OFFSET {
-            Stack(it - STACK_OFFSET)
-        } else if it > HEAP_OFFSET {
-            Heap(it - HEAP_OFFSET)
+impl Address {
+    fn from_usize(it: usize, space: AddressSpace) -> Self {
+        if it > space.stack_offset {
+            Stack(it - space.stack_offset)
+        } else if it > space.heap_offset {
+            Heap(it - space.heap_offset)
         } else {
             Invalid(it)
         }
     }
 
-    fn to_bytes(&self) -> [u8; size_of::<usize>()] {
-        usize::to_le_bytes(self.to_usize())
-    }
-
-    fn to_usize(&self) -> usize {
+    fn to_usize(self, space: AddressSpace) -> usize {
         match self {
-            Stack(it) => *it + STACK_OFFSET,
-            Heap(it) => *it + HEAP_OFFSET,
-            Invalid(it) => *it,
+            Stack(it) => it + space.stack_offset,
+            Heap(it) => it + space.heap_offset,
+            Invalid(it) => it,
         }
     }
 
@@ -332,8 +428,8 @@ pub enum MirEvalError {
     ConstEvalError(String, Box<ConstEvalError>),
     LayoutError(LayoutError, Ty),
     TargetDataLayoutNotAvailable(Arc<str>),
-    /// Means that code had undefined behavior. We don't try to actively detect UB, but if it was detected
-    /// then use this type of error.
+    /// Means that code had undefined behavior. UB is only actively detected when the checks in
+    /// [`ub_checks`] are enabled, otherwise this is only reported for UB we happen to run into.
     UndefinedBehavior(String),
     Panic(String),
     // FIXME: This should be folded into ConstEvalError?
@@ -351,6 +447,8 @@ pub enum MirEvalError {
     CoerceUnsizedError(Ty),
     /// These should not occur, usually indicates a bug in mir lowering.
     InternalError(Box<str>),
+    /// The evaluation was stopped from the outside, e.g. by a debugger.
+    Terminated,
 }
 
 impl MirEvalError {
@@ -380,31 +478,8 @@ impl MirEvalError {
                         writeln!(f, "In {closure:?}")?;
                     }
                 }
-                let source_map = db.body_with_source_map(*def).1;
-                let span: InFile<SyntaxNodePtr> = match span {
-                    MirSpan::ExprId(e) => match source_map.expr_syntax(*e) {
-                        Ok(s) => s.map(|it| it.into()),
-                        Err(_) => continue,
-                    },
-                    MirSpan::PatId(p) => match source_map.pat_syntax(*p) {
-                        Ok(s) => s.map(|it| it.syntax_node_ptr()),
-                        Err(_) => continue,
-                    },
-                    MirSpan::BindingId(b) => {
-                        match source_map
-                            .patterns_for_binding(*b)
-                            .iter()
-                            .find_map(|p| source_map.pat_syntax(*p).ok())
-                        {
-                            Some(s) => s.map(|it| it.syntax_node_ptr()),
-                            None => continue,
-                        }
-                    }
-                    MirSpan::SelfParam => match source_map.self_param_syntax() {
-                        Some(s) => s.map(|it| it.syntax_node_ptr()),
-                        None => continue,
-                    },
-                    MirSpan::Unknown => continue,
+                let Some(span) = mir_span_syntax(db, *def, *span) else {
+                    continue;
                 };
                 let file_id = span.file_id.original_file(db.upcast());
                 let text_range = span.value.text_range();
@@ -468,7 +543,8 @@ impl MirEvalError {
             | MirEvalError::StackOverflow
             | MirEvalError::CoerceUnsizedError(_)
             | MirEvalError::InternalError(_)
-            | MirEvalError::InvalidVTableId(_) => writeln!(f, "{err:?}")?,
+            | MirEvalError::InvalidVTableId(_)
+            | MirEvalError::Terminated => writeln!(f, "{err:?}")?,
         }
         Ok(())
     }
@@ -504,6 +580,7 @@ impl std::fmt::Debug for MirEvalError {
             Self::TypeIsUnsized(ty, it) => write!(f, "{ty:?} is unsized. {it} should be sized."),
             Self::ExecutionLimitExceeded => write!(f, "execution limit exceeded"),
             Self::StackOverflow => write!(f, "stack overflow"),
+            Self::Terminated => write!(f, "evaluation terminated"),
             Self::MirLowerError(arg0, arg1) => {
                 f.debug_tuple("MirLowerError").field(arg0).field(arg1).finish()
             }
@@ -589,30 +666,45 @@ pub fn interpret_mir(
     assert_placeholder_ty_is_unused: bool,
     trait_env: Option<Arc<TraitEnvironment>>,
 ) -> Result<(Result<Const>, MirOutput)> {
-    let ty = body.locals[return_slot()].ty.clone();
-    let mut evaluator = Evaluator::new(db, body.owner, assert_placeholder_ty_is_unused, trait_env)?;
-    let it: Result<Const> = (|| {
-        if evaluator.ptr_size() != size_of::<usize>() {
-            not_supported!("targets with different pointer size from host");
-        }
-        let interval = evaluator.interpret_mir(body.clone(), None.into_iter())?;
-        let bytes = interval.get(&evaluator)?;
-        let mut memory_map = evaluator.create_memory_map(
-            bytes,
-            &ty,
-            &Locals { ptr: ArenaMap::new(), body, drop_flags: DropFlags::default() },
-        )?;
-        let bytes = bytes.into();
-        let memory_map = if memory_map.memory.is_empty() && evaluator.vtable_map.is_empty() {
-            MemoryMap::Empty
-        } else {
-            memory_map.vtable = mem::take(&mut evaluator.vtable_map);
-            memory_map.vtable.shrink_to_fit();
-            MemoryMap::Complex(Box::new(memory_map))
-        };
-        Ok(intern_const_scalar(ConstScalar::Bytes(bytes, memory_map), ty))
-    })();
-    Ok((it, MirOutput { stdout: evaluator.stdout, stderr: evaluator.stderr }))
+    let evaluator = Evaluator::new(db, body.owner, assert_placeholder_ty_is_unused, trait_env)?;
+    evaluator.run_to_const(body)
+}
+
+/// Like [`interpret_mir`], but hands control to `debugger` before running each statement and
+/// terminator.
+pub fn debug_mir<'a>(
+    db: &'a dyn HirDatabase,
+    body: Arc<MirBody>,
+    debugger: &'a mut dyn MirDebugger,
+) -> Result<(Result<Const>, MirOutput)> {
+    interpret_mir_with(db, body, Some(debugger), false)
+}
+
+/// Like [`interpret_mir`], with the optional features of the interpreter: `debugger` gets control
+/// before each step, and `check_ub` enables the detection of undefined behavior of [`ub_checks`].
+pub fn interpret_mir_with<'a>(
+    db: &'a dyn HirDatabase,
+    body: Arc<MirBody>,
+    debugger: Option<&'a mut dyn MirDebugger>,
+    check_ub: bool,
+) -> Result<(Result<Const>, MirOutput)> {
+    let mut evaluator = Evaluator::new(db, body.owner, false, None)?;
+    evaluator.debugger = debugger.map(|it| Box::new(it) as Box<dyn MirDebugger>);
+    if check_ub {
+        evaluator.ub_checks = Some(UbChecks::default());
+    }
+    evaluator.run_to_const(body)
+}
+
+/// Like [`interpret_mir`], but gives up after `execution_limit` steps, instead of the default limit.
+pub fn interpret_mir_with_limit(
+    db: &dyn HirDatabase,
+    body: Arc<MirBody>,
+    execution_limit: usize,
+) -> Result<(Result<Const>, MirOutput)> {
+    let mut evaluator = Evaluator::new(db, body.owner, false, None)?;
+    evaluator.execution_limit = execution_limit;
+    evaluator.run_to_const(body)
 }
 
 #[cfg(test)]
@@ -621,18 +713,24 @@ const EXECUTION_LIMIT: usize = 100_000;
 const EXECUTION_LIMIT: usize = 10_000_000;
 
 impl Evaluator<'_> {
-    pub fn new(
-        db: &dyn HirDatabase,
+    pub fn new<'a>(
+        db: &'a dyn HirDatabase,
         owner: DefWithBodyId,
         assert_placeholder_ty_is_unused: bool,
         trait_env: Option<Arc<TraitEnvironment>>,
-    ) -> Result<Evaluator<'_>> {
+    ) -> Result<Evaluator<'a>> {
         let crate_id = owner.module(db.upcast()).krate();
         let target_data_layout = match db.target_data_layout(crate_id) {
             Ok(target_data_layout) => target_data_layout,
             Err(e) => return Err(MirEvalError::TargetDataLayoutNotAvailable(e)),
         };
+        // FIXME: Values, like consts, are always stored in little endian. Refuse to evaluate on big
+        // endian targets instead of giving wrong results for code that observes the byte order.
+        if target_data_layout.endian == Endian::Big {
+            return Err(MirEvalError::NotSupported("evaluation for big endian targets".to_owned()));
+        }
         let cached_ptr_size = target_data_layout.pointer_size.bytes_usize();
+        let address_space = AddressSpace::new(cached_ptr_size);
         Ok(Evaluator {
             target_data_layout,
             stack: vec![0],
@@ -650,13 +748,17 @@ impl Evaluator<'_> {
             assert_placeholder_ty_is_unused,
             stack_depth_limit: 100,
             execution_limit: EXECUTION_LIMIT,
-            memory_limit: 1_000_000_000, // 2GB, 1GB for stack and 1GB for heap
+            // 2GB, 1GB for stack and 1GB for heap, less if the target's address space is smaller
+            memory_limit: address_space.heap_size().min(1_000_000_000),
+            debugger: None,
+            ub_checks: None,
             layout_cache: RefCell::new(Default::default()),
             projected_ty_cache: RefCell::new(Default::default()),
             not_special_fn_cache: RefCell::new(Default::default()),
             mir_or_dyn_index_cache: RefCell::new(Default::default()),
             unused_locals_store: RefCell::new(Default::default()),
             cached_ptr_size,
+            address_space,
             cached_fn_trait_func: db
                 .lang_item(crate_id, LangItem::Fn)
                 .and_then(|x| x.as_trait())
@@ -678,6 +780,29 @@ impl Evaluator<'_> {
         })
     }
 
+    fn run_to_const(mut self, body: Arc<MirBody>) -> Result<(Result<Const>, MirOutput)> {
+        let ty = body.locals[return_slot()].ty.clone();
+        let it: Result<Const> = (|| {
+            let interval = self.interpret_mir(body.clone(), None.into_iter())?;
+            let bytes = interval.get(&self)?;
+            let mut memory_map = self.create_memory_map(
+                bytes,
+                &ty,
+                &Locals { ptr: ArenaMap::new(), body, drop_flags: DropFlags::default() },
+            )?;
+            let bytes = bytes.into();
+            let memory_map = if memory_map.memory.is_empty() && self.vtable_map.is_empty() {
+                MemoryMap::Empty
+            } else {
+                memory_map.vtable = mem::take(&mut self.vtable_map);
+                memory_map.vtable.shrink_to_fit();
+                MemoryMap::Complex(Box::new(memory_map))
+            };
+            Ok(intern_const_scalar(ConstScalar::Bytes(bytes, memory_map), ty))
+        })();
+        Ok((it, MirOutput { stdout: self.stdout, stderr: self.stderr }))
+    }
+
     fn place_addr(&self, p: &Place, locals: &Locals) -> Result<Address> {
         Ok(self.place_addr_and_ty_and_metadata(p, locals)?.0)
     }
@@ -698,6 +823,29 @@ impl Evaluator<'_> {
         self.cached_ptr_size
     }
 
+    /// Encodes a `usize` in the target's pointer width.
+    fn usize_to_bytes(&self, it: usize) -> Vec<u8> {
+        let mut bytes = (it as u128).to_le_bytes().to_vec();
+        bytes.truncate(self.ptr_size());
+        bytes
+    }
+
+    fn address_from_bytes(&self, it: &[u8]) -> Result<Address> {
+        Ok(Address::from_usize(usize_from_bytes(it)?, self.address_space))
+    }
+
+    fn address_from_usize(&self, it: usize) -> Address {
+        Address::from_usize(it, self.address_space)
+    }
+
+    fn address_to_usize(&self, addr: Address) -> usize {
+        addr.to_usize(self.address_space)
+    }
+
+    fn address_to_bytes(&self, addr: Address) -> Vec<u8> {
+        self.usize_to_bytes(self.address_to_usize(addr))
+    }
+
     fn projected_ty(&self, ty: Ty, proj: PlaceElem) -> Ty {
         let pair = (ty, proj);
         if let Some(r) = self.projected_ty_cache.borrow().get(&pair) {
@@ -747,7 +895,8 @@ impl Evaluator<'_> {
                         None
                     };
                     let it = from_bytes!(usize, self.read_memory(addr, self.ptr_size())?);
-                    addr = Address::from_usize(it);
+                    addr = self.address_from_usize(it);
+                    self.check_deref(addr, &ty, metadata.as_ref(), locals)?;
                 }
                 ProjectionElem::Index(op) => {
                     let offset = from_bytes!(
@@ -769,7 +918,7 @@ impl Evaluator<'_> {
                                 }
                             },
                             TyKind::Slice(_) => match metadata {
-                                Some(it) => from_bytes!(u64, it.get(self)?),
+                                Some(it) => from_bytes!(usize, it.get(self)?) as u64,
                                 None => not_supported!("slice place without metadata"),
                             },
                             _ => not_supported!("bad type for const index"),
@@ -790,9 +939,9 @@ impl Evaluator<'_> {
                     };
                     metadata = match metadata {
                         Some(it) => {
-                            let prev_len = from_bytes!(u64, it.get(self)?);
+                            let prev_len = from_bytes!(usize, it.get(self)?);
                             Some(IntervalOrOwned::Owned(
-                                (prev_len - from - to).to_le_bytes().to_vec(),
+                                self.usize_to_bytes(prev_len - from as usize - to as usize),
                             ))
                         }
                         None => None,
@@ -811,7 +960,10 @@ impl Evaluator<'_> {
                     let layout = self.layout(&prev_ty)?;
                     let offset = layout.fields.offset(f.index as usize).bytes_usize();
                     addr = addr.offset(offset);
-                    metadata = None; // tuple field is always sized FIXME: This is wrong, the tail can be unsized
+                    // The tail of a tuple can be unsized, and then shares the tuple's metadata
+                    if self.size_align_of(&ty, locals)?.is_some() {
+                        metadata = None;
+                    }
                 }
                 ProjectionElem::Field(Either::Left(f)) => {
                     let layout = self.layout(&prev_ty)?;
@@ -915,6 +1067,9 @@ impl Evaluator<'_> {
                         return Err(MirEvalError::ExecutionLimitExceeded);
                     }
                     for statement in &current_block.statements {
+                        if self.debugger.is_some() {
+                            self.debug_step(locals, my_stack_frame.span, statement.span)?;
+                        }
                         match &statement.kind {
                             StatementKind::Assign(l, r) => {
                                 let addr = self.place_addr(l, locals)?;
@@ -932,6 +1087,9 @@ impl Evaluator<'_> {
                     let Some(terminator) = current_block.terminator.as_ref() else {
                         not_supported!("block without terminator");
                     };
+                    if self.debugger.is_some() {
+                        self.debug_step(locals, my_stack_frame.span, terminator.span)?;
+                    }
                     match &terminator.kind {
                         TerminatorKind::Goto { target } => {
                             current_block_idx = *target;
@@ -1027,6 +1185,13 @@ impl Evaluator<'_> {
                 }
             };
             let return_interval = my_stack_frame.locals.ptr[return_slot()];
+            if let Some(checks) = &mut self.ub_checks {
+                // Temporaries may outlive their frame, since we don't do const promotion
+                let body = &my_stack_frame.locals.body;
+                for &local in body.param_locals.iter().chain(body.binding_locals.values()) {
+                    checks.free_local(my_stack_frame.locals.ptr[local].addr);
+                }
+            }
             self.unused_locals_store
                 .borrow_mut()
                 .entry(my_stack_frame.locals.body.owner)
@@ -1110,6 +1275,9 @@ impl Evaluator<'_> {
                 let my_ptr = stack_ptr;
                 stack_ptr += size;
                 locals.ptr.insert(id, Interval { addr: Stack(my_ptr), size });
+                if let Some(checks) = &mut self.ub_checks {
+                    checks.allocate(Stack(my_ptr), size);
+                }
             }
             stack_ptr - self.stack.len()
         };
@@ -1129,7 +1297,7 @@ impl Evaluator<'_> {
             Rvalue::Use(it) => Borrowed(self.eval_operand(it, locals)?),
             Rvalue::Ref(_, p) => {
                 let (addr, _, metadata) = self.place_addr_and_ty_and_metadata(p, locals)?;
-                let mut r = addr.to_bytes().to_vec();
+                let mut r = self.address_to_bytes(addr);
                 if let Some(metadata) = metadata {
                     r.extend(metadata.get(self)?);
                 }
@@ -1152,7 +1320,7 @@ impl Evaluator<'_> {
                 while let TyKind::Ref(_, _, z) = ty.kind(Interner) {
                     ty = z.clone();
                     let size = self.size_of_sized(&ty, locals, "operand of unary op")?;
-                    c = self.read_memory(Address::from_bytes(c)?, size)?;
+                    c = self.read_memory(self.address_from_bytes(c)?, size)?;
                 }
                 if let TyKind::Scalar(chalk_ir::Scalar::Float(f)) = ty.kind(Interner) {
                     match f {
@@ -1214,14 +1382,14 @@ impl Evaluator<'_> {
                         }
                         lc = &lc[..self.ptr_size()];
                         rc = &rc[..self.ptr_size()];
-                        lc = self.read_memory(Address::from_bytes(lc)?, ls)?;
-                        rc = self.read_memory(Address::from_bytes(rc)?, ls)?;
+                        lc = self.read_memory(self.address_from_bytes(lc)?, ls)?;
+                        rc = self.read_memory(self.address_from_bytes(rc)?, ls)?;
                         break 'binary_op Owned(vec![u8::from(lc == rc)]);
                     } else {
                         self.size_of_sized(&ty, locals, "operand of binary op")?
                     };
-                    lc = self.read_memory(Address::from_bytes(lc)?, size)?;
-                    rc = self.read_memory(Address::from_bytes(rc)?, size)?;
+                    lc = self.read_memory(self.address_from_bytes(lc)?, size)?;
+                    rc = self.read_memory(self.address_from_bytes(rc)?, size)?;
                 }
                 if let TyKind::Scalar(chalk_ir::Scalar::Float(f)) = ty.kind(Interner) {
                     match f {
@@ -1410,7 +1578,9 @@ impl Evaluator<'_> {
             }
             Rvalue::Discriminant(p) => {
                 let ty = self.place_ty(p, locals)?;
-                let bytes = self.eval_place(p, locals)?.get(self)?;
+                let interval = self.eval_place(p, locals)?;
+                self.check_value(interval.addr, &ty, locals)?;
+                let bytes = interval.get(self)?;
                 let result = self.compute_discriminant(ty, bytes)?;
                 Owned(result.to_le_bytes().to_vec())
             }
@@ -1429,7 +1599,7 @@ impl Evaluator<'_> {
                     not_supported!("unsized box initialization");
                 };
                 let addr = self.heap_allocate(size, align)?;
-                Owned(addr.to_bytes().to_vec())
+                Owned(self.address_to_bytes(addr))
             }
             Rvalue::CopyForDeref(_) => not_supported!("copy for deref"),
             Rvalue::Aggregate(kind, values) => {
@@ -1518,7 +1688,14 @@ impl Evaluator<'_> {
                         Borrowed(self.eval_operand(operand, locals)?.slice(0..self.ptr_size()))
                     }
                 },
-                CastKind::DynStar => not_supported!("dyn star cast"),
+                CastKind::DynStar => {
+                    // A `dyn*` is a pointer sized value followed by the vtable of its type.
+                    let current_ty = self.operand_ty(operand, locals)?;
+                    let mut value = self.eval_operand(operand, locals)?.get(self)?.to_vec();
+                    let id = self.vtable_map.id(current_ty);
+                    value.extend(self.usize_to_bytes(id));
+                    Owned(value)
+                }
                 CastKind::IntToInt
                 | CastKind::PtrToPtr
                 | CastKind::PointerExposeAddress
@@ -1538,20 +1715,8 @@ impl Evaluator<'_> {
                     let TyKind::Scalar(chalk_ir::Scalar::Float(ty)) = ty.kind(Interner) else {
                         not_supported!("invalid float to int cast");
                     };
-                    let value = self.eval_operand(operand, locals)?.get(self)?;
-                    let value = match ty {
-                        chalk_ir::FloatTy::F32 => {
-                            let value = value.try_into().unwrap();
-                            f32::from_le_bytes(value) as f64
-                        }
-                        chalk_ir::FloatTy::F64 => {
-                            let value = value.try_into().unwrap();
-                            f64::from_le_bytes(value)
-                        }
-                        chalk_ir::FloatTy::F16 | chalk_ir::FloatTy::F128 => {
-                            not_supported!("unstable floating point type f16 and f128");
-                        }
-                    };
+                    let value =
+                        quad_from_bytes(*ty, self.eval_operand(operand, locals)?.get(self)?)?;
                     let is_signed = matches!(
                         target_ty.kind(Interner),
                         TyKind::Scalar(chalk_ir::Scalar::Int(_))
@@ -1559,49 +1724,27 @@ impl Evaluator<'_> {
                     let dest_size =
                         self.size_of_sized(target_ty, locals, "destination of float to int cast")?;
                     let dest_bits = dest_size * 8;
-                    let (max, min) = if dest_bits == 128 {
-                        (i128::MAX, i128::MIN)
-                    } else if is_signed {
-                        let max = 1i128 << (dest_bits - 1);
-                        (max - 1, -max)
+                    // Both saturate on overflow and map NaN to zero, like `as` does.
+                    let result = if is_signed {
+                        value.to_i128(dest_bits).value.to_le_bytes()
                     } else {
-                        (1i128 << dest_bits, 0)
+                        value.to_u128(dest_bits).value.to_le_bytes()
                     };
-                    let value = (value as i128).min(max).max(min);
-                    let result = value.to_le_bytes();
                     Owned(result[0..dest_size].to_vec())
                 }
                 CastKind::FloatToFloat => {
                     let ty = self.operand_ty(operand, locals)?;
                     let TyKind::Scalar(chalk_ir::Scalar::Float(ty)) = ty.kind(Interner) else {
-                        not_supported!("invalid float to int cast");
-                    };
-                    let value = self.eval_operand(operand, locals)?.get(self)?;
-                    let value = match ty {
-                        chalk_ir::FloatTy::F32 => {
-                            let value = value.try_into().unwrap();
-                            f32::from_le_bytes(value) as f64
-                        }
-                        chalk_ir::FloatTy::F64 => {
-                            let value = value.try_into().unwrap();
-                            f64::from_le_bytes(value)
-                        }
-                        chalk_ir::FloatTy::F16 | chalk_ir::FloatTy::F128 => {
-                            not_supported!("unstable floating point type f16 and f128");
-                        }
+                        not_supported!("invalid float to float cast");
                     };
+                    let value =
+                        quad_from_bytes(*ty, self.eval_operand(operand, locals)?.get(self)?)?;
                     let TyKind::Scalar(chalk_ir::Scalar::Float(target_ty)) =
                         target_ty.kind(Interner)
                     else {
                         not_supported!("invalid float to float cast");
                     };
-                    match target_ty {
-                        chalk_ir::FloatTy::F32 => Owned((value as f32).to_le_bytes().to_vec()),
-                        chalk_ir::FloatTy::F64 => Owned((value as f64).to_le_bytes().to_vec()),
-                        chalk_ir::FloatTy::F16 | chalk_ir::FloatTy::F128 => {
-                            not_supported!("unstable floating point type f16 and f128");
-                        }
-                    }
+                    Owned(quad_to_bytes(*target_ty, value))
                 }
                 CastKind::IntToFloat => {
                     let current_ty = self.operand_ty(operand, locals)?;
@@ -1616,15 +1759,13 @@ impl Evaluator<'_> {
                     else {
                         not_supported!("invalid int to float cast");
                     };
-                    match target_ty {
-                        chalk_ir::FloatTy::F32 => Owned((value as f32).to_le_bytes().to_vec()),
-                        chalk_ir::FloatTy::F64 => Owned((value as f64).to_le_bytes().to_vec()),
-                        chalk_ir::FloatTy::F16 | chalk_ir::FloatTy::F128 => {
-                            not_supported!("unstable floating point type f16 and f128");
-                        }
-                    }
+                    Owned(int_to_float_bytes(*target_ty, value, is_signed))
+                }
+                CastKind::FnPtrToPtr => {
+                    // Function pointers are represented by their vtable id, which we keep as the
+                    // address of the pointer.
+                    Borrowed(self.eval_operand(operand, locals)?)
                 }
-                CastKind::FnPtrToPtr => not_supported!("fn ptr to ptr cast"),
             },
             Rvalue::ThreadLocalRef(n)
             | Rvalue::AddressOf(n)
@@ -1674,26 +1815,33 @@ impl Evaluator<'_> {
         }
     }
 
-    fn coerce_unsized_look_through_fields<T>(
-        &self,
-        ty: &Ty,
-        goal: impl Fn(&TyKind) -> Option<T>,
-    ) -> Result<T> {
-        let kind = ty.kind(Interner);
-        if let Some(it) = goal(kind) {
-            return Ok(it);
-        }
-        if let TyKind::Adt(id, subst) = kind {
-            if let AdtId::StructId(struct_id) = id.0 {
+    /// Finds the pointees of the pointers being coerced, looking through the field that changes
+    /// in `CoerceUnsized` structs like `Box` or `Rc`.
+    fn coerce_unsized_pointees(&self, current_ty: &Ty, target_ty: &Ty) -> Result<(Ty, Ty)> {
+        match (current_ty.kind(Interner), target_ty.kind(Interner)) {
+            (
+                TyKind::Raw(_, current) | TyKind::Ref(_, _, current),
+                TyKind::Raw(_, target) | TyKind::Ref(_, _, target),
+            ) => Ok((current.clone(), target.clone())),
+            (TyKind::Adt(current_id, current_subst), TyKind::Adt(target_id, target_subst))
+                if current_id == target_id =>
+            {
+                let AdtId::StructId(struct_id) = current_id.0 else {
+                    return Err(MirEvalError::CoerceUnsizedError(target_ty.clone()));
+                };
                 let field_types = self.db.field_types(struct_id.into());
-                if let Some(ty) =
-                    field_types.iter().last().map(|it| it.1.clone().substitute(Interner, subst))
-                {
-                    return self.coerce_unsized_look_through_fields(&ty, goal);
+                let field = field_types.iter().find_map(|(_, ty)| {
+                    let current = ty.clone().substitute(Interner, current_subst);
+                    let target = ty.clone().substitute(Interner, target_subst);
+                    (current != target).then_some((current, target))
+                });
+                match field {
+                    Some((current, target)) => self.coerce_unsized_pointees(&current, &target),
+                    None => Err(MirEvalError::CoerceUnsizedError(target_ty.clone())),
                 }
             }
+            _ => Err(MirEvalError::CoerceUnsizedError(target_ty.clone())),
         }
-        Err(MirEvalError::CoerceUnsizedError(ty.clone()))
     }
 
     fn coerce_unsized(
@@ -1702,15 +1850,7 @@ impl Evaluator<'_> {
         current_ty: &Ty,
         target_ty: &Ty,
     ) -> Result<IntervalOrOwned> {
-        fn for_ptr(it: &TyKind) -> Option<Ty> {
-            match it {
-                TyKind::Raw(_, ty) | TyKind::Ref(_, _, ty) => Some(ty.clone()),
-                _ => None,
-            }
-        }
-        let target_ty = self.coerce_unsized_look_through_fields(target_ty, for_ptr)?;
-        let current_ty = self.coerce_unsized_look_through_fields(current_ty, for_ptr)?;
-
+        let (current_ty, target_ty) = self.coerce_unsized_pointees(current_ty, target_ty)?;
         self.unsizing_ptr_from_addr(target_ty, current_ty, addr)
     }
 
@@ -1734,35 +1874,35 @@ impl Evaluator<'_> {
                     let mut r = Vec::with_capacity(16);
                     let addr = addr.get(self)?;
                     r.extend(addr.iter().copied());
-                    r.extend(len.to_le_bytes());
+                    r.extend(self.usize_to_bytes(len));
                     Owned(r)
                 }
                 t => {
                     not_supported!("slice unsizing from non array type {t:?}")
                 }
             },
-            TyKind::Dyn(_) => {
-                let vtable = self.vtable_map.id(current_ty);
-                let mut r = Vec::with_capacity(16);
-                let addr = addr.get(self)?;
-                r.extend(addr.iter().copied());
-                r.extend(vtable.to_le_bytes());
-                Owned(r)
-            }
+            TyKind::Dyn(_) => match &current_ty.kind(Interner) {
+                // Vtables are keyed by the concrete type, so upcasting keeps the metadata as is.
+                TyKind::Dyn(_) => Borrowed(addr),
+                _ => {
+                    let vtable = self.vtable_map.id(current_ty);
+                    let mut r = Vec::with_capacity(16);
+                    let addr = addr.get(self)?;
+                    r.extend(addr.iter().copied());
+                    r.extend(self.usize_to_bytes(vtable));
+                    Owned(r)
+                }
+            },
             TyKind::Adt(id, target_subst) => match &current_ty.kind(Interner) {
-                TyKind::Adt(current_id, current_subst) => {
-                    if id != current_id {
-                        not_supported!("unsizing struct with different type");
-                    }
-                    let id = match id.0 {
-                        AdtId::StructId(s) => s,
-                        AdtId::UnionId(_) => not_supported!("unsizing unions"),
-                        AdtId::EnumId(_) => not_supported!("unsizing enums"),
+                TyKind::Adt(current_id, current_subst) if id == current_id => {
+                    // Only structs can be unsized, through their last field.
+                    let AdtId::StructId(id) = id.0 else {
+                        return Err(MirEvalError::CoerceUnsizedError(target_ty));
                     };
                     let Some((last_field, _)) =
                         self.db.struct_data(id).variant_data.fields().iter().next_back()
                     else {
-                        not_supported!("unsizing struct without field");
+                        return Err(MirEvalError::CoerceUnsizedError(target_ty));
                     };
                     let target_last_field = self.db.field_types(id.into())[last_field]
                         .clone()
@@ -1776,9 +1916,21 @@ impl Evaluator<'_> {
                         addr,
                     );
                 }
-                _ => not_supported!("unsizing struct with non adt type"),
+                _ => return Err(MirEvalError::CoerceUnsizedError(target_ty)),
+            },
+            TyKind::Tuple(_, target_subst) => match &current_ty.kind(Interner) {
+                TyKind::Tuple(len, current_subst) if *len > 0 => {
+                    let target_last = target_subst.at(Interner, len - 1).assert_ty_ref(Interner);
+                    let current_last = current_subst.at(Interner, len - 1).assert_ty_ref(Interner);
+                    return self.unsizing_ptr_from_addr(
+                        target_last.clone(),
+                        current_last.clone(),
+                        addr,
+                    );
+                }
+                _ => return Err(MirEvalError::CoerceUnsizedError(target_ty)),
             },
-            _ => not_supported!("unknown unsized cast"),
+            _ => return Err(MirEvalError::CoerceUnsizedError(target_ty)),
         })
     }
 
@@ -1886,7 +2038,11 @@ impl Evaluator<'_> {
         Ok(match it {
             Operand::Copy(p) | Operand::Move(p) => {
                 locals.drop_flags.remove_place(p, &locals.body.projection_store);
-                self.eval_place(p, locals)?
+                let interval = self.eval_place(p, locals)?;
+                if self.ub_checks.is_some() {
+                    self.check_value(interval.addr, &self.place_ty(p, locals)?, locals)?;
+                }
+                interval
             }
             Operand::Static(st) => {
                 let addr = self.eval_static(*st, locals)?;
@@ -1932,7 +2088,7 @@ impl Evaluator<'_> {
         let patch_map = memory_map.transform_addresses(|b, align| {
             let addr = self.heap_allocate(b.len(), align)?;
             self.write_memory(addr, b)?;
-            Ok(addr.to_usize())
+            Ok(self.address_to_usize(addr))
         })?;
         let (size, align) = self.size_align_of(ty, locals)?.unwrap_or((v.len(), 1));
         let v: Cow<'_, [u8]> = if size != v.len() {
@@ -1990,6 +2146,9 @@ impl Evaluator<'_> {
     }
 
     fn write_memory_using_ref(&mut self, addr: Address, size: usize) -> Result<&mut [u8]> {
+        if let Some(checks) = &mut self.ub_checks {
+            checks.mark_init(addr, size);
+        }
         let (mem, pos) = match addr {
             Stack(it) => (&mut self.stack, it),
             Heap(it) => (&mut self.heap, it),
@@ -2024,6 +2183,9 @@ impl Evaluator<'_> {
         }
 
         let oob = || MirEvalError::UndefinedBehavior("out of bounds memory write".to_owned());
+        if let Some(checks) = &mut self.ub_checks {
+            checks.copy_init(addr, r.addr, r.size);
+        }
 
         match (addr, r.addr) {
             (Stack(dst), Stack(src)) => {
@@ -2033,7 +2195,7 @@ impl Evaluator<'_> {
                 self.stack.copy_within(src..src + r.size, dst)
             }
             (Heap(dst), Heap(src)) => {
-                if self.stack.len() < src + r.size || self.stack.len() < dst + r.size {
+                if self.heap.len() < src + r.size || self.heap.len() < dst + r.size {
                     return Err(oob());
                 }
                 self.heap.copy_within(src..src + r.size, dst)
@@ -2122,6 +2284,9 @@ impl Evaluator<'_> {
         }
         let pos = self.heap.len();
         self.heap.extend(iter::repeat(0).take(size));
+        if let Some(checks) = &mut self.ub_checks {
+            checks.allocate(Heap(pos), size);
+        }
         Ok(Address::Heap(pos))
     }
 
@@ -2163,7 +2328,7 @@ impl Evaluator<'_> {
                             let addr_usize = from_bytes!(usize, bytes);
                             mm.insert(
                                 addr_usize,
-                                this.read_memory(Address::from_usize(addr_usize), size)?.into(),
+                                this.read_memory(this.address_from_usize(addr_usize), size)?.into(),
                             )
                         }
                         None => {
@@ -2187,9 +2352,9 @@ impl Evaluator<'_> {
                                 _ => from_bytes!(usize, meta),
                             };
                             let size = element_size * count;
-                            let addr = Address::from_bytes(addr)?;
+                            let addr = this.address_from_bytes(addr)?;
                             let b = this.read_memory(addr, size)?;
-                            mm.insert(addr.to_usize(), b.into());
+                            mm.insert(this.address_to_usize(addr), b.into());
                             if let Some(ty) = check_inner {
                                 for i in 0..count {
                                     let offset = element_size * i;
@@ -2318,13 +2483,13 @@ impl Evaluator<'_> {
                     Some(_) => {
                         let current = from_bytes!(usize, self.read_memory(addr, my_size)?);
                         if let Some(it) = patch_map.get(&current) {
-                            self.write_memory(addr, &it.to_le_bytes())?;
+                            self.write_memory(addr, &self.usize_to_bytes(*it))?;
                         }
                     }
                     None => {
                         let current = from_bytes!(usize, self.read_memory(addr, my_size / 2)?);
                         if let Some(it) = patch_map.get(&current) {
-                            self.write_memory(addr, &it.to_le_bytes())?;
+                            self.write_memory(addr, &self.usize_to_bytes(*it))?;
                         }
                     }
                 }
@@ -2332,7 +2497,7 @@ impl Evaluator<'_> {
             TyKind::Function(_) => {
                 let ty = ty_of_bytes(self.read_memory(addr, my_size)?)?.clone();
                 let new_id = self.vtable_map.id(ty);
-                self.write_memory(addr, &new_id.to_le_bytes())?;
+                self.write_memory(addr, &self.usize_to_bytes(new_id))?;
             }
             TyKind::Adt(id, subst) => match id.0 {
                 AdtId::StructId(s) => {
@@ -2458,7 +2623,7 @@ impl Evaluator<'_> {
             .map_err(|it| MirEvalError::MirLowerErrorForClosure(closure, it))?;
         let closure_data = if mir_body.locals[mir_body.param_locals[0]].ty.as_reference().is_some()
         {
-            closure_data.addr.to_bytes().to_vec()
+            self.address_to_bytes(closure_data.addr)
         } else {
             closure_data.get(self)?.to_owned()
         };
@@ -2658,10 +2823,11 @@ impl Evaluator<'_> {
         target_bb: Option<BasicBlockId>,
     ) -> Result<Option<StackFrame>> {
         Ok(if let Some(target_bb) = target_bb {
+            // The call site lives in the body of the caller
+            let span = (span, locals.body.owner);
             let (mut locals, prev_stack_ptr) =
                 self.create_locals_for_body(&mir_body, Some(destination))?;
             self.fill_locals_for_body(&mir_body, &mut locals, arg_bytes.into_iter())?;
-            let span = (span, locals.body.owner);
             Some(StackFrame { locals, destination: Some(target_bb), prev_stack_ptr, span })
         } else {
             let result = self.interpret_mir(mir_body, arg_bytes).map_err(|e| {
@@ -2699,7 +2865,7 @@ impl Evaluator<'_> {
                 func_ty = self.vtable_map.ty(id)?.clone();
             }
             let size = self.size_of_sized(&func_ty, locals, "self type of fn trait")?;
-            func_data = Interval { addr: Address::from_bytes(func_data.get(self)?)?, size };
+            func_data = Interval { addr: self.address_from_bytes(func_data.get(self)?)?, size };
         }
         match &func_ty.kind(Interner) {
             TyKind::FnDef(def, subst) => {
@@ -2769,10 +2935,14 @@ impl Evaluator<'_> {
                 not_supported!("unsized extern static");
             };
             let addr = self.heap_allocate(size, align)?;
+            // We don't link with anything, so extern statics are just zeroed
+            if let Some(checks) = &mut self.ub_checks {
+                checks.mark_init(addr, size);
+            }
             Interval::new(addr, size)
         };
         let addr = self.heap_allocate(self.ptr_size(), self.ptr_size())?;
-        self.write_memory(addr, &result.addr.to_bytes())?;
+        self.write_memory(addr, &self.address_to_bytes(result.addr))?;
         self.static_locations.insert(st, addr);
         Ok(addr)
     }
@@ -2833,7 +3003,7 @@ impl Evaluator<'_> {
                 body,
                 locals,
                 drop_fn,
-                iter::once(IntervalOrOwned::Owned(addr.to_bytes().to_vec())),
+                iter::once(IntervalOrOwned::Owned(self.address_to_bytes(addr))),
                 span,
                 Interval { addr: Address::Invalid(0), size: 0 },
                 None,
@@ -2938,19 +3108,22 @@ pub fn render_const_using_debug_impl(
     // FIXME: we should call the said function, but since its name is going to break in the next rustc version
     // and its ABI doesn't break yet, we put it in memory manually.
     let a2 = evaluator.heap_allocate(evaluator.ptr_size() * 2, evaluator.ptr_size())?;
-    evaluator.write_memory(a2, &data.addr.to_bytes())?;
+    evaluator.write_memory(a2, &evaluator.address_to_bytes(data.addr))?;
     let debug_fmt_fn_ptr = evaluator.vtable_map.id(TyKind::FnDef(
         db.intern_callable_def(debug_fmt_fn.into()).into(),
         Substitution::from1(Interner, c.data(Interner).ty.clone()),
     )
     .intern(Interner));
-    evaluator.write_memory(a2.offset(evaluator.ptr_size()), &debug_fmt_fn_ptr.to_le_bytes())?;
+    evaluator.write_memory(
+        a2.offset(evaluator.ptr_size()),
+        &evaluator.usize_to_bytes(debug_fmt_fn_ptr),
+    )?;
     // a3 = ::core::fmt::Arguments::new_v1(a1, a2)
     // FIXME: similarly, we should call function here, not directly working with memory.
     let a3 = evaluator.heap_allocate(evaluator.ptr_size() * 6, evaluator.ptr_size())?;
-    evaluator.write_memory(a3, &a1.to_bytes())?;
+    evaluator.write_memory(a3, &evaluator.address_to_bytes(a1))?;
     evaluator.write_memory(a3.offset(evaluator.ptr_size()), &[1])?;
-    evaluator.write_memory(a3.offset(2 * evaluator.ptr_size()), &a2.to_bytes())?;
+    evaluator.write_memory(a3.offset(2 * evaluator.ptr_size()), &evaluator.address_to_bytes(a2))?;
     evaluator.write_memory(a3.offset(3 * evaluator.ptr_size()), &[1])?;
     let Some(ValueNs::FunctionId(format_fn)) = resolver.resolve_path_in_value_ns_fully(
         db.upcast(),
@@ -2965,8 +3138,8 @@ pub fn render_const_using_debug_impl(
             .into_iter(),
     )?;
     let message_string = interval.get(&evaluator)?;
-    let addr =
-        Address::from_bytes(&message_string[evaluator.ptr_size()..2 * evaluator.ptr_size()])?;
+    let addr = evaluator
+        .address_from_bytes(&message_string[evaluator.ptr_size()..2 * evaluator.ptr_size()])?;
     let size = from_bytes!(usize, message_string[2 * evaluator.ptr_size()..]);
     Ok(std::string::String::from_utf8_lossy(evaluator.read_memory(addr, size)?).into_owned())
 }
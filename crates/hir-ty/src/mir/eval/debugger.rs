@@ -0,0 +1,201 @@
+//! Lets a debugger drive the interpreter step by step and inspect its state while it is paused.
+
+use std::ops::ControlFlow;
+
+use hir_def::{DefWithBodyId, Lookup};
+use hir_expand::files::FileRange;
+
+use crate::{
+    consteval::intern_const_scalar,
+    display::{DisplayTarget, HirDisplay},
+    mir::{eval::mir_span_syntax, MirSpan},
+    ConstScalar, MemoryMap,
+};
+
+use super::{Evaluator, Locals, MirEvalError, Result};
+
+/// Gets control before the interpreter runs each statement and terminator, see [`debug_mir`].
+///
+/// [`debug_mir`]: super::debug_mir
+pub trait MirDebugger {
+    /// Called before each step. Blocking here pauses the evaluation, and returning
+    /// `ControlFlow::Break` stops it with [`MirEvalError::Terminated`].
+    fn on_step(&mut self, state: &DebugState<'_, '_>) -> ControlFlow<()>;
+}
+
+impl<T: MirDebugger + ?Sized> MirDebugger for &mut T {
+    fn on_step(&mut self, state: &DebugState<'_, '_>) -> ControlFlow<()> {
+        (**self).on_step(state)
+    }
+}
+
+/// The state of the interpreter right before a step.
+pub struct DebugState<'a, 'db> {
+    evaluator: &'a Evaluator<'db>,
+    locals: &'a Locals,
+    /// The call site of the current frame, and the body containing it.
+    call_site: (MirSpan, DefWithBodyId),
+    span: MirSpan,
+}
+
+/// A frame of the call stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugFrame {
+    pub owner: DefWithBodyId,
+    /// The name of the function or other body the frame is running.
+    pub name: String,
+    /// The source of the step being run in this frame, i.e. the call site for all frames except
+    /// the innermost one.
+    pub range: Option<FileRange>,
+}
+
+/// A local variable of a frame, rendered with its type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugLocal {
+    pub name: String,
+    pub ty: String,
+    pub value: String,
+}
+
+impl DebugState<'_, '_> {
+    /// The number of frames in the call stack.
+    pub fn depth(&self) -> usize {
+        self.evaluator.code_stack.len() + 1
+    }
+
+    /// The source of the step that is about to run.
+    ///
+    /// This is `None` for steps without a source of their own, like the cleanup at the end of a
+    /// body, which would otherwise point at the whole body.
+    pub fn range(&self) -> Option<FileRange> {
+        let owner = self.locals.body.owner;
+        if self.span == MirSpan::ExprId(self.evaluator.db.body(owner).body_expr) {
+            return None;
+        }
+        self.span_range(owner, self.span)
+    }
+
+    /// The frames of the call stack, the innermost first.
+    pub fn frames(&self) -> Vec<DebugFrame> {
+        let mut frames = Vec::with_capacity(self.depth());
+        frames.push(self.frame(self.locals.body.owner, self.span));
+        let mut call_site = self.call_site;
+        for frame in self.evaluator.code_stack.iter().rev() {
+            frames.push(self.frame(frame.locals.body.owner, call_site.0));
+            call_site = frame.span;
+        }
+        frames
+    }
+
+    /// The named locals of the frame at `frame` in [`DebugState::frames`].
+    pub fn locals(&self, frame: usize) -> Vec<DebugLocal> {
+        let locals = match frame.checked_sub(1) {
+            None => self.locals,
+            Some(idx) => match self.evaluator.code_stack.iter().rev().nth(idx) {
+                Some(frame) => &frame.locals,
+                None => return Vec::new(),
+            },
+        };
+        let db = self.evaluator.db;
+        let display_target = DisplayTarget::from_crate(db, self.evaluator.crate_id);
+        let body = db.body(locals.body.owner);
+        let mut result: Vec<_> = locals
+            .body
+            .binding_locals
+            .iter()
+            .map(|(binding, &local)| {
+                let ty = &locals.body.locals[local].ty;
+                let value = match self.render_local(locals, local, display_target) {
+                    Ok(it) => it,
+                    Err(_) => "<unavailable>".to_owned(),
+                };
+                DebugLocal {
+                    name: body.bindings[binding]
+                        .name
+                        .display(db.upcast(), display_target.edition)
+                        .to_string(),
+                    ty: ty.display(db, display_target).to_string(),
+                    value,
+                }
+            })
+            .collect();
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+        result
+    }
+
+    fn render_local(
+        &self,
+        locals: &Locals,
+        local: super::LocalId,
+        display_target: DisplayTarget,
+    ) -> Result<String> {
+        let evaluator = self.evaluator;
+        let ty = &locals.body.locals[local].ty;
+        let interval = locals
+            .ptr
+            .get(local)
+            .ok_or_else(|| MirEvalError::InternalError("local without storage".into()))?;
+        let bytes = interval.get(evaluator)?;
+        let mut memory_map = evaluator.create_memory_map(bytes, ty, locals)?;
+        memory_map.vtable = evaluator.vtable_map.clone();
+        let konst = intern_const_scalar(
+            ConstScalar::Bytes(bytes.into(), MemoryMap::Complex(Box::new(memory_map))),
+            ty.clone(),
+        );
+        Ok(konst.display(evaluator.db, display_target).to_string())
+    }
+
+    fn frame(&self, owner: DefWithBodyId, span: MirSpan) -> DebugFrame {
+        let db = self.evaluator.db;
+        let edition = DisplayTarget::from_crate(db, self.evaluator.crate_id).edition;
+        let name = match owner {
+            DefWithBodyId::FunctionId(it) => {
+                db.function_data(it).name.display(db.upcast(), edition).to_string()
+            }
+            DefWithBodyId::StaticId(it) => {
+                db.static_data(it).name.display(db.upcast(), edition).to_string()
+            }
+            DefWithBodyId::ConstId(it) => match &db.const_data(it).name {
+                Some(name) => name.display(db.upcast(), edition).to_string(),
+                None => "_".to_owned(),
+            },
+            DefWithBodyId::VariantId(it) => {
+                let loc = it.lookup(db.upcast());
+                let variant = &db.enum_variant_data(it).name;
+                format!(
+                    "{}::{}",
+                    db.enum_data(loc.parent).name.display(db.upcast(), edition),
+                    variant.display(db.upcast(), edition)
+                )
+            }
+            DefWithBodyId::InTypeConstId(_) => "<const>".to_owned(),
+        };
+        DebugFrame { owner, name, range: self.span_range(owner, span) }
+    }
+
+    fn span_range(&self, owner: DefWithBodyId, span: MirSpan) -> Option<FileRange> {
+        let db = self.evaluator.db;
+        let syntax = mir_span_syntax(db, owner, span)?;
+        Some(syntax.map(|it| it.text_range()).original_node_file_range_rooted(db.upcast()))
+    }
+}
+
+impl Evaluator<'_> {
+    pub(super) fn debug_step(
+        &mut self,
+        locals: &Locals,
+        call_site: (MirSpan, DefWithBodyId),
+        span: MirSpan,
+    ) -> Result<()> {
+        let Some(mut debugger) = self.debugger.take() else {
+            return Ok(());
+        };
+        let state = DebugState { evaluator: self, locals, call_site, span };
+        let flow = debugger.on_step(&state);
+        self.debugger = Some(debugger);
+        match flow {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(()) => Err(MirEvalError::Terminated),
+        }
+    }
+}
@@ -11,16 +11,20 @@ use hir_def::{
 };
 use hir_expand::name::Name;
 use intern::{sym, Symbol};
+use rustc_apfloat::{
+    ieee::{Double, Half as f16, Quad as f128},
+    Float, FloatConvert, Round,
+};
 use stdx::never;
 
 use crate::{
     display::DisplayTarget,
     error_lifetime,
     mir::eval::{
-        pad16, Address, AdtId, Arc, BuiltinType, Evaluator, FunctionId, HasModule, HirDisplay,
-        InternedClosure, Interner, Interval, IntervalAndTy, IntervalOrOwned, ItemContainerId,
-        LangItem, Layout, Locals, Lookup, MirEvalError, MirSpan, Mutability, Result, Substitution,
-        Ty, TyBuilder, TyExt,
+        pad16, usize_from_bytes, Address, AdtId, Arc, BuiltinType, Evaluator, FunctionId,
+        HasModule, HirDisplay, InternedClosure, Interner, Interval, IntervalAndTy, IntervalOrOwned,
+        ItemContainerId, LangItem, Layout, Locals, Lookup, MirEvalError, MirSpan, Mutability,
+        Result, Substitution, Ty, TyBuilder, TyExt,
     },
     DropGlue,
 };
@@ -28,6 +32,9 @@ use crate::{
 mod simd;
 
 macro_rules! from_bytes {
+    (usize, $value:expr) => {
+        usize_from_bytes(&($value))?
+    };
     ($ty:tt, $value:expr) => {
         ($ty::from_le_bytes(match ($value).try_into() {
             Ok(it) => it,
@@ -182,7 +189,7 @@ impl Evaluator<'_> {
                 let [arg] = args else {
                     not_supported!("wrong arg count for clone");
                 };
-                let addr = Address::from_bytes(arg.get(self)?)?;
+                let addr = self.address_from_bytes(arg.get(self)?)?;
                 return destination
                     .write_from_interval(self, Interval { addr, size: destination.size });
             }
@@ -190,7 +197,7 @@ impl Evaluator<'_> {
                 let [arg] = args else {
                     not_supported!("wrong arg count for clone");
                 };
-                let addr = Address::from_bytes(arg.get(self)?)?;
+                let addr = self.address_from_bytes(arg.get(self)?)?;
                 let InternedClosure(closure_owner, _) = self.db.lookup_intern_closure((*id).into());
                 let infer = self.db.infer(closure_owner);
                 let (captures, _) = infer.closure_info(id);
@@ -202,7 +209,7 @@ impl Evaluator<'_> {
                 let [arg] = args else {
                     not_supported!("wrong arg count for clone");
                 };
-                let addr = Address::from_bytes(arg.get(self)?)?;
+                let addr = self.address_from_bytes(arg.get(self)?)?;
                 let layout = self.layout(&self_ty)?;
                 let ty_iter = subst.iter(Interner).map(|ga| ga.assert_ty_ref(Interner).clone());
                 self.exec_clone_for_fields(ty_iter, layout, addr, def, locals, destination, span)?;
@@ -240,7 +247,7 @@ impl Evaluator<'_> {
                 ty: TyKind::Ref(Mutability::Not, error_lifetime(), ty.clone()).intern(Interner),
             };
             let offset = layout.fields.offset(i).bytes_usize();
-            self.write_memory(tmp, &addr.offset(offset).to_bytes())?;
+            self.write_memory(tmp, &self.address_to_bytes(addr.offset(offset)))?;
             self.exec_clone(
                 def,
                 &[arg],
@@ -269,9 +276,27 @@ impl Evaluator<'_> {
                 let size = from_bytes!(usize, size.get(self)?);
                 let align = from_bytes!(usize, align.get(self)?);
                 let result = self.heap_allocate(size, align)?;
-                destination.write_from_bytes(self, &result.to_bytes())?;
+                if *alloc_fn == sym::rustc_allocator_zeroed {
+                    if let Some(checks) = &mut self.ub_checks {
+                        checks.mark_init(result, size);
+                    }
+                }
+                destination.write_from_bytes(self, &self.address_to_bytes(result))?;
+            }
+            _ if *alloc_fn == sym::rustc_deallocator => {
+                // The memory is never reused, so there is nothing to do unless we look for UB
+                if self.ub_checks.is_some() {
+                    let [ptr, ..] = args else {
+                        return Err(MirEvalError::InternalError(
+                            "rustc_deallocator args are not provided".into(),
+                        ));
+                    };
+                    let ptr = self.address_from_bytes(ptr.get(self)?)?;
+                    if let Some(checks) = &mut self.ub_checks {
+                        checks.free(ptr)?;
+                    }
+                }
             }
-            _ if *alloc_fn == sym::rustc_deallocator => { /* no-op for now */ }
             _ if *alloc_fn == sym::rustc_reallocator => {
                 let [ptr, old_size, align, new_size] = args else {
                     return Err(MirEvalError::InternalError(
@@ -283,12 +308,15 @@ impl Evaluator<'_> {
                 if old_size >= new_size {
                     destination.write_from_interval(self, ptr.interval)?;
                 } else {
-                    let ptr = Address::from_bytes(ptr.get(self)?)?;
+                    let ptr = self.address_from_bytes(ptr.get(self)?)?;
                     let align = from_bytes!(usize, align.get(self)?);
                     let result = self.heap_allocate(new_size, align)?;
                     Interval { addr: result, size: old_size }
                         .write_from_interval(self, Interval { addr: ptr, size: old_size })?;
-                    destination.write_from_bytes(self, &result.to_bytes())?;
+                    if let Some(checks) = &mut self.ub_checks {
+                        checks.free(ptr)?;
+                    }
+                    destination.write_from_bytes(self, &self.address_to_bytes(result))?;
                 }
             }
             _ => not_supported!("unknown alloc function"),
@@ -341,7 +369,7 @@ impl Evaluator<'_> {
                         return {
                             Err(MirEvalError::Panic(
                                 std::str::from_utf8(
-                                    self.read_memory(Address::from_bytes(pointee)?, len)?,
+                                    self.read_memory(self.address_from_bytes(pointee)?, len)?,
                                 )
                                 .unwrap()
                                 .to_owned(),
@@ -351,7 +379,7 @@ impl Evaluator<'_> {
                     let size = self.size_of_sized(ty, locals, "begin panic arg")?;
                     let pointee = arg.interval.get(self)?;
                     arg = IntervalAndTy {
-                        interval: Interval::new(Address::from_bytes(pointee)?, size),
+                        interval: Interval::new(self.address_from_bytes(pointee)?, size),
                         ty: ty.clone(),
                     };
                 }
@@ -382,7 +410,7 @@ impl Evaluator<'_> {
                 self.run_drop_glue_deep(
                     ty.clone(),
                     locals,
-                    Address::from_bytes(&arg[0..self.ptr_size()])?,
+                    self.address_from_bytes(&arg[0..self.ptr_size()])?,
                     &arg[self.ptr_size()..],
                     span,
                 )?;
@@ -408,7 +436,7 @@ impl Evaluator<'_> {
                         "SYS_getrandom args are not provided".into(),
                     ));
                 };
-                let addr = Address::from_bytes(buf.get(self)?)?;
+                let addr = self.address_from_bytes(buf.get(self)?)?;
                 let size = from_bytes!(usize, len.get(self)?);
                 for i in 0..size {
                     let rand_byte = self.random_state.rand_u64() as u8;
@@ -436,8 +464,8 @@ impl Evaluator<'_> {
                 let [ptr1, ptr2, size] = args else {
                     return Err(MirEvalError::InternalError("memcmp args are not provided".into()));
                 };
-                let addr1 = Address::from_bytes(ptr1.get(self)?)?;
-                let addr2 = Address::from_bytes(ptr2.get(self)?)?;
+                let addr1 = self.address_from_bytes(ptr1.get(self)?)?;
+                let addr2 = self.address_from_bytes(ptr2.get(self)?)?;
                 let size = from_bytes!(usize, size.get(self)?);
                 let slice1 = self.read_memory(addr1, size)?;
                 let slice2 = self.read_memory(addr2, size)?;
@@ -456,7 +484,7 @@ impl Evaluator<'_> {
                 };
                 let fd = u128::from_le_bytes(pad16(fd.get(self)?, false));
                 let interval = Interval {
-                    addr: Address::from_bytes(ptr.get(self)?)?,
+                    addr: self.address_from_bytes(ptr.get(self)?)?,
                     size: from_bytes!(usize, len.get(self)?),
                 };
                 match fd {
@@ -478,7 +506,7 @@ impl Evaluator<'_> {
                         "pthread_key_create arg0 is not provided".into(),
                     ));
                 };
-                let arg0_addr = Address::from_bytes(arg0.get(self)?)?;
+                let arg0_addr = self.address_from_bytes(arg0.get(self)?)?;
                 let key_ty = if let Some((ty, ..)) = arg0.ty.as_reference_or_ptr() {
                     ty
                 } else {
@@ -534,7 +562,7 @@ impl Evaluator<'_> {
                 let Some((id, rest)) = args.split_first() else {
                     return Err(MirEvalError::InternalError("syscall arg1 is not provided".into()));
                 };
-                let id = from_bytes!(i64, id.get(self)?);
+                let id = i128::from_le_bytes(pad16(id.get(self)?, true)) as i64;
                 self.exec_syscall(id, rest, destination, locals, span)
             }
             "sched_getaffinity" => {
@@ -543,7 +571,7 @@ impl Evaluator<'_> {
                         "libc::write args are not provided".into(),
                     ));
                 };
-                let set = Address::from_bytes(set.get(self)?)?;
+                let set = self.address_from_bytes(set.get(self)?)?;
                 // Only enable core 0 (we are single threaded anyway), which is bitset 0x0000001
                 self.write_memory(set, &[1])?;
                 // return 0 as success
@@ -558,7 +586,7 @@ impl Evaluator<'_> {
                 };
                 let mut name_buf = vec![];
                 let name = {
-                    let mut index = Address::from_bytes(name.get(self)?)?;
+                    let mut index = self.address_from_bytes(name.get(self)?)?;
                     loop {
                         let byte = self.read_memory(index, 1)?[0];
                         index = index.offset(1);
@@ -579,7 +607,7 @@ impl Evaluator<'_> {
                         value.push('\0');
                         let addr = self.heap_allocate(value.len(), 1)?;
                         self.write_memory(addr, value.as_bytes())?;
-                        self.write_memory(destination.addr, &addr.to_bytes())?;
+                        self.write_memory(destination.addr, &self.address_to_bytes(addr))?;
                     }
                 }
                 Ok(())
@@ -608,7 +636,12 @@ impl Evaluator<'_> {
                 .exec_simd_intrinsic(name, args, generic_args, destination, locals, span)
                 .map(|()| true);
         }
-        // FIXME(#17451): Add `f16` and `f128` intrinsics.
+        if let Some(name) = name.strip_suffix("f16") {
+            return self.exec_soft_float_intrinsic::<f16>(name, "f16", args, destination);
+        }
+        if let Some(name) = name.strip_suffix("f128") {
+            return self.exec_soft_float_intrinsic::<f128>(name, "f128", args, destination);
+        }
         if let Some(name) = name.strip_suffix("f64") {
             let result = match name {
                 "sqrt" | "sin" | "cos" | "exp" | "exp2" | "log" | "log10" | "log2" | "fabs"
@@ -790,11 +823,11 @@ impl Evaluator<'_> {
                     ));
                 };
                 if let Some((size, _)) = self.size_align_of(ty, locals)? {
-                    destination.write_from_bytes(self, &size.to_le_bytes())
+                    destination.write_from_bytes(self, &self.usize_to_bytes(size))
                 } else {
                     let metadata = arg.interval.slice(self.ptr_size()..self.ptr_size() * 2);
                     let (size, _) = self.size_align_of_unsized(ty, metadata, locals)?;
-                    destination.write_from_bytes(self, &size.to_le_bytes())
+                    destination.write_from_bytes(self, &self.usize_to_bytes(size))
                 }
             }
             "min_align_of_val" => {
@@ -811,11 +844,11 @@ impl Evaluator<'_> {
                     ));
                 };
                 if let Some((_, align)) = self.size_align_of(ty, locals)? {
-                    destination.write_from_bytes(self, &align.to_le_bytes())
+                    destination.write_from_bytes(self, &self.usize_to_bytes(align))
                 } else {
                     let metadata = arg.interval.slice(self.ptr_size()..self.ptr_size() * 2);
                     let (_, align) = self.size_align_of_unsized(ty, metadata, locals)?;
-                    destination.write_from_bytes(self, &align.to_le_bytes())
+                    destination.write_from_bytes(self, &self.usize_to_bytes(align))
                 }
             }
             "type_name" => {
@@ -842,10 +875,12 @@ impl Evaluator<'_> {
                 let len = ty_name.len();
                 let addr = self.heap_allocate(len, 1)?;
                 self.write_memory(addr, ty_name.as_bytes())?;
-                destination.slice(0..self.ptr_size()).write_from_bytes(self, &addr.to_bytes())?;
+                destination
+                    .slice(0..self.ptr_size())
+                    .write_from_bytes(self, &self.address_to_bytes(addr))?;
                 destination
                     .slice(self.ptr_size()..2 * self.ptr_size())
-                    .write_from_bytes(self, &len.to_le_bytes())
+                    .write_from_bytes(self, &self.usize_to_bytes(len))
             }
             "needs_drop" => {
                 let Some(ty) =
@@ -1051,8 +1086,8 @@ impl Evaluator<'_> {
                         "copy_nonoverlapping generic arg is not provided".into(),
                     ));
                 };
-                let src = Address::from_bytes(src.get(self)?)?;
-                let dst = Address::from_bytes(dst.get(self)?)?;
+                let src = self.address_from_bytes(src.get(self)?)?;
+                let dst = self.address_from_bytes(dst.get(self)?)?;
                 let offset = from_bytes!(usize, offset.get(self)?);
                 let size = self.size_of_sized(ty, locals, "copy_nonoverlapping ptr type")?;
                 let size = offset * size;
@@ -1112,7 +1147,16 @@ impl Evaluator<'_> {
                 let offset = u128::from_le_bytes(pad16(offset.get(self)?, false));
                 let size = self.size_of_sized(ty, locals, "offset ptr type")? as u128;
                 let ans = ptr + offset * size;
-                destination.write_from_bytes(self, &ans.to_le_bytes()[0..destination.size])
+                let ans = &ans.to_le_bytes()[0..destination.size];
+                if name == "offset" {
+                    if let Some(checks) = &self.ub_checks {
+                        checks.check_offset(
+                            self.address_from_usize(ptr as usize),
+                            self.address_from_bytes(ans)?,
+                        )?;
+                    }
+                }
+                destination.write_from_bytes(self, ans)
             }
             "assert_inhabited" | "assert_zero_valid" | "assert_uninit_valid" | "assume" => {
                 // FIXME: We should actually implement these checks
@@ -1233,7 +1277,7 @@ impl Evaluator<'_> {
                         "discriminant_value generic arg is not provided".into(),
                     ));
                 };
-                let addr = Address::from_bytes(arg.get(self)?)?;
+                let addr = self.address_from_bytes(arg.get(self)?)?;
                 let size = self.size_of_sized(ty, locals, "discriminant_value ptr type")?;
                 let interval = Interval { addr, size };
                 let r = self.compute_discriminant(ty.clone(), interval.get(self)?)?;
@@ -1285,7 +1329,7 @@ impl Evaluator<'_> {
                         "read_via_copy args are not provided".into(),
                     ));
                 };
-                let addr = Address::from_bytes(arg.interval.get(self)?)?;
+                let addr = self.address_from_bytes(arg.interval.get(self)?)?;
                 destination.write_from_interval(self, Interval { addr, size: destination.size })
             }
             "write_via_move" => {
@@ -1294,7 +1338,7 @@ impl Evaluator<'_> {
                         "write_via_move args are not provided".into(),
                     ));
                 };
-                let dst = Address::from_bytes(ptr.get(self)?)?;
+                let dst = self.address_from_bytes(ptr.get(self)?)?;
                 let Some(ty) =
                     generic_args.as_slice(Interner).first().and_then(|it| it.ty(Interner))
                 else {
@@ -1321,7 +1365,7 @@ impl Evaluator<'_> {
                         "write_bytes generic arg is not provided".into(),
                     ));
                 };
-                let dst = Address::from_bytes(dst.get(self)?)?;
+                let dst = self.address_from_bytes(dst.get(self)?)?;
                 let size = self.size_of_sized(ty, locals, "copy_nonoverlapping ptr type")?;
                 let size = count * size;
                 self.write_memory_using_ref(dst, size)?.fill(val);
@@ -1409,6 +1453,124 @@ impl Evaluator<'_> {
         .map(|()| true)
     }
 
+    /// Float intrinsics for the types without a stable host counterpart, computed in software.
+    ///
+    /// Everything that can be computed exactly is done with `rustc_apfloat`. The remaining
+    /// operations go through `f64` when it is precise enough for the type, which is only the
+    /// case for `f16`.
+    fn exec_soft_float_intrinsic<F>(
+        &mut self,
+        name: &str,
+        ty_name: &str,
+        args: &[IntervalAndTy],
+        destination: Interval,
+    ) -> Result<bool>
+    where
+        F: Float + FloatConvert<Double>,
+        Double: FloatConvert<F>,
+    {
+        let read = |this: &Self, arg: &IntervalAndTy| -> Result<F> {
+            let bytes = arg.get(this)?;
+            if bytes.len() != F::BITS / 8 {
+                return Err(MirEvalError::InternalError(
+                    format!("{name}{ty_name} intrinsic argument has an invalid size").into(),
+                ));
+            }
+            Ok(F::from_bits(u128::from_le_bytes(pad16(bytes, false))))
+        };
+        let via_f64 = |it: F, op: &dyn Fn(f64) -> f64| -> Result<F> {
+            if F::PRECISION * 2 + 2 > Double::PRECISION {
+                not_supported!("{name}{ty_name} intrinsic");
+            }
+            let it: Double = it.convert(&mut false).value;
+            let result =
+                Double::from_bits(op(f64::from_bits(it.to_bits() as u64)).to_bits().into());
+            Ok(result.convert(&mut false).value)
+        };
+        let result = match name {
+            "sqrt" | "sin" | "cos" | "exp" | "exp2" | "log" | "log10" | "log2" | "fabs"
+            | "floor" | "ceil" | "trunc" | "rint" | "nearbyint" | "round" | "roundeven" => {
+                let [arg] = args else {
+                    return Err(MirEvalError::InternalError(
+                        format!("{ty_name} intrinsic signature doesn't match fn ({ty_name}) -> {ty_name}")
+                            .into(),
+                    ));
+                };
+                let arg = read(self, arg)?;
+                match name {
+                    "sqrt" => via_f64(arg, &f64::sqrt)?,
+                    "sin" => via_f64(arg, &f64::sin)?,
+                    "cos" => via_f64(arg, &f64::cos)?,
+                    "exp" => via_f64(arg, &f64::exp)?,
+                    "exp2" => via_f64(arg, &f64::exp2)?,
+                    "log" => via_f64(arg, &f64::ln)?,
+                    "log10" => via_f64(arg, &f64::log10)?,
+                    "log2" => via_f64(arg, &f64::log2)?,
+                    "fabs" => arg.abs(),
+                    "floor" => arg.round_to_integral(Round::TowardNegative).value,
+                    "ceil" => arg.round_to_integral(Round::TowardPositive).value,
+                    "trunc" => arg.round_to_integral(Round::TowardZero).value,
+                    "round" => arg.round_to_integral(Round::NearestTiesToAway).value,
+                    "rint" | "nearbyint" | "roundeven" => {
+                        arg.round_to_integral(Round::NearestTiesToEven).value
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            "pow" | "minnum" | "maxnum" | "copysign" => {
+                let [arg1, arg2] = args else {
+                    return Err(MirEvalError::InternalError(
+                        format!(
+                            "{ty_name} intrinsic signature doesn't match fn ({ty_name}, {ty_name}) -> {ty_name}"
+                        )
+                        .into(),
+                    ));
+                };
+                let arg1 = read(self, arg1)?;
+                let arg2 = read(self, arg2)?;
+                match name {
+                    "pow" => {
+                        let arg2: Double = arg2.convert(&mut false).value;
+                        let arg2 = f64::from_bits(arg2.to_bits() as u64);
+                        via_f64(arg1, &|it| it.powf(arg2))?
+                    }
+                    "minnum" => arg1.min(arg2),
+                    "maxnum" => arg1.max(arg2),
+                    "copysign" => arg1.copy_sign(arg2),
+                    _ => unreachable!(),
+                }
+            }
+            "powi" => {
+                let [arg1, arg2] = args else {
+                    return Err(MirEvalError::InternalError(
+                        format!("powi{ty_name} signature doesn't match fn ({ty_name}, i32) -> {ty_name}")
+                            .into(),
+                    ));
+                };
+                let arg1 = read(self, arg1)?;
+                let arg2 = from_bytes!(i32, arg2.get(self)?);
+                via_f64(arg1, &|it| it.powi(arg2))?
+            }
+            "fma" => {
+                let [arg1, arg2, arg3] = args else {
+                    return Err(MirEvalError::InternalError(
+                        format!(
+                            "fma{ty_name} signature doesn't match fn ({ty_name}, {ty_name}, {ty_name}) -> {ty_name}"
+                        )
+                        .into(),
+                    ));
+                };
+                let arg1 = read(self, arg1)?;
+                let arg2 = read(self, arg2)?;
+                let arg3 = read(self, arg3)?;
+                arg1.mul_add(arg2, arg3).value
+            }
+            _ => not_supported!("unknown {ty_name} intrinsic {name}"),
+        };
+        let result = result.to_bits().to_le_bytes();
+        destination.write_from_bytes(self, &result[0..F::BITS / 8]).map(|()| true)
+    }
+
     fn size_align_of_unsized(
         &mut self,
         ty: &Ty,
@@ -1490,7 +1652,7 @@ impl Evaluator<'_> {
                 "atomic intrinsic arg0 is not provided".into(),
             ));
         };
-        let arg0_addr = Address::from_bytes(arg0.get(self)?)?;
+        let arg0_addr = self.address_from_bytes(arg0.get(self)?)?;
         let arg0_interval =
             Interval::new(arg0_addr, self.size_of_sized(ty, locals, "atomic intrinsic type arg")?);
         if name.starts_with("load_") {
@@ -1,14 +1,21 @@
+use std::ops::ControlFlow;
+
+use base_db::SourceDatabase;
+use expect_test::{expect, Expect};
 use hir_def::db::DefDatabase;
 use span::{Edition, EditionedFileId};
 use syntax::{TextRange, TextSize};
 use test_fixture::WithFixture;
+use triomphe::Arc;
 
 use crate::display::DisplayTarget;
 use crate::{db::HirDatabase, mir::MirLowerError, test_db::TestDB, Interner, Substitution};
 
-use super::{interpret_mir, MirEvalError};
+use super::{
+    debug_mir, interpret_mir, interpret_mir_with, DebugState, MirBody, MirDebugger, MirEvalError,
+};
 
-fn eval_main(db: &TestDB, file_id: EditionedFileId) -> Result<(String, String), MirEvalError> {
+fn main_body(db: &TestDB, file_id: EditionedFileId) -> Result<Arc<MirBody>, MirEvalError> {
     let module_id = db.module_for_file(file_id);
     let def_map = module_id.def_map(db);
     let scope = &def_map[module_id.local_id].scope;
@@ -25,14 +32,16 @@ fn eval_main(db: &TestDB, file_id: EditionedFileId) -> Result<(String, String),
             _ => None,
         })
         .expect("no main function found");
-    let body = db
-        .monomorphized_mir_body(
-            func_id.into(),
-            Substitution::empty(Interner),
-            db.trait_environment(func_id.into()),
-        )
-        .map_err(|e| MirEvalError::MirLowerError(func_id, e))?;
+    db.monomorphized_mir_body(
+        func_id.into(),
+        Substitution::empty(Interner),
+        db.trait_environment(func_id.into()),
+    )
+    .map_err(|e| MirEvalError::MirLowerError(func_id, e))
+}
 
+fn eval_main(db: &TestDB, file_id: EditionedFileId) -> Result<(String, String), MirEvalError> {
+    let body = main_body(db, file_id)?;
     let (result, output) = interpret_mir(db, body, false, None)?;
     result?;
     Ok((output.stdout().into_owned(), output.stderr().into_owned()))
@@ -982,3 +991,328 @@ fn main<'a, T: Foo + Bar + Baz>(
         |e| matches!(e, MirEvalError::MirLowerError(_, MirLowerError::GenericArgNotProvided(..))),
     );
 }
+
+/// Records a snapshot of the stack and its locals at each step of the innermost frames.
+struct RecordingDebugger<'a> {
+    text: &'a str,
+    snapshots: Vec<String>,
+    stop_after: Option<usize>,
+}
+
+impl MirDebugger for RecordingDebugger<'_> {
+    fn on_step(&mut self, state: &DebugState<'_, '_>) -> ControlFlow<()> {
+        if self.stop_after == Some(self.snapshots.len()) {
+            return ControlFlow::Break(());
+        }
+        let mut snapshot = String::new();
+        for (idx, frame) in state.frames().iter().enumerate() {
+            let source = frame.range.map_or("?", |it| &self.text[it.range]);
+            snapshot += &format!("{} `{source}`", frame.name);
+            for local in state.locals(idx) {
+                snapshot += &format!(" {}: {} = {}", local.name, local.ty, local.value);
+            }
+            snapshot += "\n";
+        }
+        if self.snapshots.last() != Some(&snapshot) {
+            self.snapshots.push(snapshot);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+fn check_debugger(
+    #[rust_analyzer::rust_fixture] ra_fixture: &str,
+    stop_after: Option<usize>,
+    expect: Expect,
+) {
+    let (db, file_ids) = TestDB::with_many_files(ra_fixture);
+    let file_id = *file_ids.last().unwrap();
+    let text = db.file_text(file_id.file_id()).text(&db);
+    let body = main_body(&db, file_id).unwrap();
+    let mut debugger = RecordingDebugger { text: &text, snapshots: vec![], stop_after };
+    let (result, _) = debug_mir(&db, body, &mut debugger).unwrap();
+    let mut actual = debugger.snapshots.join("--\n");
+    if let Err(e) = result {
+        actual += &format!("{e:?}\n");
+    }
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn debugger_steps() {
+    check_debugger(
+        r#"
+fn add(a: i32, b: i32) -> i32 {
+    let sum = a + b;
+    sum
+}
+
+fn main() {
+    let x = 5;
+    let y = add(x, 2);
+}
+"#,
+        None,
+        expect![[r#"
+            main `5` x: i32 = 0 y: i32 = 0
+            --
+            main `{
+                let x = 5;
+                let y = add(x, 2);
+            }` x: i32 = 0 y: i32 = 0
+            --
+            main `x` x: i32 = 0 y: i32 = 0
+            --
+            main `add(x, 2)` x: i32 = 5 y: i32 = 0
+            --
+            add `a + b` a: i32 = 5 b: i32 = 2 sum: i32 = 0
+            main `add(x, 2)` x: i32 = 5 y: i32 = 0
+            --
+            add `{
+                let sum = a + b;
+                sum
+            }` a: i32 = 5 b: i32 = 2 sum: i32 = 0
+            main `add(x, 2)` x: i32 = 5 y: i32 = 0
+            --
+            add `sum` a: i32 = 5 b: i32 = 2 sum: i32 = 0
+            main `add(x, 2)` x: i32 = 5 y: i32 = 0
+            --
+            add `sum` a: i32 = 5 b: i32 = 2 sum: i32 = 7
+            main `add(x, 2)` x: i32 = 5 y: i32 = 0
+            --
+            add `{
+                let sum = a + b;
+                sum
+            }` a: i32 = 5 b: i32 = 2 sum: i32 = 7
+            main `add(x, 2)` x: i32 = 5 y: i32 = 0
+            --
+            main `{
+                let x = 5;
+                let y = add(x, 2);
+            }` x: i32 = 5 y: i32 = 0
+            --
+            main `y` x: i32 = 5 y: i32 = 0
+            --
+            main `{
+                let x = 5;
+                let y = add(x, 2);
+            }` x: i32 = 5 y: i32 = 7
+        "#]],
+    );
+}
+
+#[test]
+fn debugger_terminates() {
+    check_debugger(
+        r#"
+fn main() {
+    let x = 5;
+    let y = x + 2;
+}
+"#,
+        Some(1),
+        expect![[r#"
+            main `5` x: i32 = 0 y: i32 = 0
+            WithStack { error: evaluation terminated, stack: [] }
+        "#]],
+    );
+}
+
+fn check_ub(#[rust_analyzer::rust_fixture] ra_fixture: &str, expect: Expect) {
+    let (db, file_ids) = TestDB::with_many_files(&format!("{ra_fixture}{UB_CHECKS_PRELUDE}"));
+    let file_id = *file_ids.last().unwrap();
+    let body = main_body(&db, file_id).unwrap();
+    let (result, _) = interpret_mir_with(&db, body, None, true).unwrap();
+    let actual = match result {
+        Ok(_) => "no undefined behavior\n".to_owned(),
+        Err(MirEvalError::InFunction(e, _)) => format!("{e:?}\n"),
+        Err(e) => format!("{e:?}\n"),
+    };
+    expect.assert_eq(&actual);
+}
+
+const UB_CHECKS_PRELUDE: &str = r#"
+extern "rust-intrinsic" {
+    pub fn offset<Ptr, Delta>(dst: Ptr, offset: Delta) -> Ptr;
+}
+extern "Rust" {
+    #[rustc_allocator]
+    fn __rust_alloc(size: usize, align: usize) -> *mut u8;
+    #[rustc_deallocator]
+    fn __rust_dealloc(ptr: *mut u8, size: usize, align: usize);
+}
+"#;
+
+#[test]
+fn ub_checks_pass() {
+    check_ub(
+        r#"
+fn main() {
+    let x = [1u8, 2, 3];
+    let p = &x as *const [u8; 3] as *const u8;
+    let y = unsafe { *offset(p, 2usize) };
+    let b = y == 3;
+    unsafe {
+        let h = __rust_alloc(4, 4) as *mut u32;
+        *h = 5;
+        let z = *h;
+        __rust_dealloc(h as *mut u8, 4, 4);
+    }
+}
+"#,
+        expect![[r#"
+            no undefined behavior
+        "#]],
+    );
+}
+
+#[test]
+fn ub_checks_out_of_bounds() {
+    check_ub(
+        r#"
+fn main() {
+    let x = [1u8, 2, 3];
+    let p = &x as *const [u8; 3] as *const u8;
+    let y = unsafe { *offset(p, 4usize) };
+}
+"#,
+        expect![[r#"
+            UndefinedBehavior("out of bounds pointer arithmetic: offsetting Stack(1) to Stack(5)")
+        "#]],
+    );
+}
+
+#[test]
+fn ub_checks_use_after_free() {
+    check_ub(
+        r#"
+fn main() {
+    unsafe {
+        let h = __rust_alloc(4, 4) as *mut u32;
+        *h = 5;
+        __rust_dealloc(h as *mut u8, 4, 4);
+        let z = *h;
+    }
+}
+"#,
+        expect![[r#"
+            UndefinedBehavior("use after free: dereferencing Heap(24), which was deallocated")
+        "#]],
+    );
+}
+
+#[test]
+fn ub_checks_double_free() {
+    check_ub(
+        r#"
+fn main() {
+    unsafe {
+        let h = __rust_alloc(4, 4);
+        __rust_dealloc(h, 4, 4);
+        __rust_dealloc(h, 4, 4);
+    }
+}
+"#,
+        expect![[r#"
+            UndefinedBehavior("double free")
+        "#]],
+    );
+}
+
+#[test]
+fn ub_checks_dangling_local() {
+    check_ub(
+        r#"
+fn dangling() -> *const i32 {
+    let x = 5;
+    &x as *const i32
+}
+
+fn main() {
+    let y = unsafe { *dangling() };
+}
+"#,
+        expect![[r#"
+            UndefinedBehavior("use after free: dereferencing Stack(24), which was deallocated")
+        "#]],
+    );
+}
+
+#[test]
+fn ub_checks_invalid_bool_and_char() {
+    check_ub(
+        r#"
+//- minicore: transmute
+fn main() {
+    let b: bool = unsafe { core::mem::transmute(2u8) };
+}
+"#,
+        expect![[r#"
+            UndefinedBehavior("invalid value 2 for a `bool`")
+        "#]],
+    );
+    check_ub(
+        r#"
+//- minicore: transmute
+fn main() {
+    let c: char = unsafe { core::mem::transmute(0xD800u32) };
+}
+"#,
+        expect![[r#"
+            UndefinedBehavior("invalid value 0xd800 for a `char`")
+        "#]],
+    );
+}
+
+#[test]
+fn ub_checks_invalid_discriminant() {
+    check_ub(
+        r#"
+//- minicore: transmute
+enum E {
+    A = 1,
+    B = 5,
+}
+
+fn main() {
+    let e: E = unsafe { core::mem::transmute(3u8) };
+}
+"#,
+        expect![[r#"
+            UndefinedBehavior("invalid discriminant 3 for enum `E`")
+        "#]],
+    );
+}
+
+#[test]
+fn ub_checks_misaligned_read() {
+    check_ub(
+        r#"
+fn main() {
+    let x = [0u32; 2];
+    let p = &x as *const [u32; 2] as *const u8;
+    let y = unsafe { *(offset(p, 1usize) as *const u32) };
+}
+"#,
+        expect![[r#"
+            UndefinedBehavior("misaligned pointer dereference: address 0x1000000000000005 is not aligned to 4 bytes")
+        "#]],
+    );
+}
+
+#[test]
+fn ub_checks_uninitialized_read() {
+    check_ub(
+        r#"
+fn main() {
+    unsafe {
+        let h = __rust_alloc(4, 4) as *mut u32;
+        let z = *h;
+    }
+}
+"#,
+        expect![[r#"
+            UndefinedBehavior("reading uninitialized memory at Heap(24) as u32")
+        "#]],
+    );
+}
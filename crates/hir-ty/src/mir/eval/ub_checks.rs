@@ -0,0 +1,431 @@
+//! Opt-in detection of undefined behavior, in the spirit of Miri.
+//!
+//! When enabled, the interpreter tracks every allocation of the program and whether each byte of
+//! memory is initialized. It then checks that:
+//! * dereferenced pointers are aligned and point into a live allocation with room for the pointee,
+//!   which catches out of bounds accesses, use after free and dangling pointers.
+//! * pointer arithmetic with `offset` stays inside the allocation the pointer points into.
+//! * only live heap allocations are freed, and only once.
+//! * values are valid for their type whenever they are read, i.e. they are initialized, `bool`s
+//!   are `0` or `1`, `char`s are unicode scalar values and enums have a valid discriminant.
+//!
+//! Unlike Miri, provenance is recovered from the address alone, so a pointer that was moved out of
+//! its allocation into a neighboring one is not detected, unless this was done with `offset`.
+
+use std::collections::BTreeMap;
+
+use chalk_ir::{Scalar, TyKind};
+use hir_def::{
+    layout::{TagEncoding, Variants},
+    AdtId, VariantId,
+};
+
+use crate::{
+    consteval::try_const_usize,
+    display::{DisplayTarget, HirDisplay},
+    layout::Layout,
+    mir::pad16,
+    Interner, Substitution, Ty,
+};
+
+use super::{usize_from_bytes, Address, Evaluator, IntervalOrOwned, Locals, MirEvalError, Result};
+
+/// The state needed to detect undefined behavior.
+#[derive(Debug, Default)]
+pub(super) struct UbChecks {
+    stack: Memory,
+    heap: Memory,
+}
+
+#[derive(Debug, Default)]
+struct Memory {
+    /// The allocations by their start. Freed allocations are kept, to tell use after free apart
+    /// from other dangling pointers.
+    allocations: BTreeMap<usize, Allocation>,
+    /// Whether each byte is initialized. Bytes past the end are not.
+    init: Vec<bool>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Allocation {
+    size: usize,
+    live: bool,
+}
+
+fn ub<T>(message: String) -> Result<T> {
+    Err(MirEvalError::UndefinedBehavior(message))
+}
+
+impl Memory {
+    /// Finds the allocation containing `pos`, or ending right at it.
+    fn allocation_at(&self, pos: usize) -> Option<(usize, Allocation)> {
+        let (&start, &allocation) = self.allocations.range(..=pos).next_back()?;
+        (pos <= start + allocation.size).then_some((start, allocation))
+    }
+
+    fn set_init(&mut self, pos: usize, size: usize, init: bool) {
+        if self.init.len() < pos + size {
+            self.init.resize(pos + size, false);
+        }
+        self.init[pos..pos + size].fill(init);
+    }
+
+    fn is_init(&self, pos: usize, size: usize) -> bool {
+        self.init.get(pos..pos + size).is_some_and(|it| it.iter().all(|&it| it))
+    }
+
+    fn init_range(&self, pos: usize, size: usize) -> Vec<bool> {
+        (pos..pos + size).map(|it| self.init.get(it).copied().unwrap_or(false)).collect()
+    }
+}
+
+impl UbChecks {
+    fn memory(&self, addr: Address) -> Option<(&Memory, usize)> {
+        match addr {
+            Address::Stack(it) => Some((&self.stack, it)),
+            Address::Heap(it) => Some((&self.heap, it)),
+            Address::Invalid(_) => None,
+        }
+    }
+
+    fn memory_mut(&mut self, addr: Address) -> Option<(&mut Memory, usize)> {
+        match addr {
+            Address::Stack(it) => Some((&mut self.stack, it)),
+            Address::Heap(it) => Some((&mut self.heap, it)),
+            Address::Invalid(_) => None,
+        }
+    }
+
+    /// Records a new allocation. Zero sized allocations share their address with other
+    /// allocations, and are not tracked.
+    pub(super) fn allocate(&mut self, addr: Address, size: usize) {
+        if size == 0 {
+            return;
+        }
+        if let Some((memory, pos)) = self.memory_mut(addr) {
+            memory.allocations.insert(pos, Allocation { size, live: true });
+            memory.set_init(pos, size, false);
+        }
+    }
+
+    /// Frees the heap allocation starting at `addr`.
+    pub(super) fn free(&mut self, addr: Address) -> Result<()> {
+        let Address::Heap(pos) = addr else {
+            return ub(format!("freeing {addr:?}, which was not allocated on the heap"));
+        };
+        if let Some(allocation) = self.heap.allocations.get_mut(&pos) {
+            if !allocation.live {
+                return ub("double free".to_owned());
+            }
+            allocation.live = false;
+            return Ok(());
+        }
+        match self.heap.allocation_at(pos) {
+            Some((start, allocation)) if pos < start + allocation.size => {
+                ub("freeing a pointer to the middle of an allocation".to_owned())
+            }
+            // Zero sized allocations are not tracked.
+            _ => Ok(()),
+        }
+    }
+
+    /// Marks the stack allocation of a local as dead, when its function returns.
+    pub(super) fn free_local(&mut self, addr: Address) {
+        if let Some((memory, pos)) = self.memory_mut(addr) {
+            if let Some(allocation) = memory.allocations.get_mut(&pos) {
+                allocation.live = false;
+            }
+        }
+    }
+
+    pub(super) fn mark_init(&mut self, addr: Address, size: usize) {
+        if let Some((memory, pos)) = self.memory_mut(addr) {
+            memory.set_init(pos, size, true);
+        }
+    }
+
+    /// Copies the initialization state along with the bytes of a copy.
+    pub(super) fn copy_init(&mut self, dst: Address, src: Address, size: usize) {
+        let Some((memory, pos)) = self.memory(src) else {
+            return;
+        };
+        let init = memory.init_range(pos, size);
+        if let Some((memory, pos)) = self.memory_mut(dst) {
+            if memory.init.len() < pos + size {
+                memory.init.resize(pos + size, false);
+            }
+            memory.init[pos..pos + size].copy_from_slice(&init);
+        }
+    }
+
+    fn is_init(&self, addr: Address, size: usize) -> bool {
+        match self.memory(addr) {
+            Some((memory, pos)) => memory.is_init(pos, size),
+            None => size == 0,
+        }
+    }
+
+    /// Checks that `size` bytes at `addr` are inside a live allocation.
+    fn check_access(&self, addr: Address, size: usize) -> Result<()> {
+        // Zero sized allocations are not tracked, and may share their address with a dead one.
+        if size == 0 {
+            return Ok(());
+        }
+        let Some((memory, pos)) = self.memory(addr) else {
+            return ub(format!("dereferencing dangling pointer {addr:?}"));
+        };
+        let Some((start, allocation)) = memory.allocation_at(pos) else {
+            return ub(format!("dereferencing dangling pointer {addr:?}"));
+        };
+        if !allocation.live {
+            return ub(format!("use after free: dereferencing {addr:?}, which was deallocated"));
+        }
+        if pos + size > start + allocation.size {
+            return ub(format!(
+                "out of bounds access: accessing {size} bytes at offset {} of an allocation of {} bytes",
+                pos - start,
+                allocation.size
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that `offset`ing a pointer from `from` to `to` stays inside its allocation, or
+    /// just past its end.
+    pub(super) fn check_offset(&self, from: Address, to: Address) -> Result<()> {
+        if from == to {
+            return Ok(());
+        }
+        let in_bounds = match (self.memory(from), to) {
+            (Some((memory, pos)), Address::Stack(to_pos) | Address::Heap(to_pos))
+                if std::mem::discriminant(&from) == std::mem::discriminant(&to) =>
+            {
+                memory.allocation_at(pos).is_some_and(|(start, allocation)| {
+                    allocation.live && (start..=start + allocation.size).contains(&to_pos)
+                })
+            }
+            _ => false,
+        };
+        if !in_bounds {
+            return ub(format!("out of bounds pointer arithmetic: offsetting {from:?} to {to:?}"));
+        }
+        Ok(())
+    }
+}
+
+impl Evaluator<'_> {
+    /// Checks that `addr` can be dereferenced as a `pointee`, which has the given metadata if it
+    /// is unsized.
+    pub(super) fn check_deref(
+        &self,
+        addr: Address,
+        pointee: &Ty,
+        metadata: Option<&IntervalOrOwned>,
+        locals: &Locals,
+    ) -> Result<()> {
+        let Some(checks) = &self.ub_checks else {
+            return Ok(());
+        };
+        let (size, align) = match self.size_align_of(pointee, locals)? {
+            Some(it) => it,
+            None => match (pointee.kind(Interner), metadata) {
+                (TyKind::Str, Some(metadata)) => (usize_from_bytes(metadata.get(self)?)?, 1),
+                (TyKind::Slice(inner), Some(metadata)) => {
+                    let len = usize_from_bytes(metadata.get(self)?)?;
+                    let (size, align) =
+                        self.size_align_of_sized(inner, locals, "slice inner type")?;
+                    let Some(size) = size.checked_mul(len) else {
+                        return ub(format!("slice of {len} elements is too big"));
+                    };
+                    (size, align)
+                }
+                (TyKind::Dyn(_), Some(metadata)) => self.size_align_of_sized(
+                    self.vtable_map.ty_of_bytes(metadata.get(self)?)?,
+                    locals,
+                    "dyn concrete type",
+                )?,
+                _ => (0, 1),
+            },
+        };
+        let address = self.address_to_usize(addr);
+        if address == 0 {
+            return ub("null pointer dereference".to_owned());
+        }
+        if address % align != 0 {
+            return ub(format!(
+                "misaligned pointer dereference: address {address:#x} is not aligned to {align} bytes"
+            ));
+        }
+        checks.check_access(addr, size)
+    }
+
+    /// Checks that the value of type `ty` at `addr` is valid.
+    pub(super) fn check_value(&self, addr: Address, ty: &Ty, locals: &Locals) -> Result<()> {
+        match &self.ub_checks {
+            Some(checks) => self.check_value_in(checks, addr, ty, locals),
+            None => Ok(()),
+        }
+    }
+
+    fn check_value_in(
+        &self,
+        checks: &UbChecks,
+        addr: Address,
+        ty: &Ty,
+        locals: &Locals,
+    ) -> Result<()> {
+        match ty.kind(Interner) {
+            TyKind::Scalar(Scalar::Bool) => {
+                let value = self.read_initialized(checks, addr, 1, ty)?[0];
+                if value > 1 {
+                    return ub(format!("invalid value {value} for a `bool`"));
+                }
+            }
+            TyKind::Scalar(Scalar::Char) => {
+                let bytes = self.read_initialized(checks, addr, 4, ty)?;
+                let value = u32::from_le_bytes(pad16(bytes, false)[..4].try_into().unwrap());
+                if char::from_u32(value).is_none() {
+                    return ub(format!("invalid value {value:#x} for a `char`"));
+                }
+            }
+            TyKind::Scalar(_) | TyKind::Raw(..) | TyKind::Function(_) => {
+                let size = self.size_of_sized(ty, locals, "scalar type")?;
+                self.read_initialized(checks, addr, size, ty)?;
+            }
+            TyKind::Ref(..) => {
+                let size = self.size_of_sized(ty, locals, "reference type")?;
+                let bytes = self.read_initialized(checks, addr, size, ty)?;
+                if usize_from_bytes(&bytes[..self.ptr_size()])? == 0 {
+                    return ub("null reference".to_owned());
+                }
+            }
+            TyKind::Never => return ub("a value of the never type was read".to_owned()),
+            TyKind::Tuple(_, subst) => {
+                let layout = self.layout(ty)?;
+                for (i, field) in subst.iter(Interner).filter_map(|it| it.ty(Interner)).enumerate()
+                {
+                    let offset = layout.fields.offset(i).bytes_usize();
+                    self.check_value_in(checks, addr.offset(offset), field, locals)?;
+                }
+            }
+            TyKind::Array(inner, len) => {
+                let Some(len) = try_const_usize(self.db, len) else {
+                    return Ok(());
+                };
+                let size = self.size_of_sized(inner, locals, "array inner type")?;
+                if let TyKind::Scalar(Scalar::Int(_) | Scalar::Uint(_) | Scalar::Float(_)) =
+                    inner.kind(Interner)
+                {
+                    self.read_initialized(checks, addr, size * len as usize, ty)?;
+                    return Ok(());
+                }
+                for i in 0..len as usize {
+                    self.check_value_in(checks, addr.offset(i * size), inner, locals)?;
+                }
+            }
+            TyKind::Adt(chalk_ir::AdtId(AdtId::StructId(s)), subst) => {
+                let layout = self.layout(ty)?;
+                self.check_fields(checks, addr, (*s).into(), &layout, subst, locals)?;
+            }
+            TyKind::Adt(chalk_ir::AdtId(AdtId::EnumId(e)), subst) => {
+                // The layout is not available while we evaluate the discriminants it depends on
+                let Ok(layout) = self.layout(ty) else {
+                    return Ok(());
+                };
+                let variants = &self.db.enum_data(*e).variants;
+                let (idx, variant_layout) = match &layout.variants {
+                    Variants::Empty => {
+                        return ub("a value of an enum without variants was read".to_owned())
+                    }
+                    Variants::Single { index } => (index.0, &*layout),
+                    Variants::Multiple { tag, tag_encoding, variants: layouts, .. } => {
+                        let size = tag.size(&*self.target_data_layout).bytes_usize();
+                        // The only field on enum variants is the tag field
+                        let offset = layout.fields.offset(0).bytes_usize();
+                        let tag_bytes =
+                            self.read_initialized(checks, addr.offset(offset), size, ty)?;
+                        let idx = match tag_encoding {
+                            TagEncoding::Direct => {
+                                let tag_value =
+                                    i128::from_le_bytes(pad16(tag_bytes, tag.is_signed()));
+                                let mut found = None;
+                                for (idx, _) in layouts.iter_enumerated() {
+                                    if self.const_eval_discriminant(variants[idx.0].0)? == tag_value
+                                    {
+                                        found = Some(idx);
+                                        break;
+                                    }
+                                }
+                                match found {
+                                    Some(it) => it,
+                                    None => {
+                                        return ub(format!(
+                                            "invalid discriminant {tag_value} for enum `{}`",
+                                            self.db.enum_data(*e).name.as_str()
+                                        ))
+                                    }
+                                }
+                            }
+                            TagEncoding::Niche { untagged_variant, niche_start, .. } => {
+                                let candidate_tag = i128::from_le_bytes(pad16(tag_bytes, false))
+                                    .wrapping_sub(*niche_start as i128)
+                                    as usize;
+                                layouts
+                                    .iter_enumerated()
+                                    .map(|(it, _)| it)
+                                    .filter(|it| it != untagged_variant)
+                                    .nth(candidate_tag)
+                                    .unwrap_or(*untagged_variant)
+                            }
+                        };
+                        (idx.0, &layouts[idx])
+                    }
+                };
+                self.check_fields(
+                    checks,
+                    addr,
+                    variants[idx].0.into(),
+                    variant_layout,
+                    subst,
+                    locals,
+                )?;
+            }
+            // Unions may hold anything, e.g. `MaybeUninit`
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn check_fields(
+        &self,
+        checks: &UbChecks,
+        addr: Address,
+        variant: VariantId,
+        layout: &Layout,
+        subst: &Substitution,
+        locals: &Locals,
+    ) -> Result<()> {
+        for (id, ty) in self.db.field_types(variant).iter() {
+            let ty = ty.clone().substitute(Interner, subst);
+            let offset = layout.fields.offset(u32::from(id.into_raw()) as usize).bytes_usize();
+            self.check_value_in(checks, addr.offset(offset), &ty, locals)?;
+        }
+        Ok(())
+    }
+
+    fn read_initialized(
+        &self,
+        checks: &UbChecks,
+        addr: Address,
+        size: usize,
+        ty: &Ty,
+    ) -> Result<&[u8]> {
+        if !checks.is_init(addr, size) {
+            return ub(format!(
+                "reading uninitialized memory at {addr:?} as {}",
+                ty.display(self.db, DisplayTarget::from_crate(self.db, self.crate_id))
+            ));
+        }
+        self.read_memory(addr, size)
+    }
+}
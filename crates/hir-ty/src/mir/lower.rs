@@ -21,7 +21,7 @@ use hir_def::{
 use hir_expand::name::Name;
 use la_arena::ArenaMap;
 use rustc_apfloat::Float;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use span::{Edition, FileId};
 use syntax::TextRange;
 use triomphe::Arc;
@@ -183,6 +183,9 @@ impl MirLowerError {
                 )?;
             }
             MirLowerError::HasErrors => writeln!(f, "Type inference result contains errors")?,
+            MirLowerError::InaccessibleLocal => {
+                writeln!(f, "Use of a local variable declared outside of the evaluated code")?
+            }
             MirLowerError::TypeMismatch(e) => writeln!(
                 f,
                 "Type mismatch: Expected {}, found {}",
@@ -210,7 +213,6 @@ impl MirLowerError {
             | MirLowerError::UnsizedTemporary(_)
             | MirLowerError::IncompleteExpr
             | MirLowerError::IncompletePattern
-            | MirLowerError::InaccessibleLocal
             | MirLowerError::TraitFunctionDefinition(_, _)
             | MirLowerError::UnresolvedName(_)
             | MirLowerError::RecordLiteralWithoutPath
@@ -1404,31 +1406,35 @@ impl<'ctx> MirLowerCtx<'ctx> {
                 .layout_of_ty(ty.clone(), self.db.trait_environment_for_body(self.owner))
                 .map(|it| it.size.bytes_usize())
         };
-        const USIZE_SIZE: usize = size_of::<usize>();
+        // A reference to the literal's data, which is put at address 0 of its memory map.
+        let reference_to_data = |len: usize| -> Result<Box<[u8]>> {
+            let usize_size = self
+                .db
+                .layout_of_ty(TyBuilder::usize(), self.db.trait_environment_for_body(self.owner))?
+                .size
+                .bytes_usize();
+            let mut data = vec![0; 2 * usize_size];
+            data[usize_size..].copy_from_slice(&(len as u128).to_le_bytes()[..usize_size]);
+            Ok(data.into())
+        };
         let bytes: Box<[_]> = match l {
             hir_def::hir::Literal::String(b) => {
                 let b = b.as_str();
-                let mut data = [0; { 2 * USIZE_SIZE }];
-                data[..USIZE_SIZE].copy_from_slice(&0usize.to_le_bytes());
-                data[USIZE_SIZE..].copy_from_slice(&b.len().to_le_bytes());
+                let data = reference_to_data(b.len())?;
                 let mm = MemoryMap::simple(b.as_bytes().into());
-                return Ok(Operand::from_concrete_const(Box::new(data), mm, ty));
+                return Ok(Operand::from_concrete_const(data, mm, ty));
             }
             hir_def::hir::Literal::CString(b) => {
                 let bytes = b.iter().copied().chain(iter::once(0)).collect::<Box<_>>();
 
-                let mut data = [0; { 2 * USIZE_SIZE }];
-                data[..USIZE_SIZE].copy_from_slice(&0usize.to_le_bytes());
-                data[USIZE_SIZE..].copy_from_slice(&bytes.len().to_le_bytes());
+                let data = reference_to_data(bytes.len())?;
                 let mm = MemoryMap::simple(bytes);
-                return Ok(Operand::from_concrete_const(Box::new(data), mm, ty));
+                return Ok(Operand::from_concrete_const(data, mm, ty));
             }
             hir_def::hir::Literal::ByteString(b) => {
-                let mut data = [0; { 2 * USIZE_SIZE }];
-                data[..USIZE_SIZE].copy_from_slice(&0usize.to_le_bytes());
-                data[USIZE_SIZE..].copy_from_slice(&b.len().to_le_bytes());
+                let data = reference_to_data(b.len())?;
                 let mm = MemoryMap::simple(b.clone());
-                return Ok(Operand::from_concrete_const(Box::new(data), mm, ty));
+                return Ok(Operand::from_concrete_const(data, mm, ty));
             }
             hir_def::hir::Literal::Char(c) => Box::new(u32::from(*c).to_le_bytes()),
             hir_def::hir::Literal::Bool(b) => Box::new([*b as u8]),
@@ -2206,3 +2212,48 @@ pub fn lower_to_mir(
     }
     Ok(ctx.result)
 }
+
+/// Lowers `expr`, an expression somewhere inside `body`, as if it was a body of its own. Only the
+/// bindings declared inside `expr` get a local, so a use of any other local of `body` fails with
+/// [`MirLowerError::InaccessibleLocal`].
+pub fn lower_expr_to_mir(
+    db: &dyn HirDatabase,
+    owner: DefWithBodyId,
+    body: &Body,
+    infer: &InferenceResult,
+    expr: ExprId,
+) -> Result<MirBody> {
+    if infer.type_mismatches().next().is_some() {
+        return Err(MirLowerError::HasErrors);
+    }
+    let mut declared = FxHashSet::default();
+    collect_declared_bindings(body, expr, &mut declared);
+    let mut ctx = MirLowerCtx::new(db, owner, body, infer);
+    ctx.result.locals.alloc(Local { ty: ctx.expr_ty_after_adjustments(expr) });
+    let current = ctx.lower_params_and_bindings([].into_iter(), None, |b| declared.contains(&b))?;
+    if let Some(current) = ctx.lower_expr_to_place(expr, return_slot().into(), current)? {
+        let current = ctx.pop_drop_scope_assert_finished(current, expr.into())?;
+        ctx.set_terminator(current, TerminatorKind::Return, expr.into());
+    }
+    Ok(ctx.result)
+}
+
+fn collect_declared_bindings(body: &Body, expr: ExprId, acc: &mut FxHashSet<BindingId>) {
+    let mut add_pat = |pat| body.walk_bindings_in_pat(pat, |b| _ = acc.insert(b));
+    match &body[expr] {
+        Expr::Let { pat, .. } => add_pat(*pat),
+        Expr::Block { statements, .. }
+        | Expr::Async { statements, .. }
+        | Expr::Unsafe { statements, .. } => {
+            for statement in statements.iter() {
+                if let hir_def::hir::Statement::Let { pat, .. } = statement {
+                    add_pat(*pat);
+                }
+            }
+        }
+        Expr::Match { arms, .. } => arms.iter().for_each(|arm| add_pat(arm.pat)),
+        // Closures are lowered on their own, with their parameters.
+        _ => (),
+    }
+    body.walk_child_exprs(expr, |child| collect_declared_bindings(body, child, acc));
+}
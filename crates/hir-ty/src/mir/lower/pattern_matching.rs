@@ -1,15 +1,18 @@
 //! MIR lowering for patterns
 
-use hir_def::{hir::ExprId, AssocItemId};
+use hir_def::{
+    hir::{ExprId, Literal},
+    AssocItemId,
+};
 
 use crate::{
     mir::{
         lower::{
             BasicBlockId, BinOp, BindingId, BorrowKind, Either, Expr, FieldId, Idx, Interner,
-            MemoryMap, MirLowerCtx, MirLowerError, MirSpan, Mutability, Operand, Pat, PatId, Place,
-            PlaceElem, ProjectionElem, RecordFieldPat, ResolveValueResult, Result, Rvalue,
-            Substitution, SwitchTargets, TerminatorKind, TupleFieldId, TupleId, TyBuilder, TyKind,
-            ValueNs, VariantData, VariantId,
+            MirLowerCtx, MirLowerError, MirSpan, Mutability, Operand, Pat, PatId, Place, PlaceElem,
+            ProjectionElem, RecordFieldPat, ResolveValueResult, Result, Rvalue, Substitution,
+            SwitchTargets, TerminatorKind, TupleFieldId, TupleId, TyBuilder, TyKind, ValueNs,
+            VariantData, VariantId,
         },
         LocalId, MutBorrowKind,
     },
@@ -272,11 +275,10 @@ impl MirLowerCtx<'_> {
                                 pattern.into(),
                             );
                         } else {
-                            let c = Operand::from_concrete_const(
-                                pattern_len.to_le_bytes().into(),
-                                MemoryMap::default(),
+                            let c = self.lower_literal_to_operand(
                                 TyBuilder::usize(),
-                            );
+                                &Literal::Uint(pattern_len as u128, None),
+                            )?;
                             let discr: Place =
                                 self.temp(TyBuilder::bool(), current, pattern.into())?.into();
                             self.push_assignment(
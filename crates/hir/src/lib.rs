@@ -71,7 +71,7 @@ use hir_ty::{
     direct_super_traits, error_lifetime, known_const_to_ast,
     layout::{Layout as TyLayout, RustcEnumVariantIdx, RustcFieldIdx, TagEncoding},
     method_resolution,
-    mir::{interpret_mir, MutBorrowKind},
+    mir::{interpret_mir_with, MutBorrowKind},
     primitive::UintTy,
     traits::FnTrait,
     AliasTy, CallableSig, Canonical, CanonicalVarKinds, Cast, ClosureId, GenericArg,
@@ -98,7 +98,8 @@ pub use crate::{
     diagnostics::*,
     has_source::HasSource,
     semantics::{
-        PathResolution, Semantics, SemanticsImpl, SemanticsScope, TypeInfo, VisibleTraits,
+        CoercionSource, PathResolution, Semantics, SemanticsImpl, SemanticsScope, TypeInfo,
+        VisibleTraits,
     },
 };
 
@@ -275,6 +276,12 @@ impl Crate {
         doc_url.map(|s| s.trim_matches('"').trim_end_matches('/').to_owned() + "/")
     }
 
+    /// The environment variables the crate is compiled with, including the ones its build script
+    /// sets, sorted by name.
+    pub fn env(self, db: &dyn HirDatabase) -> Vec<(String, String)> {
+        self.id.env(db).clone().into()
+    }
+
     pub fn cfg(&self, db: &dyn HirDatabase) -> Arc<CfgOptions> {
         Arc::clone(self.id.cfg_options(db))
     }
@@ -2475,18 +2482,60 @@ impl Function {
         def_map.fn_as_proc_macro(self.id).map(|id| Macro { id: id.into() })
     }
 
+    /// Runs this function in the MIR interpreter. With `check_ub`, the interpreter also reports
+    /// undefined behavior like out of bounds accesses and invalid values.
     pub fn eval(
         self,
         db: &dyn HirDatabase,
         span_formatter: impl Fn(FileId, TextRange) -> String,
+        check_ub: bool,
+    ) -> Result<String, ConstEvalError> {
+        self.eval_with(db, span_formatter, None, check_ub).map(|it| it.output)
+    }
+
+    /// Like [`Function::eval`], but lets `debugger` pause and inspect the evaluation before each
+    /// step.
+    pub fn debug(
+        self,
+        db: &dyn HirDatabase,
+        span_formatter: impl Fn(FileId, TextRange) -> String,
+        debugger: &mut dyn mir::MirDebugger,
     ) -> Result<String, ConstEvalError> {
+        self.eval_with(db, span_formatter, Some(debugger), false).map(|it| it.output)
+    }
+
+    /// Runs this function as a test, taking a `should_panic` attribute into account. `debugger` can
+    /// stop the evaluation early, which fails the test.
+    pub fn eval_test(
+        self,
+        db: &dyn HirDatabase,
+        span_formatter: impl Fn(FileId, TextRange) -> String,
+        debugger: &mut dyn mir::MirDebugger,
+        check_ub: bool,
+    ) -> Result<EvaluatedTest, ConstEvalError> {
+        let mut result = self.eval_with(db, span_formatter, Some(debugger), check_ub)?;
+        if db.attrs(self.id.into()).is_should_panic() {
+            result.passed = result.panicked;
+        }
+        Ok(result)
+    }
+
+    fn eval_with(
+        self,
+        db: &dyn HirDatabase,
+        span_formatter: impl Fn(FileId, TextRange) -> String,
+        debugger: Option<&mut dyn mir::MirDebugger>,
+        check_ub: bool,
+    ) -> Result<EvaluatedTest, ConstEvalError> {
         let body = db.monomorphized_mir_body(
             self.id.into(),
             Substitution::empty(Interner),
             db.trait_environment(self.id.into()),
         )?;
-        let (result, output) = interpret_mir(db, body, false, None)?;
-        let mut text = match result {
+        let debugger = debugger.map(|it| it as &mut dyn mir::MirDebugger);
+        let (result, output) = interpret_mir_with(db, body, debugger, check_ub)?;
+        let panicked = result.as_ref().is_err_and(|e| e.is_panic().is_some());
+        let mut text = match &result {
             Ok(_) => "pass".to_owned(),
             Err(e) => {
                 let mut r = String::new();
@@ -2504,15 +2553,26 @@ impl Function {
             text += "\n--------- stdout ---------\n";
             text += &stdout;
         }
-        let stderr = output.stdout().into_owned();
+        let stderr = output.stderr().into_owned();
         if !stderr.is_empty() {
             text += "\n--------- stderr ---------\n";
             text += &stderr;
         }
-        Ok(text)
+        Ok(EvaluatedTest { passed: result.is_ok(), panicked, output: text })
     }
 }
 
+/// The result of running a function with the mir interpreter, see [`Function::eval_test`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvaluatedTest {
+    pub passed: bool,
+    /// Whether the evaluation stopped with a panic.
+    pub panicked: bool,
+    /// The error with its backtrace if the evaluation failed, followed by the captured stdout
+    /// and stderr.
+    pub output: String,
+}
+
 // Note: logically, this belongs to `hir_ty`, but we are not using it there yet.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Access {
@@ -2745,6 +2805,16 @@ impl Const {
         db.const_eval(self.id.into(), Substitution::empty(Interner), None)
             .map(|it| EvaluatedConst { const_: it, def: self.id.into() })
     }
+
+    /// Like [`Const::eval`], but gives up after `execution_limit` steps of the interpreter.
+    pub fn eval_with_limit(
+        self,
+        db: &dyn HirDatabase,
+        execution_limit: usize,
+    ) -> Result<EvaluatedConst, ConstEvalError> {
+        db.const_eval_with_limit(self.id, execution_limit)
+            .map(|it| EvaluatedConst { const_: it, def: self.id.into() })
+    }
 }
 
 impl HasVisibility for Const {
@@ -2764,31 +2834,34 @@ impl EvaluatedConst {
     }
 
     pub fn render_debug(&self, db: &dyn HirDatabase) -> Result<String, MirEvalError> {
-        let data = self.const_.data(Interner);
-        if let TyKind::Scalar(s) = data.ty.kind(Interner) {
-            if matches!(s, Scalar::Int(_) | Scalar::Uint(_)) {
-                if let hir_ty::ConstValue::Concrete(c) = &data.value {
-                    if let hir_ty::ConstScalar::Bytes(b, _) = &c.interned {
-                        let value = u128::from_le_bytes(mir::pad16(b, false));
-                        let value_signed =
-                            i128::from_le_bytes(mir::pad16(b, matches!(s, Scalar::Int(_))));
-                        let mut result = if let Scalar::Int(_) = s {
-                            value_signed.to_string()
-                        } else {
-                            value.to_string()
-                        };
-                        if value >= 10 {
-                            format_to!(result, " ({value:#X})");
-                            return Ok(result);
-                        } else {
-                            return Ok(result);
-                        }
-                    }
-                }
-            }
+        if let Some(result) = self.render_int() {
+            return Ok(result);
         }
         mir::render_const_using_debug_impl(db, self.def, &self.const_)
     }
+
+    /// Renders integers, with their hexadecimal value if it has more than one digit. Returns
+    /// `None` for other values.
+    pub fn render_int(&self) -> Option<String> {
+        let data = self.const_.data(Interner);
+        let TyKind::Scalar(s @ (Scalar::Int(_) | Scalar::Uint(_))) = data.ty.kind(Interner) else {
+            return None;
+        };
+        let hir_ty::ConstValue::Concrete(c) = &data.value else {
+            return None;
+        };
+        let hir_ty::ConstScalar::Bytes(b, _) = &c.interned else {
+            return None;
+        };
+        let value = u128::from_le_bytes(mir::pad16(b, false));
+        let value_signed = i128::from_le_bytes(mir::pad16(b, matches!(s, Scalar::Int(_))));
+        let mut result =
+            if let Scalar::Int(_) = s { value_signed.to_string() } else { value.to_string() };
+        if value >= 10 {
+            format_to!(result, " ({value:#X})");
+        }
+        Some(result)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -3170,6 +3243,32 @@ impl Macro {
         }
     }
 
+    /// Whether this is `include!`, `include_str!` or `include_bytes!`.
+    pub fn is_include_like(&self, db: &dyn HirDatabase) -> bool {
+        match self.id {
+            MacroId::Macro2Id(it) => {
+                matches!(it.lookup(db.upcast()).expander, MacroExpander::BuiltInEager(eager) if eager.is_include_like())
+            }
+            MacroId::MacroRulesId(it) => {
+                matches!(it.lookup(db.upcast()).expander, MacroExpander::BuiltInEager(eager) if eager.is_include_like())
+            }
+            MacroId::ProcMacroId(_) => false,
+        }
+    }
+
+    /// Whether this is `include!`, whose included file is parsed as Rust.
+    pub fn is_include(&self, db: &dyn HirDatabase) -> bool {
+        match self.id {
+            MacroId::Macro2Id(it) => {
+                matches!(it.lookup(db.upcast()).expander, MacroExpander::BuiltInEager(eager) if eager.is_include())
+            }
+            MacroId::MacroRulesId(it) => {
+                matches!(it.lookup(db.upcast()).expander, MacroExpander::BuiltInEager(eager) if eager.is_include())
+            }
+            MacroId::ProcMacroId(_) => false,
+        }
+    }
+
     pub fn is_asm_or_global_asm(&self, db: &dyn HirDatabase) -> bool {
         match self.id {
             MacroId::Macro2Id(it) => {
@@ -5898,6 +5997,19 @@ impl Callable {
     pub fn ty(&self) -> &Type {
         &self.ty
     }
+
+    /// The type arguments of the called function, like `T` in `size_of::<T>()`.
+    pub fn type_arguments(&self) -> impl Iterator<Item = Type> + '_ {
+        let substs = match self.ty.ty.kind(Interner) {
+            TyKind::FnDef(_, substs) => Some(substs),
+            _ => None,
+        };
+        substs
+            .into_iter()
+            .flat_map(|substs| substs.iter(Interner))
+            .filter_map(|arg| arg.ty(Interner).cloned())
+            .map(move |ty| self.ty.derived(ty))
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -5956,6 +6068,68 @@ impl Layout {
             };
         Some(tag_size)
     }
+
+    /// Where an enum with this layout stores which of its variants it holds, if it has more than
+    /// one.
+    pub fn enum_tag(&self) -> Option<EnumTag> {
+        let layout::Variants::Multiple { tag, tag_encoding, .. } = &self.0.variants else {
+            return None;
+        };
+        Some(EnumTag {
+            // The tag is the only field of the enum itself
+            offset: self.0.fields.offset(0).bytes(),
+            size: tag.size(&*self.1).bytes(),
+            align: tag.align(&*self.1).abi.bytes(),
+            is_niche: matches!(tag_encoding, TagEncoding::Niche { .. }),
+        })
+    }
+
+    /// The layout of `variant`, if this is the layout of its enum. Returns `None` for variants
+    /// which are never constructed, because they are uninhabited.
+    pub fn enum_variant(&self, db: &dyn HirDatabase, variant: Variant) -> Option<Layout> {
+        let idx = RustcEnumVariantIdx(variant.id.lookup(db.upcast()).index as usize);
+        match &self.0.variants {
+            layout::Variants::Multiple { variants, .. } => {
+                Some(Layout(Arc::new(variants.get(idx)?.clone()), self.1.clone()))
+            }
+            layout::Variants::Single { index } if *index == idx => Some(self.clone()),
+            _ => None,
+        }
+    }
+
+    /// The value stored in the niche of an enum with this layout, when it holds `variant`.
+    /// Returns `None` if the enum has a dedicated tag, or `variant` is the one whose fields
+    /// contain the niche.
+    pub fn enum_niche_value(&self, db: &dyn HirDatabase, variant: Variant) -> Option<u128> {
+        let layout::Variants::Multiple {
+            tag,
+            tag_encoding: TagEncoding::Niche { untagged_variant, niche_variants, niche_start },
+            ..
+        } = &self.0.variants
+        else {
+            return None;
+        };
+        let idx = RustcEnumVariantIdx(variant.id.lookup(db.upcast()).index as usize);
+        if idx == *untagged_variant
+            || !(niche_variants.start().0..=niche_variants.end().0).contains(&idx.0)
+        {
+            return None;
+        }
+        let value = ((idx.0 - niche_variants.start().0) as u128).wrapping_add(*niche_start);
+        let bits = tag.size(&*self.1).bits();
+        Some(if bits < 128 { value & ((1 << bits) - 1) } else { value })
+    }
+}
+
+/// The field of an enum holding its tag, which tells which variant the enum holds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EnumTag {
+    pub offset: u64,
+    pub size: u64,
+    pub align: u64,
+    /// Whether the tag is stored in a niche of the fields of one of the variants, instead of
+    /// a field of its own.
+    pub is_niche: bool,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -50,11 +50,12 @@ use crate::{
     db::HirDatabase,
     semantics::source_to_def::{ChildContainer, SourceToDefCache, SourceToDefCtx},
     source_analyzer::{name_hygiene, resolve_hir_path, SourceAnalyzer},
-    Adjust, Adjustment, Adt, AutoBorrow, BindingMode, BuiltinAttr, Callable, Const, ConstParam,
-    Crate, DefWithBody, DeriveHelper, Enum, Field, Function, GenericSubstitution, HasSource,
-    HirFileId, Impl, InFile, InlineAsmOperand, ItemInNs, Label, LifetimeParam, Local, Macro,
-    Module, ModuleDef, Name, OverloadedDeref, Path, ScopeDef, Static, Struct, ToolModule, Trait,
-    TraitAlias, TupleField, Type, TypeAlias, TypeParam, Union, Variant, VariantDef,
+    Adjust, Adjustment, Adt, AutoBorrow, BindingMode, BuiltinAttr, Callable, Const, ConstEvalError,
+    ConstParam, Crate, DefWithBody, DeriveHelper, Enum, EvaluatedConst, Field, Function,
+    GenericSubstitution, HasSource, HirFileId, Impl, InFile, InlineAsmOperand, ItemInNs, Label,
+    LifetimeParam, Local, Macro, Module, ModuleDef, Name, OverloadedDeref, Path, ScopeDef, Static,
+    Struct, ToolModule, Trait, TraitAlias, TupleField, Type, TypeAlias, TypeParam, Union, Variant,
+    VariantDef,
 };
 
 const CONTINUE_NO_BREAKS: ControlFlow<Infallible, ()> = ControlFlow::Continue(());
@@ -129,6 +130,21 @@ impl TypeInfo {
     }
 }
 
+/// Where the type an expression was coerced or unified to came from.
+#[derive(Debug)]
+pub enum CoercionSource {
+    /// The type annotation of the `let` statement the expression initializes.
+    LetAnnotation,
+    /// The parameter at `index` of the function called by `call`, not counting the receiver.
+    Argument { call: ast::Expr, index: usize },
+    /// The return type of the enclosing function, closure or async block.
+    Return,
+    /// The other branches or elements of this `if`, `match` or array expression.
+    Branches(ast::Expr),
+    /// The field of a record literal.
+    Field(Field),
+}
+
 /// Primary API to get semantic information, like types, from syntax trees.
 pub struct Semantics<'db, DB> {
     pub db: &'db DB,
@@ -1431,6 +1447,39 @@ impl<'db> SemanticsImpl<'db> {
             .map(|(ty, coerced)| TypeInfo { original: ty, adjusted: coerced })
     }
 
+    /// Returns the expected and the actual type of `expr` if they don't match.
+    pub fn type_mismatch_of_expr(&self, expr: &ast::Expr) -> Option<(Type, Type)> {
+        self.analyze(expr.syntax())?.type_mismatch_of_expr(self.db, expr)
+    }
+
+    /// Returns the type `expr` was coerced or unified to during inference and where it came from.
+    ///
+    /// This infers the containing body again, so it should only be used on demand.
+    pub fn coercion_source_of_expr(&self, expr: &ast::Expr) -> Option<(CoercionSource, Type)> {
+        let analyzer = self.analyze(expr.syntax())?;
+        let (source, ty) = analyzer.coercion_source_of_expr(self.db, expr)?;
+        let to_node = |expr| {
+            let ptr = analyzer.expr_syntax(expr)?;
+            ptr.value.to_node(&self.parse_or_expand(ptr.file_id)).left()
+        };
+        let source = match source {
+            hir_ty::CoercionSource::LetAnnotation => CoercionSource::LetAnnotation,
+            hir_ty::CoercionSource::Argument { call, index } => {
+                CoercionSource::Argument { call: to_node(call)?, index }
+            }
+            hir_ty::CoercionSource::Return => CoercionSource::Return,
+            hir_ty::CoercionSource::Branches(expr) => CoercionSource::Branches(to_node(expr)?),
+            hir_ty::CoercionSource::Field(field) => CoercionSource::Field(field.into()),
+        };
+        Some((source, ty))
+    }
+
+    /// Evaluates `expr` on its own with the MIR interpreter. Fails if the expression uses locals
+    /// declared outside of it.
+    pub fn eval_expr(&self, expr: &ast::Expr) -> Option<Result<EvaluatedConst, ConstEvalError>> {
+        self.analyze(expr.syntax())?.eval_expr(self.db, expr)
+    }
+
     pub fn type_of_pat(&self, pat: &ast::Pat) -> Option<TypeInfo> {
         self.analyze(pat.syntax())?
             .type_of_pat(self.db, pat)
@@ -2029,6 +2078,25 @@ impl SemanticsScope<'_> {
         })
     }
 
+    /// Whether this scope is inside a closure or an async block, where `return` and `?` apply to
+    /// them rather than to the containing function.
+    pub fn is_inside_closure_or_async_block(&self) -> bool {
+        let Some((owner, scope)) = self.resolver.expr_scope() else { return false };
+        let body = self.db.body(owner);
+        let expr_scopes = self.db.expr_scopes(owner);
+        let inner_scopes: FxHashSet<_> = body
+            .exprs
+            .iter()
+            .filter_map(|(id, expr)| match expr {
+                Expr::Closure { body, .. } => expr_scopes.scope_for(*body),
+                Expr::Async { .. } => expr_scopes.scope_for(id),
+                _ => None,
+            })
+            .collect();
+        let mut scope_chain = expr_scopes.scope_chain(Some(scope));
+        scope_chain.any(|it| inner_scopes.contains(&it))
+    }
+
     pub(crate) fn resolver(&self) -> &Resolver {
         &self.resolver
     }
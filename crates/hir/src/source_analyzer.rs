@@ -9,14 +9,15 @@ use std::iter::{self, once};
 
 use crate::{
     db::HirDatabase, semantics::PathResolution, Adt, AssocItem, BindingMode, BuiltinAttr,
-    BuiltinType, Callable, Const, DeriveHelper, Field, Function, GenericSubstitution, Local, Macro,
-    ModuleDef, Static, Struct, ToolModule, Trait, TraitAlias, TupleField, Type, TypeAlias, Variant,
+    BuiltinType, Callable, Const, DeriveHelper, EvaluatedConst, Field, Function,
+    GenericSubstitution, Local, Macro, ModuleDef, Static, Struct, ToolModule, Trait, TraitAlias,
+    TupleField, Type, TypeAlias, Variant,
 };
 use either::Either;
 use hir_def::{
     expr_store::{
         scope::{ExprScopes, ScopeId},
-        Body, BodySourceMap, HygieneId,
+        Body, BodySourceMap, ExprOrPatSource, HygieneId,
     },
     hir::{BindingId, Expr, ExprId, ExprOrPatId, Pat},
     lang_item::LangItem,
@@ -34,14 +35,15 @@ use hir_expand::{
     HirFileId, InFile, InMacroFile, MacroFileId, MacroFileIdExt,
 };
 use hir_ty::{
+    consteval::{self, ConstEvalError},
     diagnostics::{
         record_literal_missing_fields, record_pattern_missing_fields, unsafe_operations,
         InsideUnsafeBlock,
     },
     from_assoc_type_id,
     lang_items::lang_items_for_bin_op,
-    method_resolution, Adjustment, InferenceResult, Interner, Substitution, TraitEnvironment, Ty,
-    TyExt, TyKind, TyLoweringContext,
+    method_resolution, Adjustment, CoercionSource, InferenceResult, Interner, Substitution,
+    TraitEnvironment, Ty, TyExt, TyKind, TyLoweringContext,
 };
 use intern::sym;
 use itertools::Itertools;
@@ -189,6 +191,19 @@ impl SourceAnalyzer {
         infer.expr_adjustments.get(&expr_id).map(|v| &**v)
     }
 
+    pub(crate) fn eval_expr(
+        &self,
+        db: &dyn HirDatabase,
+        expr: &ast::Expr,
+    ) -> Option<Result<EvaluatedConst, ConstEvalError>> {
+        let (def, ..) = self.def.as_ref()?;
+        let expr_id = self.expr_id(db, expr)?.as_expr()?;
+        Some(
+            consteval::eval_expr_in_body(db, *def, expr_id)
+                .map(|const_| EvaluatedConst { def: *def, const_ }),
+        )
+    }
+
     pub(crate) fn type_of_expr(
         &self,
         db: &dyn HirDatabase,
@@ -205,6 +220,33 @@ impl SourceAnalyzer {
         Some((mk_ty(ty), coerced.map(mk_ty)))
     }
 
+    pub(crate) fn type_mismatch_of_expr(
+        &self,
+        db: &dyn HirDatabase,
+        expr: &ast::Expr,
+    ) -> Option<(Type, Type)> {
+        let expr_id = self.expr_id(db, expr)?.as_expr()?;
+        let mismatch = self.infer.as_ref()?.type_mismatch_for_expr(expr_id)?;
+        let mk_ty = |ty: &Ty| Type::new_with_resolver(db, &self.resolver, ty.clone());
+        Some((mk_ty(&mismatch.expected), mk_ty(&mismatch.actual)))
+    }
+
+    pub(crate) fn coercion_source_of_expr(
+        &self,
+        db: &dyn HirDatabase,
+        expr: &ast::Expr,
+    ) -> Option<(CoercionSource, Type)> {
+        let (def, ..) = self.def.as_ref()?;
+        let expr_id = self.expr_id(db, expr)?.as_expr()?;
+        let coercion_sources = db.coercion_sources(*def);
+        let (source, ty) = coercion_sources.get(expr_id)?;
+        Some((source, Type::new_with_resolver(db, &self.resolver, ty.clone())))
+    }
+
+    pub(crate) fn expr_syntax(&self, expr: ExprId) -> Option<ExprOrPatSource> {
+        self.body_source_map()?.expr_syntax(expr).ok()
+    }
+
     pub(crate) fn type_of_pat(
         &self,
         db: &dyn HirDatabase,
@@ -16,7 +16,9 @@ mod tactics;
 #[derive(Debug, Hash, PartialEq, Eq)]
 enum NewTypesKey {
     ImplMethod,
+    ImplMethodWithClosure,
     StructProjection,
+    Try,
 }
 
 /// Helper enum to squash big number of alternative trees into `Many` variant as there is too many
@@ -104,7 +106,9 @@ impl LookupTable {
     fn new(many_threshold: usize, goal: Type) -> Self {
         let mut res = Self { many_threshold, ..Default::default() };
         res.new_types.insert(NewTypesKey::ImplMethod, Vec::new());
+        res.new_types.insert(NewTypesKey::ImplMethodWithClosure, Vec::new());
         res.new_types.insert(NewTypesKey::StructProjection, Vec::new());
+        res.new_types.insert(NewTypesKey::Try, Vec::new());
         res.types_wishlist.insert(goal);
         res
     }
@@ -255,8 +259,9 @@ impl Default for TermSearchConfig {
 ///    as well as from well knows values (such as `true/false` and `()`)
 /// 2. Iteratively expand the frontier (or contents of the lookup) by trying different type
 ///    transformation tactics. For example functions take as from set of types (arguments) to some
-///    type (return type). Other transformations include methods on type, type constructors and
-///    projections to struct fields (field access).
+///    type (return type). Other transformations include methods on type, type constructors,
+///    projections to struct fields (field access), methods taking closures and unwrapping with
+///    the `?` operator.
 /// 3. If we run out of fuel (term search takes too long) we stop iterating.
 /// 4. Return all the paths (type trees) that take us to the `goal` type.
 ///
@@ -297,6 +302,13 @@ pub fn term_search<DB: HirDatabase>(ctx: &TermSearchCtx<'_, DB>) -> Vec<Expr> {
         solutions.extend(tactics::struct_projection(ctx, &defs, &mut lookup, should_continue));
         solutions.extend(tactics::impl_static_method(ctx, &defs, &mut lookup, should_continue));
         solutions.extend(tactics::make_tuple(ctx, &defs, &mut lookup, should_continue));
+        solutions.extend(tactics::impl_method_with_closure(
+            ctx,
+            &defs,
+            &mut lookup,
+            should_continue,
+        ));
+        solutions.extend(tactics::try_operator(ctx, &defs, &mut lookup, should_continue));
     }
 
     solutions.into_iter().filter(|it| !it.is_many()).unique().collect()
@@ -84,6 +84,12 @@ pub enum Expr {
     Field { expr: Box<Expr>, field: Field },
     /// Passing type as reference (with `&`)
     Reference(Box<Expr>),
+    /// Closure passed as an argument, takes at most one parameter named `it`
+    Closure { param: Option<Type>, body: Box<Expr> },
+    /// Parameter of the enclosing closure
+    ClosureParam(Type),
+    /// Propagating the error or `None` with the `?` operator, `ty` is the type of the output
+    Try { ty: Type, expr: Box<Expr> },
     /// Indicates possibility of many different options that all evaluate to `ty`
     Many(Type),
 }
@@ -291,13 +297,31 @@ impl Expr {
                     expr.gen_source_code(sema_scope, many_formatter, cfg, display_target)?;
                 Ok(format!("&{inner}"))
             }
+            Expr::Closure { param, body } => {
+                let body = body.gen_source_code(sema_scope, many_formatter, cfg, display_target)?;
+                match param {
+                    Some(_) => Ok(format!("|it| {body}")),
+                    None => Ok(format!("|| {body}")),
+                }
+            }
+            Expr::ClosureParam(_) => Ok(String::from("it")),
+            Expr::Try { expr, .. } => {
+                if expr.contains_many_in_illegal_pos(db) {
+                    return Ok(many_formatter(&expr.ty(db)));
+                }
+
+                let inner =
+                    expr.gen_source_code(sema_scope, many_formatter, cfg, display_target)?;
+                Ok(format!("{inner}?"))
+            }
             Expr::Many(ty) => Ok(many_formatter(ty)),
         }
     }
 
     /// Get type of the type tree.
     ///
-    /// Same as getting the type of root node
+    /// Same as getting the type of root node. Closures have no nameable type, so for them the
+    /// type of the closure body is returned.
     pub fn ty(&self, db: &dyn HirDatabase) -> Type {
         match self {
             Expr::Const(it) => it.ty(db),
@@ -308,10 +332,16 @@ impl Expr {
             Expr::Function { func, generics, .. } => {
                 func.ret_type_with_args(db, generics.iter().cloned())
             }
-            Expr::Method { func, generics, target, .. } => func.ret_type_with_args(
-                db,
-                target.ty(db).type_arguments().chain(generics.iter().cloned()),
-            ),
+            Expr::Method { func, generics, target, .. } => {
+                // Generics of the impl are the type arguments of the target, the only generic of
+                // the (non-generic) trait declaring a provided method is the target itself
+                let parent_args: Vec<Type> = match func.as_assoc_item(db).map(|it| it.container(db))
+                {
+                    Some(AssocItemContainer::Trait(_)) => vec![target.ty(db)],
+                    _ => target.ty(db).type_arguments().collect(),
+                };
+                func.ret_type_with_args(db, parent_args.into_iter().chain(generics.iter().cloned()))
+            }
             Expr::Variant { variant, generics, .. } => {
                 Adt::from(variant.parent_enum(db)).ty_with_args(db, generics.iter().cloned())
             }
@@ -321,6 +351,9 @@ impl Expr {
             Expr::Tuple { ty, .. } => ty.clone(),
             Expr::Field { expr, field } => field.ty_with_args(db, expr.ty(db).type_arguments()),
             Expr::Reference(it) => it.ty(db),
+            Expr::Closure { body, .. } => body.ty(db),
+            Expr::ClosureParam(ty) => ty.clone(),
+            Expr::Try { ty, .. } => ty.clone(),
             Expr::Many(ty) => ty.clone(),
         }
     }
@@ -329,18 +362,47 @@ impl Expr {
     pub fn traits_used(&self, db: &dyn HirDatabase) -> Vec<Trait> {
         let mut res = Vec::new();
 
-        if let Expr::Method { func, params, .. } = self {
-            res.extend(params.iter().flat_map(|it| it.traits_used(db)));
-            if let Some(it) = func.as_assoc_item(db) {
-                if let Some(it) = it.container_or_implemented_trait(db) {
-                    res.push(it);
+        match self {
+            Expr::Method { func, params, .. } => {
+                res.extend(params.iter().flat_map(|it| it.traits_used(db)));
+                if let Some(it) = func.as_assoc_item(db) {
+                    if let Some(it) = it.container_or_implemented_trait(db) {
+                        res.push(it);
+                    }
                 }
             }
+            Expr::Closure { body, .. } => res.extend(body.traits_used(db)),
+            _ => (),
         }
 
         res
     }
 
+    /// Rough measure of how complex the type tree is, the number of values, calls, constructors
+    /// and projections it is built from. Cheaper trees are usually the ones users intend to write.
+    pub fn cost(&self) -> usize {
+        match self {
+            Expr::Const(_)
+            | Expr::Static(_)
+            | Expr::Local(_)
+            | Expr::ConstParam(_)
+            | Expr::ClosureParam(_)
+            | Expr::Many(_) => 1,
+            // Values like `true` or `""` are guesses, unlike values that already exist
+            Expr::FamousType { .. } => 2,
+            Expr::Function { params, .. }
+            | Expr::Variant { params, .. }
+            | Expr::Struct { params, .. }
+            | Expr::Tuple { params, .. } => 1 + params.iter().map(Expr::cost).sum::<usize>(),
+            Expr::Method { target, params, .. } => {
+                1 + target.cost() + params.iter().map(Expr::cost).sum::<usize>()
+            }
+            Expr::Field { expr, .. } | Expr::Try { expr, .. } => 1 + expr.cost(),
+            Expr::Closure { body, .. } => 1 + body.cost(),
+            Expr::Reference(it) => it.cost(),
+        }
+    }
+
     /// Check in the tree contains `Expr::Many` variant in illegal place to insert `todo`,
     /// `unimplemented` or similar macro
     ///
@@ -349,6 +411,7 @@ impl Expr {
     /// macro!().foo
     /// macro!().bar()
     /// &macro!()
+    /// macro!()?
     /// ```
     fn contains_many_in_illegal_pos(&self, db: &dyn HirDatabase) -> bool {
         match self {
@@ -359,7 +422,7 @@ impl Expr {
                 }
             }
             Expr::Field { expr, .. } => expr.contains_many_in_illegal_pos(db),
-            Expr::Reference(target) => target.is_many(),
+            Expr::Reference(target) | Expr::Try { expr: target, .. } => target.is_many(),
             Expr::Many(_) => true,
             _ => false,
         }
@@ -10,16 +10,24 @@
 
 use std::iter;
 
+use hir_def::{lang_item::LangItem, TraitId};
+use hir_expand::name::Name;
 use hir_ty::db::HirDatabase;
 use hir_ty::mir::BorrowKind;
-use hir_ty::TyBuilder;
+use hir_ty::traits::FnTrait;
+use hir_ty::{
+    consteval::unknown_const_as_generic, error_lifetime, from_assoc_type_id, to_placeholder_idx,
+    AliasEq, AliasTy, CallableSig, Cast, FnAbi, Interner, ParamKind, ProjectionTyExt, Safety,
+    Substitution, TraitRefExt, Ty, TyBuilder, TyExt, TyKind, WhereClause,
+};
+use intern::{sym, Symbol};
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 use span::Edition;
 
 use crate::{
-    Adt, AssocItem, GenericDef, GenericParam, HasAttrs, HasVisibility, Impl, ModuleDef, ScopeDef,
-    Type, TypeParam,
+    Adt, AsAssocItem, AssocItem, AssocItemContainer, Function, GenericDef, GenericParam, HasAttrs,
+    HasVisibility, Impl, ModuleDef, ScopeDef, StructKind, Trait, Type, TypeAlias, TypeParam,
 };
 
 use crate::term_search::Expr;
@@ -291,7 +299,9 @@ pub(super) fn data_constructor<'a, DB: HirDatabase>(
 /// # Free function tactic
 ///
 /// Attempts to call different functions in scope with parameters from lookup table.
-/// Functions that include generics are not used for performance reasons.
+/// Generic functions are only used if they have at most one type parameter that can be inferred
+/// from the arguments. It is instantiated with types from lookup table that satisfy its trait
+/// bounds, functions with more generics are not used for performance reasons.
 ///
 /// Updates lookup by new types reached and returns iterator that yields
 /// elements that unify with `goal`.
@@ -336,10 +346,26 @@ pub(super) fn free_function<'a, DB: HirDatabase>(
                     type_params.iter().filter(|it| it.default(db).is_none()).count();
 
                 // Ignore bigger number of generics for now as they kill the performance
-                if non_default_type_params_len > 0 {
+                if non_default_type_params_len > 1 {
                     return None;
                 }
 
+                // Only use generics that appear in parameters, otherwise the call would need
+                // explicit generic arguments
+                if non_default_type_params_len > 0 {
+                    let params_generics: FxHashSet<GenericParam> = it
+                        .params_without_self(db)
+                        .iter()
+                        .flat_map(|param| param.ty().generic_params(db))
+                        .collect();
+                    if type_params.iter().any(|param| {
+                        param.default(db).is_none()
+                            && !params_generics.contains(&GenericParam::TypeParam(*param))
+                    }) {
+                        return None;
+                    }
+                }
+
                 let generic_params = lookup
                     .iter_types()
                     .collect::<Vec<_>>() // Force take ownership
@@ -353,15 +379,20 @@ pub(super) fn free_function<'a, DB: HirDatabase>(
                         let mut g = generics.into_iter();
                         let generics: Vec<_> = type_params
                             .iter()
-                            .map(|it| match it.default(db) {
-                                Some(ty) => Some(ty),
-                                None => {
-                                    let generic = g.next().expect("Missing type param");
-                                    // Filter out generics that do not unify due to trait bounds
-                                    it.ty(db).could_unify_with(db, &generic).then_some(generic)
-                                }
+                            .map(|it| {
+                                it.default(db)
+                                    .unwrap_or_else(|| g.next().expect("Missing type param"))
                             })
-                            .collect::<Option<_>>()?;
+                            .collect();
+
+                        // Filter out generics that do not satisfy the trait bounds
+                        if type_params.iter().zip(&generics).any(|(param, generic)| {
+                            param.default(db).is_none()
+                                && (generic.contains_unknown()
+                                    || !satisfies_trait_bounds(db, *param, generic, &generics))
+                        }) {
+                            return None;
+                        }
 
                         let ret_ty = it.ret_type_with_args(db, generics.iter().cloned());
                         // Filter out private and unsafe functions
@@ -771,3 +802,578 @@ pub(super) fn make_tuple<'a, DB: HirDatabase>(
         .flatten()
         .filter_map(|expr| expr.ty(db).could_unify_with_deeply(db, &ctx.goal).then_some(expr))
 }
+
+/// # Impl method with closure tactic
+///
+/// Attempts to call methods that take a closure, such as `Option::map` or
+/// `Result::unwrap_or_else`, on types from lookup table.
+/// Besides methods from impls of the receiver type, provided methods of non-generic traits in
+/// scope that the receiver implements are used, so iterator adapters such as `Iterator::map` are
+/// found too.
+/// Only methods that take exactly one closure with at most one parameter are used. The closure
+/// body is either a value from lookup table (for closures without parameters) or a single call,
+/// field access or tuple constructor applied to the closure parameter.
+///
+/// The closure type cannot be named, so a function pointer type with the closure signature stands
+/// in for it where it shows up in the return type, for example in `Map<I, fn(u32) -> Name>`.
+///
+/// Updates lookup by new types reached and returns iterator that yields
+/// elements that unify with `goal`.
+///
+/// # Arguments
+/// * `ctx` - Context for the term search
+/// * `defs` - Set of items in scope at term search target location
+/// * `lookup` - Lookup table for types
+/// * `should_continue` - Function that indicates when to stop iterating
+pub(super) fn impl_method_with_closure<'a, DB: HirDatabase>(
+    ctx: &'a TermSearchCtx<'a, DB>,
+    defs: &'a FxHashSet<ScopeDef>,
+    lookup: &'a mut LookupTable,
+    should_continue: &'a dyn std::ops::Fn() -> bool,
+) -> impl Iterator<Item = Expr> + 'a {
+    let db = ctx.sema.db;
+    let module = ctx.scope.module();
+    let traits_in_scope = ctx.scope.visible_traits().0;
+    lookup
+        .new_types(NewTypesKey::ImplMethodWithClosure)
+        .into_iter()
+        .filter(|ty| !ty.type_arguments().any(|it| it.contains_unknown()))
+        .filter(|_| should_continue())
+        .flat_map(move |ty| {
+            let impls = Impl::all_for_type(db, ty.clone());
+
+            // Type arguments of `ty` are used as impl generics, so they have to match one to one
+            let impl_methods = impls
+                .iter()
+                .filter(|imp| self_ty_args_are_impl_params(db, **imp))
+                .flat_map(|imp| {
+                    imp.items(db).into_iter().map(|item| (GenericDef::from(*imp), item))
+                })
+                .filter_map(|(parent, item)| Some((parent, item.as_function()?)))
+                .map(|(parent, it)| (parent, it, ty.type_arguments().collect()))
+                .collect::<Vec<_>>();
+
+            // Provided methods not overridden in the impl, with `ty` as the only trait generic
+            let provided_methods = traits_in_scope
+                .iter()
+                .map(|it| Trait::from(*it))
+                .filter(|it| it.type_or_const_param_count(db, false) == 0)
+                .filter(|it| ty.impls_trait(db, *it, &[]))
+                .flat_map(|trait_| {
+                    let overridden: Vec<Name> = impls
+                        .iter()
+                        .filter(|imp| imp.trait_(db) == Some(trait_))
+                        .flat_map(|imp| imp.items(db))
+                        .filter_map(|item| item.name(db))
+                        .collect();
+                    trait_
+                        .items(db)
+                        .into_iter()
+                        .filter_map(AssocItem::as_function)
+                        .filter(move |it| it.has_body(db) && !overridden.contains(&it.name(db)))
+                        .map(move |it| (GenericDef::from(trait_), it))
+                })
+                .map(|(parent, it)| (parent, it, vec![ty.clone()]))
+                .collect::<Vec<_>>();
+
+            impl_methods.into_iter().chain(provided_methods).map(move |it| (ty.clone(), it))
+        })
+        .filter(|_| should_continue())
+        .filter_map(move |(ty, (parent_generics, it, parent_args))| {
+            let fn_generics = GenericDef::from(it);
+
+            // Ignore all functions that have something to do with lifetimes as we don't check them
+            if !fn_generics.lifetime_params(db).is_empty()
+                || !parent_generics.lifetime_params(db).is_empty()
+            {
+                return None;
+            }
+
+            // Ignore functions without self param
+            if !it.has_self_param(db) {
+                return None;
+            }
+
+            // Filter out private and unsafe functions
+            if !it.is_visible_from(db, module)
+                || it.is_unsafe_to_call(db, None, Edition::CURRENT_FIXME)
+                || it.is_unstable(db)
+            {
+                return None;
+            }
+
+            // Ignore const params for now
+            let type_params = fn_generics
+                .type_or_const_params(db)
+                .into_iter()
+                .map(|it| it.as_type_param(db))
+                .collect::<Option<Vec<TypeParam>>>()?;
+
+            // Find the only parameter that is a closure
+            let (closure_idx, closure_ty_param, callable) = it
+                .params_without_self(db)
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, param)| {
+                    let ty_param = param.ty().as_type_param(db)?;
+                    let callable = param.ty().as_callable(db)?;
+                    Some((idx, ty_param, callable))
+                })
+                .exactly_one()
+                .ok()?;
+
+            // Apart from the closure, the only generic parameter allowed is the one returned from
+            // the closure as it is decided by the closure body
+            let ret_ty_param =
+                callable.return_type().as_type_param(db).filter(|it| type_params.contains(it));
+            if type_params.iter().any(|it| *it != closure_ty_param && Some(*it) != ret_ty_param) {
+                return None;
+            }
+
+            let (closure_params, closure_ret) =
+                closure_signature(db, it, closure_ty_param, &parent_args, &ty)?;
+            let closure_param = match closure_params.as_slice() {
+                [] => None,
+                [param] if !param.contains_unknown() => Some(param.clone()),
+                _ => return None,
+            };
+            let closure_ret = match ret_ty_param {
+                Some(_) => None,
+                None if !closure_ret.contains_unknown() => Some(closure_ret),
+                None => return None,
+            };
+
+            let bodies: Vec<Expr> = match (&closure_param, &closure_ret) {
+                (None, Some(ret_ty)) => lookup.find(db, ret_ty)?,
+                (None, None) => return None,
+                (Some(param_ty), _) => closure_bodies(ctx, defs, param_ty)
+                    .into_iter()
+                    .filter(|body| match &closure_ret {
+                        Some(ret_ty) => body.ty(db).could_unify_with_deeply(db, ret_ty),
+                        None => true,
+                    })
+                    .collect(),
+            };
+
+            let self_ty = it
+                .self_param(db)
+                .expect("No self param")
+                .ty_with_args(db, parent_args.iter().cloned());
+
+            // Ignore functions that have different self type, note that the types are not compared
+            // directly as the method environment includes the closure bounds
+            if !self_ty.autoderef(db).any(|s_ty| s_ty.could_unify_with_deeply(db, &ty)) {
+                return None;
+            }
+
+            let target_type_exprs = lookup.find(db, &ty).expect("Type not in lookup");
+
+            let exprs: Vec<(Type, Vec<Expr>)> = bodies
+                .into_iter()
+                .filter(|_| should_continue())
+                .filter_map(|body| {
+                    let body_ty = body.ty(db);
+                    let closure_sig = CallableSig::from_params_and_return(
+                        closure_params.iter().map(|it| it.ty.clone()),
+                        body_ty.ty.clone(),
+                        false,
+                        Safety::Safe,
+                        FnAbi::Rust,
+                    );
+                    let closure_ty = ty.derived(TyBuilder::fn_ptr(closure_sig));
+                    let generics: Vec<Type> = type_params
+                        .iter()
+                        .map(|it| match *it == closure_ty_param {
+                            true => closure_ty.clone(),
+                            false => body_ty.clone(),
+                        })
+                        .collect();
+                    let args = || parent_args.iter().cloned().chain(generics.iter().cloned());
+
+                    let ret_ty = it.ret_type_with_args(db, args());
+                    // Filter out functions that return references
+                    if ctx.config.enable_borrowcheck && ret_ty.contains_reference(db)
+                        || ret_ty.is_raw_ptr()
+                        || ret_ty.contains_unknown()
+                    {
+                        return None;
+                    }
+
+                    let closure =
+                        Expr::Closure { param: closure_param.clone(), body: Box::new(body) };
+
+                    // Early exit if some param cannot be filled from lookup
+                    let param_exprs: Vec<Vec<Expr>> = it
+                        .params_without_self_with_args(db, args())
+                        .into_iter()
+                        .enumerate()
+                        .map(|(idx, field)| match idx == closure_idx {
+                            true => Some(vec![closure.clone()]),
+                            false => lookup.find_autoref(db, field.ty()),
+                        })
+                        .collect::<Option<_>>()?;
+
+                    let fn_exprs: Vec<Expr> = iter::once(target_type_exprs.clone())
+                        .chain(param_exprs)
+                        .multi_cartesian_product()
+                        .map(|params| {
+                            let mut params = params.into_iter();
+                            let target = Box::new(params.next().unwrap());
+                            Expr::Method {
+                                func: it,
+                                generics: generics.clone(),
+                                target,
+                                params: params.collect(),
+                            }
+                        })
+                        .collect();
+
+                    lookup.insert(ret_ty.clone(), fn_exprs.iter().cloned());
+                    Some((ret_ty, fn_exprs))
+                })
+                .collect();
+
+            Some(exprs)
+        })
+        .flatten()
+        .filter_map(|(ty, exprs)| ty.could_unify_with_deeply(db, &ctx.goal).then_some(exprs))
+        .flatten()
+}
+
+/// # Try operator tactic
+///
+/// Attempts to unwrap types from lookup table with the `?` operator, for example `Option<T>` to
+/// `T` or `Result<T, E>` to `T`. The operator is only used if the return type of the enclosing
+/// function can be built from the residual, so errors are converted with `From` impls.
+///
+/// Updates lookup by new types reached and returns iterator that yields
+/// elements that unify with `goal`.
+///
+/// # Arguments
+/// * `ctx` - Context for the term search
+/// * `defs` - Set of items in scope at term search target location
+/// * `lookup` - Lookup table for types
+/// * `should_continue` - Function that indicates when to stop iterating
+pub(super) fn try_operator<'a, DB: HirDatabase>(
+    ctx: &'a TermSearchCtx<'a, DB>,
+    _defs: &'a FxHashSet<ScopeDef>,
+    lookup: &'a mut LookupTable,
+    should_continue: &'a dyn std::ops::Fn() -> bool,
+) -> impl Iterator<Item = Expr> + 'a {
+    let db = ctx.sema.db;
+    let krate = ctx.scope.krate();
+
+    // Inside closures and async blocks `?` applies to them, and their return type is inferred from
+    // the very expressions we would suggest.
+    let fn_ret_ty = ctx
+        .scope
+        .containing_function()
+        .filter(|_| !ctx.scope.is_inside_closure_or_async_block())
+        .map(|it| it.async_ret_type(db).unwrap_or_else(|| it.ret_type(db)));
+    let try_trait =
+        db.lang_item(krate.into(), LangItem::Try).and_then(|it| it.as_trait()).map(Trait::from);
+    let from_residual_trait = db
+        .lang_item(krate.into(), LangItem::TryTraitFromResidual)
+        .and_then(|it| it.as_function())
+        .and_then(|it| Function::from(it).as_assoc_item(db)?.container_trait(db));
+    let assoc_ty = |name: Symbol| {
+        let name = Name::new_symbol_root(name);
+        try_trait?
+            .items(db)
+            .into_iter()
+            .filter_map(AssocItem::as_type_alias)
+            .find(|it| it.name(db) == name)
+    };
+    let output = assoc_ty(sym::Output.clone());
+    let residual = assoc_ty(sym::Residual.clone());
+
+    lookup
+        .new_types(NewTypesKey::Try)
+        .into_iter()
+        .filter(|ty| !ty.contains_unknown())
+        .filter(|_| should_continue())
+        .filter_map(move |ty| {
+            let fn_ret_ty = fn_ret_ty.as_ref()?;
+            if !ty.impls_trait(db, try_trait?, &[]) {
+                return None;
+            }
+
+            let output_ty = ty.normalize_trait_assoc_type(db, &[], output?)?;
+            let residual_ty = ty.normalize_trait_assoc_type(db, &[], residual?)?;
+
+            // Check that the residual can be propagated to the return type
+            if !fn_ret_ty.impls_trait(db, from_residual_trait?, &[residual_ty]) {
+                return None;
+            }
+
+            // Unwrapping a placeholder for many alternatives is not a meaningful suggestion
+            let exprs: Vec<Expr> = lookup
+                .find(db, &ty)?
+                .into_iter()
+                .filter(|expr| !expr.is_many())
+                .map(|expr| Expr::Try { ty: output_ty.clone(), expr: Box::new(expr) })
+                .collect();
+
+            lookup.insert(output_ty.clone(), exprs.iter().cloned());
+            Some((output_ty, exprs))
+        })
+        .filter_map(|(ty, exprs)| ty.could_unify_with_deeply(db, &ctx.goal).then_some(exprs))
+        .flatten()
+}
+
+/// Check if `ty` satisfies all the bounds of `param`
+///
+/// The bounds are checked with the generics of the item declaring `param` substituted by `args`,
+/// so bounds of generic traits (such as `T: Into<String>`) and their associated types (such as
+/// `T: Iterator<Item = u32>`) are supported. The item must not have parent generics.
+fn satisfies_trait_bounds(
+    db: &dyn HirDatabase,
+    param: TypeParam,
+    ty: &Type,
+    args: &[Type],
+) -> bool {
+    let mut args = args.iter().map(|it| it.ty.clone());
+    let subst = TyBuilder::subst_for_def(db, param.id.parent(), None)
+        .fill(|x| match x {
+            ParamKind::Type => {
+                args.next().unwrap_or_else(|| TyKind::Error.intern(Interner)).cast(Interner)
+            }
+            ParamKind::Const(ty) => unknown_const_as_generic(ty.clone()),
+            ParamKind::Lifetime => error_lifetime().cast(Interner),
+        })
+        .build();
+    // The first type of a trait or projection substitution is the self type, the rest are the
+    // arguments of the trait.
+    let trait_args = |subst: &Substitution| -> Vec<Type> {
+        subst
+            .iter(Interner)
+            .skip(1)
+            .filter_map(|it| it.ty(Interner))
+            .map(|it| ty.derived(it.clone()))
+            .collect()
+    };
+    param_bounds(db, param, &subst).iter().all(|pred| match pred {
+        WhereClause::Implemented(trait_ref) => {
+            let trait_ = Trait::from(trait_ref.hir_trait_id());
+            ty.impls_trait(db, trait_, &trait_args(&trait_ref.substitution))
+        }
+        WhereClause::AliasEq(AliasEq { alias: AliasTy::Projection(proj), ty: expected }) => {
+            let alias = TypeAlias::from(from_assoc_type_id(proj.associated_ty_id));
+            ty.normalize_trait_assoc_type(db, &trait_args(&proj.substitution), alias)
+                .is_some_and(|it| it.could_unify_with_deeply(db, &ty.derived(expected.clone())))
+        }
+        _ => false,
+    })
+}
+
+/// Bounds of `param`, including associated type bindings, with the generics of the item declaring
+/// it substituted by `subst`
+fn param_bounds(db: &dyn HirDatabase, param: TypeParam, subst: &Substitution) -> Vec<WhereClause> {
+    let def = param.id.parent();
+    let placeholders = TyBuilder::placeholder_subst(db, def);
+    let param_ty = to_placeholder_idx(db, param.id.into()).to_ty(Interner);
+    db.generic_predicates(def)
+        .iter()
+        .filter(|pred| {
+            let self_ty = match (*pred).clone().substitute(Interner, &placeholders).skip_binders() {
+                WhereClause::Implemented(trait_ref) => trait_ref.self_type_parameter(Interner),
+                WhereClause::AliasEq(AliasEq { alias: AliasTy::Projection(proj), .. }) => {
+                    proj.self_type_parameter(db)
+                }
+                _ => return false,
+            };
+            self_ty == param_ty
+        })
+        .map(|pred| pred.clone().substitute(Interner, subst).into_value_and_skipped_binders().0)
+        .collect()
+}
+
+/// Parameter and return types of the closure `closure` taken by `func`
+///
+/// The generics of the impl or trait containing `func` are substituted by `parent_args` and the
+/// generics of `func` itself are left unknown. Associated types such as `Self::Item` are
+/// normalized, so adapters of iterator traits get the concrete item type.
+fn closure_signature(
+    db: &dyn HirDatabase,
+    func: Function,
+    closure: TypeParam,
+    parent_args: &[Type],
+    ty: &Type,
+) -> Option<(Vec<Type>, Type)> {
+    let parent = match func.as_assoc_item(db)?.container(db) {
+        AssocItemContainer::Impl(it) => GenericDef::from(it),
+        AssocItemContainer::Trait(it) => GenericDef::from(it),
+    };
+    let mut args = parent_args.iter().map(|it| it.ty.clone());
+    let mut filler = |x: &_| match x {
+        ParamKind::Type => {
+            args.next().unwrap_or_else(|| TyKind::Error.intern(Interner)).cast(Interner)
+        }
+        ParamKind::Const(ty) => unknown_const_as_generic(ty.clone()),
+        ParamKind::Lifetime => error_lifetime().cast(Interner),
+    };
+    let parent_subst = TyBuilder::subst_for_def(db, parent.id(), None).fill(&mut filler).build();
+    let subst = TyBuilder::subst_for_def(db, func.id, Some(parent_subst)).fill(&mut filler).build();
+
+    let normalize = |it: &Ty| match it.kind(Interner) {
+        TyKind::Alias(AliasTy::Projection(proj)) => {
+            ty.derived(db.normalize_projection(proj.clone(), ty.env.clone()))
+        }
+        _ => ty.derived(it.clone()),
+    };
+    let is_fn_trait = |trait_: TraitId| {
+        db.lang_attr(trait_.into())
+            .and_then(FnTrait::from_lang_item)
+            .is_some_and(|it| matches!(it, FnTrait::FnOnce | FnTrait::FnMut | FnTrait::Fn))
+    };
+
+    let mut params = None;
+    let mut ret = ty.derived(TyBuilder::unit());
+    for pred in param_bounds(db, closure, &subst) {
+        match &pred {
+            WhereClause::Implemented(trait_ref) if is_fn_trait(trait_ref.hir_trait_id()) => {
+                let args = trait_ref.substitution.at(Interner, 1).ty(Interner)?.as_tuple()?;
+                params = Some(
+                    args.iter(Interner).filter_map(|it| it.ty(Interner)).map(normalize).collect(),
+                );
+            }
+            WhereClause::AliasEq(AliasEq { alias: AliasTy::Projection(proj), ty: output })
+                if is_fn_trait(proj.trait_(db)) =>
+            {
+                ret = normalize(output);
+            }
+            _ => (),
+        }
+    }
+    Some((params?, ret))
+}
+
+/// Check if type arguments of the impl self type are exactly the impl generics in order, so that
+/// type arguments of a type can be used as generics of the impl.
+fn self_ty_args_are_impl_params(db: &dyn HirDatabase, imp: Impl) -> bool {
+    let impl_params = GenericDef::from(imp)
+        .type_or_const_params(db)
+        .into_iter()
+        .map(|it| it.as_type_param(db))
+        .collect::<Option<Vec<TypeParam>>>();
+    let self_ty_params = imp
+        .self_ty(db)
+        .type_arguments()
+        .map(|it| it.as_type_param(db))
+        .collect::<Option<Vec<TypeParam>>>();
+    impl_params.is_some() && impl_params == self_ty_params
+}
+
+/// Bodies for closures taking single parameter of type `param_ty`
+///
+/// These are the parameter passed to a free function or a tuple constructor, a field of the
+/// parameter or a method call on the parameter. Bodies that return references are ignored as
+/// they would borrow from the parameter.
+fn closure_bodies<DB: HirDatabase>(
+    ctx: &TermSearchCtx<'_, DB>,
+    defs: &FxHashSet<ScopeDef>,
+    param_ty: &Type,
+) -> Vec<Expr> {
+    let db = ctx.sema.db;
+    let module = ctx.scope.module();
+    let param = Expr::ClosureParam(param_ty.clone());
+    let takes_param = |params: &[Type]| match params {
+        [ty] => ty.could_unify_with_deeply(db, param_ty),
+        _ => false,
+    };
+
+    let fields = param_ty
+        .fields(db)
+        .into_iter()
+        .filter(|(field, _)| field.is_visible_from(db, module))
+        .map(|(field, _)| Expr::Field { expr: Box::new(param.clone()), field });
+
+    let methods = Impl::all_for_type(db, param_ty.clone())
+        .into_iter()
+        .filter(|imp| {
+            !imp.is_unsafe(db)
+                && GenericDef::from(*imp).lifetime_params(db).is_empty()
+                && self_ty_args_are_impl_params(db, *imp)
+        })
+        .flat_map(|imp| imp.items(db))
+        .filter_map(AssocItem::as_function)
+        .filter(|it| {
+            let generics = GenericDef::from(*it);
+            it.self_param(db).is_some_and(|self_param| {
+                let self_ty = self_param.ty_with_args(db, param_ty.type_arguments());
+                let mut derefs = self_ty.autoderef(db);
+                derefs.any(|s_ty| s_ty.could_unify_with_deeply(db, param_ty))
+            }) && it.params_without_self(db).is_empty()
+                && generics.type_or_const_params(db).is_empty()
+                && generics.lifetime_params(db).is_empty()
+        })
+        .map(|func| Expr::Method {
+            func,
+            generics: Vec::new(),
+            target: Box::new(param.clone()),
+            params: Vec::new(),
+        });
+
+    let calls = defs.iter().flat_map(|def| match def {
+        ScopeDef::ModuleDef(ModuleDef::Function(func)) => {
+            let generics = GenericDef::from(*func);
+            let params: Vec<_> =
+                func.assoc_fn_params(db).into_iter().map(|it| it.ty().clone()).collect();
+            let valid = generics.type_or_const_params(db).is_empty()
+                && generics.lifetime_params(db).is_empty()
+                && takes_param(&params);
+            let expr =
+                Expr::Function { func: *func, generics: Vec::new(), params: vec![param.clone()] };
+            valid.then_some(expr).into_iter().collect()
+        }
+        ScopeDef::ModuleDef(ModuleDef::Adt(Adt::Enum(enum_)))
+            if GenericDef::from(*enum_).params(db).is_empty()
+                && enum_.is_visible_from(db, module) =>
+        {
+            enum_
+                .variants(db)
+                .into_iter()
+                .filter(|variant| {
+                    let fields: Vec<_> = variant.fields(db).iter().map(|it| it.ty(db)).collect();
+                    variant.kind(db) == StructKind::Tuple && takes_param(&fields)
+                })
+                .map(|variant| Expr::Variant {
+                    variant,
+                    generics: Vec::new(),
+                    params: vec![param.clone()],
+                })
+                .collect()
+        }
+        ScopeDef::ModuleDef(ModuleDef::Adt(Adt::Struct(strukt)))
+            if GenericDef::from(*strukt).params(db).is_empty()
+                && strukt.is_visible_from(db, module) =>
+        {
+            let fields = strukt.fields(db);
+            let field_tys: Vec<_> = fields.iter().map(|it| it.ty(db)).collect();
+            let valid = strukt.kind(db) == StructKind::Tuple
+                && takes_param(&field_tys)
+                && fields.iter().all(|it| it.is_visible_from(db, module));
+            let expr =
+                Expr::Struct { strukt: *strukt, generics: Vec::new(), params: vec![param.clone()] };
+            valid.then_some(expr).into_iter().collect()
+        }
+        _ => Vec::new(),
+    });
+
+    fields
+        .chain(methods)
+        .chain(calls)
+        .filter(|body| {
+            let ty = body.ty(db);
+            !ty.contains_reference(db) && !ty.is_raw_ptr() && !ty.contains_unknown()
+        })
+        .filter(|body| match body {
+            Expr::Method { func: it, .. } | Expr::Function { func: it, .. } => {
+                it.is_visible_from(db, module)
+                    && !it.is_unsafe_to_call(db, None, Edition::CURRENT_FIXME)
+                    && !it.is_unstable(db)
+            }
+            _ => true,
+        })
+        .collect()
+}
@@ -82,7 +82,7 @@ pub(crate) fn term_search(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
 
     use super::*;
 
@@ -320,4 +320,17 @@ impl Bar for Foo { const BAR: i32 = 0; }
 fn f() { let a: i32 = Foo::BAR; }"#,
         )
     }
+
+    #[test]
+    fn test_try_operator_with_closure() {
+        check_assist_by_label(
+            term_search,
+            r#"//- minicore: todo, unimplemented, option, result, try, from, fn
+struct Config(u8);
+fn load(id: Option<u8>) -> Option<()> { let a: Config = todo$0!(); None }"#,
+            r#"struct Config(u8);
+fn load(id: Option<u8>) -> Option<()> { let a: Config = id.map(|it| Config(it))?; None }"#,
+            "Replace todo!() with id.map(|it| Config(it))?",
+        )
+    }
 }
@@ -10,9 +10,12 @@ pub(crate) mod field;
 pub(crate) mod flyimport;
 pub(crate) mod fn_param;
 pub(crate) mod format_string;
+pub(crate) mod frontmatter;
+pub(crate) mod include_path;
 pub(crate) mod item_list;
 pub(crate) mod keyword;
 pub(crate) mod lifetime;
+pub(crate) mod macro_rules;
 pub(crate) mod mod_;
 pub(crate) mod pattern;
 pub(crate) mod postfix;
@@ -35,8 +38,8 @@ use crate::{
     },
     item::Builder,
     render::{
-        const_::render_const,
-        function::{render_fn, render_method},
+        const_::{render_const, render_qualified_const},
+        function::{render_fn, render_method, render_qualified_fn},
         literal::{render_struct_literal, render_variant_lit},
         macro_::render_macro,
         pattern::{render_struct_pat, render_variant_pat},
@@ -309,6 +312,31 @@ impl Completions {
         );
     }
 
+    /// Adds an associated function of a type as `qualifier::func`.
+    pub(crate) fn add_qualified_function(
+        &mut self,
+        ctx: &CompletionContext<'_>,
+        path_ctx: &PathCompletionCtx,
+        func: hir::Function,
+        qualifier: SmolStr,
+        import: Option<LocatedImport>,
+    ) {
+        let is_private_editable = match ctx.is_visible(&func) {
+            Visible::Yes => false,
+            Visible::Editable => true,
+            Visible::No => return,
+        };
+        self.add(
+            render_qualified_fn(
+                RenderContext::new(ctx).private_editable(is_private_editable).import_to_add(import),
+                path_ctx,
+                qualifier,
+                func,
+            )
+            .build(ctx.db),
+        );
+    }
+
     pub(crate) fn add_method(
         &mut self,
         ctx: &CompletionContext<'_>,
@@ -377,6 +405,26 @@ impl Completions {
         ));
     }
 
+    /// Adds an associated const of a type as `qualifier::CONST`.
+    pub(crate) fn add_qualified_const(
+        &mut self,
+        ctx: &CompletionContext<'_>,
+        konst: hir::Const,
+        qualifier: SmolStr,
+        import: Option<LocatedImport>,
+    ) {
+        let is_private_editable = match ctx.is_visible(&konst) {
+            Visible::Yes => false,
+            Visible::Editable => true,
+            Visible::No => return,
+        };
+        self.add_opt(render_qualified_const(
+            RenderContext::new(ctx).private_editable(is_private_editable).import_to_add(import),
+            qualifier,
+            konst,
+        ));
+    }
+
     pub(crate) fn add_type_alias(
         &mut self,
         ctx: &CompletionContext<'_>,
@@ -410,13 +458,18 @@ impl Completions {
         path_ctx: &PathCompletionCtx,
         variant: hir::Variant,
         path: hir::ModPath,
+        import: Option<LocatedImport>,
     ) {
         if !ctx.check_stability_and_hidden(variant) {
             return;
         }
-        if let Some(builder) =
-            render_variant_lit(RenderContext::new(ctx), path_ctx, None, variant, Some(path))
-        {
+        if let Some(builder) = render_variant_lit(
+            RenderContext::new(ctx).import_to_add(import),
+            path_ctx,
+            None,
+            variant,
+            Some(path),
+        ) {
             self.add(builder.build(ctx.db));
         }
     }
@@ -573,13 +626,14 @@ impl Completions {
         pattern_ctx: &PatternContext,
         variant: hir::Variant,
         path: hir::ModPath,
+        import: Option<LocatedImport>,
     ) {
         if !ctx.check_stability_and_hidden(variant) {
             return;
         }
         let path = Some(&path);
         self.add_opt(render_variant_pat(
-            RenderContext::new(ctx),
+            RenderContext::new(ctx).import_to_add(import),
             pattern_ctx,
             None,
             variant,
@@ -626,7 +680,13 @@ fn enum_variants_with_paths(
     ctx: &CompletionContext<'_>,
     enum_: hir::Enum,
     impl_: &Option<ast::Impl>,
-    cb: impl Fn(&mut Completions, &CompletionContext<'_>, hir::Variant, hir::ModPath),
+    cb: impl Fn(
+        &mut Completions,
+        &CompletionContext<'_>,
+        hir::Variant,
+        hir::ModPath,
+        Option<LocatedImport>,
+    ),
 ) {
     let mut process_variant = |variant: Variant| {
         let self_path = hir::ModPath::from_segments(
@@ -635,7 +695,7 @@ fn enum_variants_with_paths(
                 .chain(iter::once(variant.name(ctx.db))),
         );
 
-        cb(acc, ctx, variant, self_path);
+        cb(acc, ctx, variant, self_path, None);
     };
 
     let variants = enum_.variants(ctx.db);
@@ -646,6 +706,7 @@ fn enum_variants_with_paths(
         }
     }
 
+    let enum_import = imported_adt_path(ctx, hir::Adt::Enum(enum_));
     for variant in variants {
         if let Some(path) = ctx.module.find_path(
             ctx.db,
@@ -655,12 +716,48 @@ fn enum_variants_with_paths(
             // Variants with trivial paths are already added by the existing completion logic,
             // so we should avoid adding these twice
             if path.segments().len() > 1 {
-                cb(acc, ctx, variant, path);
+                match &enum_import {
+                    Some((enum_path, import)) => {
+                        let path = hir::ModPath::from_segments(
+                            hir::PathKind::Plain,
+                            enum_path.segments().iter().cloned().chain([variant.name(ctx.db)]),
+                        );
+                        cb(acc, ctx, variant, path, Some(import.clone()));
+                    }
+                    None => cb(acc, ctx, variant, path, None),
+                }
             }
         }
     }
 }
 
+/// For ADTs of other crates that are not in scope, returns the path they can be referred to by
+/// once the returned import is added.
+fn imported_adt_path(
+    ctx: &CompletionContext<'_>,
+    adt: hir::Adt,
+) -> Option<(hir::ModPath, LocatedImport)> {
+    if adt.module(ctx.db).krate() == ctx.krate {
+        return None;
+    }
+    let item = hir::ItemInNs::from(hir::ModuleDef::from(adt));
+    let import_path = ctx.module.find_use_path(
+        ctx.db,
+        item,
+        ctx.config.insert_use.prefix_kind,
+        ctx.config.import_path_config(ctx.is_nightly),
+    )?;
+    if import_path.len() <= 1 {
+        return None;
+    }
+    let path = hir::ModPath::from_segments(hir::PathKind::Plain, [adt.name(ctx.db)]);
+    // Importing the ADT would clash with the item its name already refers to.
+    if ctx.scope.resolve_mod_path(&path).next().is_some() {
+        return None;
+    }
+    Some((path, LocatedImport::new(import_path, item, item)))
+}
+
 pub(super) fn complete_name(
     acc: &mut Completions,
     ctx: &CompletionContext<'_>,
@@ -722,6 +819,7 @@ pub(super) fn complete_name_ref(
                 }
                 PathKind::Type { location } => {
                     r#type::complete_type_path(acc, ctx, path_ctx, location);
+                    snippet::complete_type_snippet(acc, ctx, path_ctx);
 
                     match location {
                         TypeLocation::TupleField => {
@@ -791,4 +889,5 @@ fn complete_patterns(
     fn_param::complete_fn_param(acc, ctx, pattern_ctx);
     pattern::complete_pattern(acc, ctx, pattern_ctx);
     record::complete_record_pattern_fields(acc, ctx, pattern_ctx);
+    snippet::complete_pattern_snippet(acc, ctx, pattern_ctx);
 }
@@ -25,13 +25,14 @@ use crate::{
 
 mod cfg;
 mod derive;
+mod derive_helper;
 mod lint;
 mod macro_use;
 mod repr;
 
-pub(crate) use self::derive::complete_derive_path;
+pub(crate) use self::{derive::complete_derive_path, derive_helper::complete_derive_helper_value};
 
-/// Complete inputs to known builtin attributes as well as derive attributes
+/// Complete inputs to known builtin attributes, derive attributes and derive helper attributes
 pub(crate) fn complete_known_attribute_input(
     acc: &mut Completions,
     ctx: &CompletionContext<'_>,
@@ -77,7 +78,9 @@ pub(crate) fn complete_known_attribute_input(
             extern_crate,
             &parse_tt_as_comma_sep_paths(tt, ctx.edition)?,
         ),
-        _ => (),
+        helper => {
+            derive_helper::complete_derive_helper_args(acc, ctx, helper, tt);
+        }
     }
     Some(())
 }
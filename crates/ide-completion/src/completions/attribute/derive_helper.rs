@@ -0,0 +1,117 @@
+//! Completion for the arguments of derive helper attributes, like `#[serde(rename_all = "…")]`.
+//!
+//! Which keys and values a helper accepts is not known to us, so these come from the
+//! `derive_helper_args` catalog of the completion config.
+use ide_db::SymbolKind;
+use syntax::{ast, AstNode, AstToken, NodeOrToken, SyntaxKind, SyntaxToken, T};
+
+use crate::{context::CompletionContext, item::CompletionItem, Completions};
+
+pub(super) fn complete_derive_helper_args(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    helper: &str,
+    input: ast::TokenTree,
+) -> Option<()> {
+    let mut args =
+        ctx.config.derive_helper_args.iter().filter(|arg| arg.helper == helper).peekable();
+    args.peek()?;
+
+    let adt = ctx.original_token.parent_ancestors().find_map(ast::Adt::cast)?;
+    let helpers = ctx.sema.derive_helpers_in_scope(&adt)?;
+    if !helpers.iter().any(|(name, _)| name.as_str() == helper) {
+        return None;
+    }
+
+    // Only complete the top level of the attribute input, nested token trees belong to
+    // arguments we know nothing about.
+    let tokens: Vec<SyntaxToken> = input
+        .syntax()
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|it| !it.kind().is_trivia())
+        .collect();
+    let cursor = tokens.iter().position(|it| {
+        matches!(it.kind(), SyntaxKind::IDENT | SyntaxKind::STRING)
+            && it.text_range().contains_inclusive(ctx.position.offset)
+    })?;
+    let prev = cursor.checked_sub(1).map(|it| &tokens[it]);
+    let in_string = tokens[cursor].kind() == SyntaxKind::STRING;
+
+    if prev.is_some_and(|it| it.kind() == T![=]) {
+        let key = &tokens[cursor.checked_sub(2)?];
+        let values = args.find(|arg| arg.key == key.text())?.values.as_ref()?;
+        // Inside of a string literal, the typed text includes the opening quote, so the whole
+        // literal is replaced and matched against the quoted value.
+        let source_range = match in_string {
+            true => tokens[cursor].text_range(),
+            false => ctx.source_range(),
+        };
+        for value in values {
+            let mut item = CompletionItem::new(
+                SymbolKind::BuiltinAttr,
+                source_range,
+                format!("\"{value}\""),
+                ctx.edition,
+            );
+            if !in_string {
+                item.lookup_by(value);
+            }
+            item.add_to(acc, ctx.db);
+        }
+        return Some(());
+    }
+    if in_string {
+        return None;
+    }
+
+    let existing_keys: Vec<&str> = tokens
+        .iter()
+        .enumerate()
+        .filter(|&(idx, it)| {
+            idx != cursor
+                && it.kind() == SyntaxKind::IDENT
+                && idx.checked_sub(1).is_none_or(|prev| tokens[prev].kind() != T![=])
+        })
+        .map(|(_, it)| it.text())
+        .collect();
+    for arg in args.filter(|arg| !existing_keys.contains(&arg.key.as_str())) {
+        let mut item = CompletionItem::new(
+            SymbolKind::BuiltinAttr,
+            ctx.source_range(),
+            match arg.values {
+                Some(_) => format!("{} = \"…\"", arg.key),
+                None => arg.key.clone(),
+            },
+            ctx.edition,
+        );
+        item.lookup_by(&arg.key);
+        match (&arg.values, ctx.config.snippet_cap) {
+            (Some(values), Some(cap)) if values.is_empty() => {
+                item.insert_snippet(cap, format!("{} = \"$0\"", arg.key));
+            }
+            (Some(_), Some(cap)) => {
+                item.insert_snippet(cap, format!("{} = $0", arg.key));
+            }
+            (Some(_), None) => {
+                item.insert_text(format!("{} = ", arg.key));
+            }
+            (None, _) => (),
+        }
+        item.add_to(acc, ctx.db);
+    }
+    Some(())
+}
+
+/// Completes the value of a derive helper argument when the cursor is inside of its string
+/// literal, like `#[serde(rename_all = "cam$0")]`.
+pub(crate) fn complete_derive_helper_value(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    original: &ast::String,
+) -> Option<()> {
+    let input = ast::TokenTree::cast(original.syntax().parent()?)?;
+    let meta = ast::Meta::cast(input.syntax().parent()?)?;
+    let helper = meta.path()?.as_single_name_ref()?;
+    complete_derive_helper_args(acc, ctx, helper.text().as_str(), input)
+}
@@ -1,5 +1,6 @@
 //! Completes environment variables defined by Cargo
-//! (<https://doc.rust-lang.org/cargo/reference/environment-variables.html>)
+//! (<https://doc.rust-lang.org/cargo/reference/environment-variables.html>), and the ones in the
+//! crate's environment, like those set by its build script with `cargo::rustc-env`.
 use hir::MacroFileIdExt;
 use ide_db::syntax_helpers::node_ext::macro_call_for_string_token;
 use syntax::{
@@ -60,6 +61,14 @@ pub(crate) fn complete_cargo_env_vars(
         item.detail(detail);
         item.add_to(acc, ctx.db);
     });
+    for (var, value) in ctx.krate.env(ctx.db) {
+        if CARGO_DEFINED_VARS.iter().any(|&(it, _)| it == var) {
+            continue;
+        }
+        let mut item = CompletionItem::new(CompletionItemKind::Keyword, range, var, ctx.edition);
+        item.detail(value);
+        item.add_to(acc, ctx.db);
+    }
 
     Some(())
 }
@@ -104,6 +113,25 @@ fn main() {
         );
     }
 
+    #[test]
+    fn completes_variables_from_crate_env() {
+        check_edit(
+            "PROTOC_VERSION",
+            r#"
+//- minicore: env
+//- /main.rs crate:main env:PROTOC_VERSION=3.21,OUT_DIR=/target/out
+fn main() {
+    let foo = env!("PRO$0");
+}
+        "#,
+            r#"
+fn main() {
+    let foo = env!("PROTOC_VERSION");
+}
+        "#,
+        );
+    }
+
     #[test]
     fn doesnt_complete_in_random_strings() {
         let fixture = r#"
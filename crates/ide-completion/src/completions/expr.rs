@@ -3,8 +3,8 @@
 use std::ops::ControlFlow;
 
 use hir::{sym, Name, PathCandidateCallback, ScopeDef};
-use ide_db::FxHashSet;
-use syntax::ast;
+use ide_db::{famous_defs::FamousDefs, imports::import_assets::LocatedImport, FxHashSet};
+use syntax::{ast, ToSmolStr};
 
 use crate::{
     completions::record::add_default_update,
@@ -292,12 +292,16 @@ pub(crate) fn complete_expr_path(
                             ctx,
                             e,
                             impl_,
-                            |acc, ctx, variant, path| {
-                                acc.add_qualified_enum_variant(ctx, path_ctx, variant, path)
+                            |acc, ctx, variant, path, import| {
+                                acc.add_qualified_enum_variant(ctx, path_ctx, variant, path, import)
                             },
                         );
                     }
                 }
+
+                if let Some((path, import)) = super::imported_adt_path(ctx, adt) {
+                    add_adt_constructors(acc, ctx, path_ctx, adt, &path, import);
+                }
             }
             ctx.process_all_names(&mut |name, def, doc_aliases| match def {
                 ScopeDef::ModuleDef(hir::ModuleDef::Trait(t)) => {
@@ -423,6 +427,58 @@ pub(crate) fn complete_expr_path(
     }
 }
 
+/// Adds the associated consts and constructor functions of `adt`, referred to by `path` once
+/// `import` is added.
+fn add_adt_constructors(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    path_ctx: &PathCompletionCtx,
+    adt: hir::Adt,
+    path: &hir::ModPath,
+    import: LocatedImport,
+) {
+    let db = ctx.db;
+    let ty = adt.ty(db);
+    let qualifier = path.display(db, ctx.edition).to_smolstr();
+
+    let inherent_items = hir::Impl::all_for_type(db, ty.clone())
+        .into_iter()
+        .filter(|impl_| impl_.trait_(db).is_none())
+        .flat_map(|impl_| impl_.items(db));
+    for item in inherent_items {
+        match item {
+            hir::AssocItem::Function(func)
+                if func.self_param(db).is_none() && func.ret_type(db).could_unify_with(db, &ty) =>
+            {
+                acc.add_qualified_function(
+                    ctx,
+                    path_ctx,
+                    func,
+                    qualifier.clone(),
+                    Some(import.clone()),
+                );
+            }
+            hir::AssocItem::Const(konst) if konst.ty(db).could_unify_with(db, &ty) => {
+                acc.add_qualified_const(ctx, konst, qualifier.clone(), Some(import.clone()));
+            }
+            _ => (),
+        }
+    }
+
+    let default_fn = FamousDefs(&ctx.sema, ctx.krate)
+        .core_default_Default()
+        .filter(|&default| ty.impls_trait(db, default, &[]))
+        .and_then(|default| {
+            default.items(db).into_iter().find_map(|item| match item {
+                hir::AssocItem::Function(func) if func.name(db) == sym::default => Some(func),
+                _ => None,
+            })
+        });
+    if let Some(default_fn) = default_fn {
+        acc.add_qualified_function(ctx, path_ctx, default_fn, qualifier, Some(import));
+    }
+}
+
 pub(crate) fn complete_expr(acc: &mut Completions, ctx: &CompletionContext<'_>) {
     let _p = tracing::info_span!("complete_expr").entered();
 
@@ -0,0 +1,212 @@
+//! Completes dependency names and features inside the manifest embedded in a cargo script's
+//! frontmatter.
+//!
+//! The manifest is usually incomplete while typing, so we insert a marker at the cursor, patch up
+//! the line the cursor is on until the manifest parses and then look for the marker in the
+//! parsed tables.
+use ide_db::{
+    cargo_script::{self, is_dependency_table},
+    FxHashSet, SymbolKind,
+};
+use syntax::{SyntaxToken, TextRange, TextSize};
+
+use crate::{
+    completions::Completions,
+    context::{CompletionContext, COMPLETION_MARKER},
+    CompletionItem, CompletionItemKind,
+};
+
+/// Suffixes that turn a manifest with the completion marker into valid TOML for the positions we
+/// complete at, like `name$0`, `features = ["feat$0` or `dep = { features = ["feat$0`.
+const FIXUPS: &[&str] = &["", " = \"\"", "\"", "\"]", "\"] }"];
+
+enum ManifestPosition {
+    DependencyName { prefix: String },
+    Feature { dependency: String, prefix: String },
+}
+
+pub(crate) fn complete_frontmatter(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    frontmatter: &SyntaxToken,
+) -> Option<()> {
+    let (range, manifest) = cargo_script::manifest(frontmatter)?;
+    if !range.contains_inclusive(ctx.position.offset) {
+        return None;
+    }
+    let offset = usize::from(ctx.position.offset - range.start());
+    let (before, after) = manifest.split_at(offset);
+    let table = FIXUPS.iter().find_map(|fixup| {
+        format!("{before}{COMPLETION_MARKER}{fixup}{after}").parse::<toml::Table>().ok()
+    })?;
+
+    let prefix_range = |prefix: &str| {
+        TextRange::new(ctx.position.offset - TextSize::of(prefix), ctx.position.offset)
+    };
+    match find_marker(&table)? {
+        ManifestPosition::DependencyName { prefix } => {
+            let range = prefix_range(&prefix);
+            for krate in cargo_script::script_dependencies(ctx.db, ctx.krate) {
+                let Some(name) = krate.display_name(ctx.db) else { continue };
+                let mut item = CompletionItem::new(
+                    CompletionItemKind::SymbolKind(SymbolKind::Module),
+                    range,
+                    name.canonical_name().as_str(),
+                    ctx.edition,
+                );
+                if let Some(version) = krate.version(ctx.db) {
+                    item.detail(version);
+                }
+                item.add_to(acc, ctx.db);
+            }
+        }
+        ManifestPosition::Feature { dependency, prefix } => {
+            let krate = cargo_script::find_dependency(ctx.db, ctx.krate, &dependency)?;
+            let range = prefix_range(&prefix);
+            let mut seen = FxHashSet::default();
+            for feature in krate.potential_cfg(ctx.db).get_cfg_values("feature") {
+                if !seen.insert(feature) {
+                    continue;
+                }
+                let item = CompletionItem::new(
+                    SymbolKind::BuiltinAttr,
+                    range,
+                    feature.as_str(),
+                    ctx.edition,
+                );
+                acc.add(item.build(ctx.db));
+            }
+        }
+    }
+    Some(())
+}
+
+fn find_marker(manifest: &toml::Table) -> Option<ManifestPosition> {
+    // `[target.'cfg(unix)'.dependencies]`
+    let target_tables = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values().filter_map(toml::Value::as_table));
+    std::iter::once(manifest)
+        .chain(target_tables)
+        .flat_map(|table| table.iter().filter(|(name, _)| is_dependency_table(name)))
+        .filter_map(|(_, dependencies)| dependencies.as_table())
+        .flatten()
+        .find_map(|(name, dependency)| {
+            if let Some(prefix) = name.strip_suffix(COMPLETION_MARKER) {
+                return Some(ManifestPosition::DependencyName { prefix: prefix.to_owned() });
+            }
+            let features = dependency.get("features")?.as_array()?;
+            let prefix =
+                features.iter().find_map(|it| it.as_str()?.strip_suffix(COMPLETION_MARKER))?;
+            Some(ManifestPosition::Feature { dependency: name.clone(), prefix: prefix.to_owned() })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::tests::{check, check_edit};
+
+    #[test]
+    fn completes_dependency_names() {
+        check(
+            r#"
+//- /main.rs crate:main deps:serde,clap
+---
+[dependencies]
+se$0
+---
+fn main() {}
+//- /serde.rs crate:serde
+//- /clap.rs crate:clap deps:clap_derive
+//- /clap_derive.rs crate:clap_derive
+"#,
+            expect![[r#"
+                md clap
+                md clap_derive
+                md serde
+            "#]],
+        );
+    }
+
+    #[test]
+    fn no_dependency_names_outside_of_dependency_tables() {
+        check(
+            r#"
+//- /main.rs crate:main deps:serde
+---
+[package]
+ed$0
+---
+fn main() {}
+//- /serde.rs crate:serde
+"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn completes_features_of_inline_table() {
+        check_edit(
+            "derive",
+            r#"
+//- /main.rs crate:main deps:serde
+---cargo
+[dependencies]
+serde = { version = "1", features = ["std", "de$0"] }
+---
+fn main() {}
+//- /serde.rs crate:serde cfg:feature=derive,feature=std
+"#,
+            r#"
+---cargo
+[dependencies]
+serde = { version = "1", features = ["std", "derive"] }
+---
+fn main() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn completes_features_of_dependency_table() {
+        check(
+            r#"
+//- /main.rs crate:main deps:serde
+---
+[dev-dependencies.serde]
+version = "1"
+features = ["$0
+---
+fn main() {}
+//- /serde.rs crate:serde cfg:feature=derive,feature=std
+"#,
+            expect![[r#"
+                ba derive
+                ba std
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_features_of_target_dependency() {
+        check(
+            r#"
+//- /main.rs crate:main deps:serde
+---
+[target.'cfg(unix)'.dependencies]
+serde = { version = "1", features = ["$0
+---
+fn main() {}
+//- /serde.rs crate:serde cfg:feature=derive,feature=std
+"#,
+            expect![[r#"
+                ba derive
+                ba std
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Completes file paths inside the string arguments of `include!`, `include_str!` and
+//! `include_bytes!`, and inside `#[path = "…"]` attributes of module declarations.
+//!
+//! Only files known to the VFS are completed, these are the ones in the source root of the current
+//! file. The VFS only loads Rust and TOML files, so other files, e.g. images or JSON data included
+//! with `include_bytes!` or `include_str!`, are not offered. Like in name resolution, `#[path]` attributes of modules nested in inline modules are
+//! relative to the directory of those inline modules.
+use hir::MacroFileIdExt;
+use ide_db::{
+    base_db::SourceDatabase, syntax_helpers::node_ext::macro_call_for_string_token, FxHashSet,
+    SymbolKind,
+};
+use syntax::{
+    ast::{self, HasAttrs, HasName, IsString},
+    AstNode, AstToken, TextRange, TextSize,
+};
+
+use crate::{
+    completions::Completions, context::CompletionContext, CompletionItem, CompletionItemKind,
+};
+
+pub(crate) fn complete_include_path(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    original: &ast::String,
+    expanded: &ast::String,
+) -> Option<()> {
+    let path_attr = is_path_attr(original);
+    let only_rust_files = if path_attr {
+        true
+    } else {
+        match ctx.sema.hir_file_for(&expanded.syntax().parent()?).macro_file() {
+            Some(it) if it.is_include_like_macro(ctx.sema.db) => it.is_include_macro(ctx.sema.db),
+            _ => {
+                let call = macro_call_for_string_token(expanded)?;
+                let makro = ctx.sema.resolve_macro_call(&call)?;
+                if !makro.is_include_like(ctx.sema.db) {
+                    return None;
+                }
+                makro.is_include(ctx.sema.db)
+            }
+        }
+    };
+
+    // Only the last segment of the typed path gets replaced, the directories before it select
+    // where to look for candidates.
+    let between_quotes = original.text_range_between_quotes()?;
+    let token_start = original.syntax().text_range().start();
+    let typed = original
+        .text()
+        .get(usize::from(between_quotes.start() - token_start)..)?
+        .get(..usize::from(ctx.position.offset.checked_sub(between_quotes.start())?))?;
+    let (dir, segment_start) = match typed.rfind('/') {
+        Some(idx) => (&typed[..idx], between_quotes.start() + TextSize::from(idx as u32 + 1)),
+        None => ("", between_quotes.start()),
+    };
+    let range = TextRange::new(segment_start, ctx.position.offset);
+
+    let file_id = ctx.position.file_id;
+    let source_root_id = ctx.db.file_source_root(file_id).source_root_id(ctx.db);
+    let source_root = ctx.db.source_root(source_root_id).source_root(ctx.db);
+    let current_file = source_root.path_for_file(&file_id)?;
+    let mut base = current_file.parent()?;
+    if path_attr {
+        let module = original.syntax().parent_ancestors().find_map(ast::Module::cast)?;
+        let (file_name, _) = current_file.name_and_extension()?;
+        let module_dir = path_attr_dir(ctx, &module, file_name)?;
+        if !module_dir.is_empty() {
+            base = base.join(module_dir.trim_end_matches('/'))?;
+        }
+    }
+    let dir = if dir.is_empty() { base } else { base.join(dir)? };
+
+    let mut seen = FxHashSet::default();
+    for candidate in source_root.iter() {
+        let Some(path) = source_root.path_for_file(&candidate) else { continue };
+        if path == current_file {
+            continue;
+        }
+        let Some(rel) = path.strip_prefix(&dir) else { continue };
+        let rel = rel.as_str().trim_start_matches('/');
+        let (name, is_dir) = match rel.split_once('/') {
+            Some((dir_name, _)) => (dir_name, true),
+            None => (rel, false),
+        };
+        if !is_dir && only_rust_files && !name.ends_with(".rs") {
+            continue;
+        }
+        if !seen.insert((name.to_owned(), is_dir)) {
+            continue;
+        }
+        let item = if is_dir {
+            CompletionItem::new(
+                CompletionItemKind::SymbolKind(SymbolKind::Module),
+                range,
+                format!("{name}/"),
+                ctx.edition,
+            )
+        } else {
+            CompletionItem::new(CompletionItemKind::Keyword, range, name, ctx.edition)
+        };
+        item.add_to(acc, ctx.db);
+    }
+    Some(())
+}
+
+/// Whether `string` is the value of a `#[path = "…"]` attribute.
+fn is_path_attr(string: &ast::String) -> bool {
+    let Some(meta) = string.syntax().parent_ancestors().nth(1).and_then(ast::Meta::cast) else {
+        return false;
+    };
+    meta.path().and_then(|it| it.as_single_name_ref()).is_some_and(|it| it.text() == "path")
+}
+
+/// The directory `#[path]` attributes on `module` are relative to, relative to the directory of the
+/// current file named `file_stem`. This follows `ModDir` of name resolution: it is the directory
+/// of the file, unless `module` is nested in inline modules. Then it is the directory owned by the
+/// file, `foo/` for `foo.rs` and the file's directory for `mod.rs` and crate roots, followed by the
+/// names or `#[path]`s of the inline modules.
+fn path_attr_dir(
+    ctx: &CompletionContext<'_>,
+    module: &ast::Module,
+    file_stem: &str,
+) -> Option<String> {
+    let inline_modules: Vec<ast::Module> =
+        module.syntax().ancestors().skip(1).filter_map(ast::Module::cast).collect();
+    if inline_modules.is_empty() {
+        return Some(String::new());
+    }
+
+    let file_module = ctx.sema.file_to_module_def(ctx.position.file_id)?;
+    let owns_dir = file_module.is_crate_root()
+        || file_stem == "mod"
+        || file_module
+            .declaration_source(ctx.db)
+            .is_some_and(|it| path_attr_value(&it.value).is_some());
+    let mut dir = if owns_dir { String::new() } else { format!("{file_stem}/") };
+    for (idx, inline_module) in inline_modules.iter().rev().enumerate() {
+        match path_attr_value(inline_module) {
+            Some(path) => {
+                // Inside of `foo.rs`, a `#[path]` is relative to the directory of the file.
+                if idx == 0 && !owns_dir {
+                    dir.clear();
+                }
+                dir.push_str(path.trim_start_matches("./"));
+                if !dir.is_empty() && !dir.ends_with('/') {
+                    dir.push('/');
+                }
+            }
+            None => {
+                dir.push_str(inline_module.name()?.text().as_str());
+                dir.push('/');
+            }
+        }
+    }
+    Some(dir)
+}
+
+/// The value of the `#[path = "…"]` attribute of `module`.
+fn path_attr_value(module: &ast::Module) -> Option<String> {
+    module.attrs().filter(|it| it.simple_name().as_deref() == Some("path")).find_map(|it| {
+        let ast::Expr::Literal(literal) = it.expr()? else { return None };
+        let ast::LiteralKind::String(string) = literal.kind() else { return None };
+        Some(string.value().ok()?.replace('\\', "/"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::tests::{check, check_edit};
+
+    #[test]
+    fn completes_files_in_include_str() {
+        check(
+            r#"
+//- minicore: include
+//- /src/main.rs crate:main
+fn main() {
+    let config = include_str!("$0");
+}
+//- /src/config.toml
+//- /src/snippet.rs
+//- /src/data/defaults.toml
+//- /examples/hello.rs
+"#,
+            expect![[r#"
+                md data/
+                kw config.toml
+                kw snippet.rs
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_files_in_nested_and_parent_directories() {
+        check_edit(
+            "hello.rs",
+            r#"
+//- minicore: include
+//- /src/main.rs crate:main
+static HELLO: &[u8] = include_bytes!("../examples/he$0");
+//- /examples/hello.rs
+"#,
+            r#"
+static HELLO: &[u8] = include_bytes!("../examples/hello.rs");
+"#,
+        );
+    }
+
+    #[test]
+    fn completes_only_rust_files_in_include_and_path_attr() {
+        check(
+            r#"
+//- minicore: include
+//- /src/main.rs crate:main
+include!("$0");
+//- /src/generated.rs
+//- /src/config.toml
+"#,
+            expect![[r#"
+                kw generated.rs
+            "#]],
+        );
+        check(
+            r#"
+//- /src/main.rs crate:main
+#[path = "$0"]
+mod imp;
+//- /src/unix/imp.rs
+//- /src/windows.rs
+//- /src/config.toml
+"#,
+            expect![[r#"
+                md unix/
+                kw windows.rs
+            "#]],
+        );
+    }
+
+    #[test]
+    fn path_attr_is_relative_to_the_module_dir() {
+        check(
+            r#"
+//- /src/main.rs crate:main
+mod foo;
+//- /src/foo.rs
+#[path = "$0"]
+mod top;
+//- /src/sibling.rs
+//- /src/foo/child.rs
+"#,
+            expect![[r#"
+                md foo/
+                kw main.rs
+                kw sibling.rs
+            "#]],
+        );
+        check(
+            r#"
+//- /src/main.rs crate:main
+mod foo;
+//- /src/foo.rs
+mod inline {
+    #[path = "$0"]
+    mod nested;
+}
+//- /src/sibling.rs
+//- /src/foo/inline/child.rs
+"#,
+            expect![[r#"
+                kw child.rs
+            "#]],
+        );
+        check(
+            r#"
+//- /src/main.rs crate:main
+mod inline {
+    #[path = "$0"]
+    mod nested;
+}
+//- /src/sibling.rs
+//- /src/inline/child.rs
+"#,
+            expect![[r#"
+                kw child.rs
+            "#]],
+        );
+    }
+}
@@ -38,13 +38,16 @@ use ide_db::{
     syntax_helpers::prettify_macro_expansion, traits::get_missing_assoc_items, SymbolKind,
 };
 use syntax::{
-    ast::{self, edit_in_place::AttrsOwnerEdit, make, HasGenericArgs, HasTypeBounds},
-    format_smolstr, ted, AstNode, SmolStr, SyntaxElement, SyntaxKind, TextRange, ToSmolStr, T,
+    ast::{
+        self, edit::AstNodeEdit, edit_in_place::AttrsOwnerEdit, make, HasGenericArgs, HasTypeBounds,
+    },
+    format_smolstr, ted, AstNode, SmolStr, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+    ToSmolStr, T,
 };
 
 use crate::{
-    context::PathCompletionCtx, CompletionContext, CompletionItem, CompletionItemKind,
-    CompletionRelevance, Completions,
+    completions::postfix::escape_snippet_bits, context::PathCompletionCtx, CompletionContext,
+    CompletionItem, CompletionItemKind, CompletionRelevance, Completions,
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -232,12 +235,30 @@ fn add_function_impl_(
         {
             let function_decl =
                 function_declaration(ctx, &transformed_fn, source.file_id.macro_file());
-            match ctx.config.snippet_cap {
-                Some(cap) => {
+            // The default body of an `async fn` can't be reused once the sugar changes
+            let default_body = match async_sugaring {
+                AsyncSugaring::Async | AsyncSugaring::Plain
+                    if ctx.config.trait_impl_default_body =>
+                {
+                    function_body(ctx, &transformed_fn, source.file_id.macro_file())
+                }
+                _ => None,
+            };
+            match (ctx.config.snippet_cap, default_body) {
+                (Some(cap), Some(mut body)) => {
+                    escape_snippet_bits(&mut body);
+                    let snippet = format!("{function_decl} {body}$0");
+                    item.snippet_edit(cap, TextEdit::replace(replacement_range, snippet));
+                }
+                (None, Some(body)) => {
+                    let fn_ = format!("{function_decl} {body}");
+                    item.text_edit(TextEdit::replace(replacement_range, fn_));
+                }
+                (Some(cap), None) => {
                     let snippet = format!("{function_decl} {{\n    $0\n}}");
                     item.snippet_edit(cap, TextEdit::replace(replacement_range, snippet));
                 }
-                None => {
+                (None, None) => {
                     let header = format!("{function_decl} {{");
                     item.text_edit(TextEdit::replace(replacement_range, header));
                 }
@@ -494,12 +515,7 @@ fn function_declaration(
     node: &ast::Fn,
     macro_file: Option<MacroFileId>,
 ) -> String {
-    let node = if let Some(macro_file) = macro_file {
-        let span_map = ctx.db.expansion_span_map(macro_file);
-        prettify_macro_expansion(ctx.db, node.syntax().clone(), &span_map, ctx.krate.into())
-    } else {
-        node.syntax().clone()
-    };
+    let node = prettify_fn(ctx, node, macro_file);
 
     let start = node.text_range().start();
     let end = node.text_range().end();
@@ -515,11 +531,38 @@ fn function_declaration(
     syntax.trim_end().to_owned()
 }
 
+/// The default body of a trait function, with the indentation reset so that it can be inserted
+/// after the declaration of the function.
+fn function_body(
+    ctx: &CompletionContext<'_>,
+    node: &ast::Fn,
+    macro_file: Option<MacroFileId>,
+) -> Option<String> {
+    let body = ast::Fn::cast(prettify_fn(ctx, node, macro_file))?.body()?;
+    Some(body.reset_indent().to_string().trim_start().to_owned())
+}
+
+fn prettify_fn(
+    ctx: &CompletionContext<'_>,
+    node: &ast::Fn,
+    macro_file: Option<MacroFileId>,
+) -> SyntaxNode {
+    if let Some(macro_file) = macro_file {
+        let span_map = ctx.db.expansion_span_map(macro_file);
+        prettify_macro_expansion(ctx.db, node.syntax().clone(), &span_map, ctx.krate.into())
+    } else {
+        node.syntax().clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use expect_test::expect;
 
-    use crate::tests::{check, check_edit, check_no_kw};
+    use crate::{
+        tests::{check, check_edit, check_edit_with_config, check_no_kw, TEST_CONFIG},
+        CompletionConfig,
+    };
 
     #[test]
     fn no_completion_inside_fn() {
@@ -1377,6 +1420,114 @@ impl AnotherTrait for () {
         );
     }
 
+    #[test]
+    fn inserts_default_body_with_transformed_paths() {
+        check_edit_with_config(
+            CompletionConfig { trait_impl_default_body: true, ..TEST_CONFIG },
+            "fn describe",
+            r#"
+mod m {
+    pub struct Wrapper<T>(pub T);
+    pub fn wrap<T>(it: T) -> Wrapper<T> { Wrapper(it) }
+    pub trait Describe<T> {
+        fn value(&self) -> T;
+        fn describe(&self) -> Wrapper<T> {
+            let value: T = self.value();
+            wrap(value)
+        }
+    }
+}
+struct Foo;
+impl m::Describe<u32> for Foo {
+    $0
+}
+"#,
+            r#"
+mod m {
+    pub struct Wrapper<T>(pub T);
+    pub fn wrap<T>(it: T) -> Wrapper<T> { Wrapper(it) }
+    pub trait Describe<T> {
+        fn value(&self) -> T;
+        fn describe(&self) -> Wrapper<T> {
+            let value: T = self.value();
+            wrap(value)
+        }
+    }
+}
+struct Foo;
+impl m::Describe<u32> for Foo {
+    fn describe(&self) -> m::Wrapper<u32> {
+    let value: u32 = self.value();
+    m::wrap(value)
+}$0
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inserts_empty_body_without_default() {
+        check_edit_with_config(
+            CompletionConfig { trait_impl_default_body: true, ..TEST_CONFIG },
+            "fn value",
+            r#"
+trait Describe {
+    fn value(&self) -> u32;
+    fn describe(&self) -> u32 { self.value() }
+}
+struct Foo;
+impl Describe for Foo {
+    $0
+}
+"#,
+            r#"
+trait Describe {
+    fn value(&self) -> u32;
+    fn describe(&self) -> u32 { self.value() }
+}
+struct Foo;
+impl Describe for Foo {
+    fn value(&self) -> u32 {
+    $0
+}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inserts_macro_generated_default_body() {
+        check_edit_with_config(
+            CompletionConfig { trait_impl_default_body: true, ..TEST_CONFIG },
+            "fn method",
+            r#"
+macro_rules! define_method {
+    () => {
+        fn method(&self) -> u32 { let price = 1; price * 2 }
+    };
+}
+trait AnotherTrait { define_method!(); }
+impl AnotherTrait for () {
+    $0
+}
+"#,
+            r#"
+macro_rules! define_method {
+    () => {
+        fn method(&self) -> u32 { let price = 1; price * 2 }
+    };
+}
+trait AnotherTrait { define_method!(); }
+impl AnotherTrait for () {
+    fn method(&self) -> u32 {
+    let price = 1;
+    price*2
+}$0
+}
+"#,
+        );
+    }
+
     // FIXME: `T` in `ty!(T)` should be replaced by `PathTransform`.
     #[test]
     fn macro_generated_assoc_item2() {
@@ -0,0 +1,209 @@
+//! Completes fragment specifiers and metavariables inside the rules of `macro_rules!`
+//! definitions.
+use ide_db::SymbolKind;
+use syntax::TextRange;
+
+use crate::{
+    completions::Completions, context::MacroRulesContext, CompletionContext, CompletionItem,
+    CompletionItemKind,
+};
+
+const FRAGMENT_SPECIFIERS: &[(&str, &str)] = &[
+    ("block", "a block expression"),
+    ("expr", "an expression"),
+    ("expr_2021", "an expression, excluding `_` and `const` blocks"),
+    ("ident", "an identifier or keyword"),
+    ("item", "an item"),
+    ("lifetime", "a lifetime"),
+    ("literal", "a literal, optionally prefixed by `-`"),
+    ("meta", "the contents of an attribute"),
+    ("pat", "a pattern, including or-patterns"),
+    ("pat_param", "a pattern, excluding top-level or-patterns"),
+    ("path", "a type path"),
+    ("stmt", "a statement without its trailing semicolon"),
+    ("tt", "a single token tree"),
+    ("ty", "a type"),
+    ("vis", "a possibly empty visibility qualifier"),
+];
+
+pub(crate) fn complete_macro_rules(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    macro_ctx: &MacroRulesContext,
+) {
+    match macro_ctx {
+        MacroRulesContext::FragmentSpecifier => {
+            for &(specifier, description) in FRAGMENT_SPECIFIERS {
+                let mut item = CompletionItem::new(
+                    CompletionItemKind::Keyword,
+                    ctx.source_range(),
+                    specifier,
+                    ctx.edition,
+                );
+                item.detail(description);
+                item.add_to(acc, ctx.db);
+            }
+        }
+        MacroRulesContext::Transcriber { metavars, dollar } => {
+            // Replace the `$` as well, so that the items are matched against it.
+            let range = match dollar {
+                Some(dollar) => {
+                    TextRange::new(dollar.text_range().start(), ctx.source_range().end())
+                }
+                None => ctx.source_range(),
+            };
+            for metavar in metavars {
+                CompletionItem::new(
+                    SymbolKind::ValueParam,
+                    range,
+                    format!("${metavar}"),
+                    ctx.edition,
+                )
+                .add_to(acc, ctx.db);
+            }
+            CompletionItem::new(CompletionItemKind::Keyword, range, "$crate", ctx.edition)
+                .add_to(acc, ctx.db);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::tests::{check, check_edit};
+
+    #[test]
+    fn completes_fragment_specifiers() {
+        check(
+            r#"
+macro_rules! m {
+    ($name:$0) => {};
+}
+"#,
+            expect![[r#"
+                kw block                                  a block expression
+                kw expr                                        an expression
+                kw expr_2021 an expression, excluding `_` and `const` blocks
+                kw ident                            an identifier or keyword
+                kw item                                              an item
+                kw lifetime                                       a lifetime
+                kw literal             a literal, optionally prefixed by `-`
+                kw meta                         the contents of an attribute
+                kw pat                      a pattern, including or-patterns
+                kw pat_param      a pattern, excluding top-level or-patterns
+                kw path                                          a type path
+                kw stmt           a statement without its trailing semicolon
+                kw tt                                    a single token tree
+                kw ty                                                 a type
+                kw vis                 a possibly empty visibility qualifier
+            "#]],
+        );
+        check_edit(
+            "pat_param",
+            r#"
+macro_rules! m {
+    ($($p:pa$0),*) => {};
+}
+"#,
+            r#"
+macro_rules! m {
+    ($($p:pat_param),*) => {};
+}
+"#,
+        );
+        check_edit(
+            "ty",
+            r#"
+macro_rules! m {
+    ($type:t$0) => {};
+}
+"#,
+            r#"
+macro_rules! m {
+    ($type:ty) => {};
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn completes_metavariables_of_the_rule() {
+        check(
+            r#"
+macro_rules! m {
+    ($a:expr) => {};
+    ($name:ident, $($ty:ty),*) => {
+        fn $$0() {}
+    };
+}
+"#,
+            expect![[r#"
+                vp $name
+                vp $ty
+                kw $crate
+            "#]],
+        );
+        check(
+            r#"
+macro_rules! m {
+    ($type:ty, $fn:ident) => {
+        fn $$0() {}
+    };
+}
+"#,
+            expect![[r#"
+                vp $fn
+                vp $type
+                kw $crate
+            "#]],
+        );
+        check_edit(
+            "$type",
+            r#"
+macro_rules! m {
+    ($type:ty) => {
+        struct S($typ$0);
+    };
+}
+"#,
+            r#"
+macro_rules! m {
+    ($type:ty) => {
+        struct S($type);
+    };
+}
+"#,
+        );
+        check_edit(
+            "$name",
+            r#"
+macro_rules! m {
+    ($name:ident) => {
+        fn $na$0() {}
+    };
+}
+"#,
+            r#"
+macro_rules! m {
+    ($name:ident) => {
+        fn $name() {}
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_completions_outside_of_rules() {
+        check(
+            r#"
+macro_rules! m {
+    ($name:ident) => {};
+    $0
+}
+"#,
+            expect![""],
+        );
+    }
+}
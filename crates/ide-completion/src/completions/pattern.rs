@@ -71,8 +71,8 @@ pub(crate) fn complete_pattern(
                 ctx,
                 e,
                 &pattern_ctx.impl_,
-                |acc, ctx, variant, path| {
-                    acc.add_qualified_variant_pat(ctx, pattern_ctx, variant, path);
+                |acc, ctx, variant, path, import| {
+                    acc.add_qualified_variant_pat(ctx, pattern_ctx, variant, path, import);
                 },
             );
         }
@@ -9,8 +9,9 @@ use ide_db::{
     imports::insert_use::ImportScope,
     text_edit::TextEdit,
     ty_filter::TryEnum,
-    RootDatabase, SnippetCap,
+    FilePosition, RootDatabase, SnippetCap,
 };
+use ide_ssr::MatchFinder;
 use stdx::never;
 use syntax::{
     ast::{self, AstNode, AstToken},
@@ -171,6 +172,10 @@ pub(crate) fn complete_postfix(
     };
     postfix_snippet("unsafe", "unsafe {}", &unsafe_completion_string).add_to(acc, ctx.db);
 
+    if !ctx.config.snippets.is_empty() && !receiver_is_ambiguous_float_literal {
+        add_structural_postfix_completions(acc, ctx, &postfix_snippet, dot_receiver, receiver_ty);
+    }
+
     // The rest of the postfix completions create an expression that moves an argument,
     // so it's better to consider references now to avoid breaking the compilation
 
@@ -185,7 +190,7 @@ pub(crate) fn complete_postfix(
         };
 
     if !ctx.config.snippets.is_empty() {
-        add_custom_postfix_completions(acc, ctx, &postfix_snippet, &receiver_text);
+        add_custom_postfix_completions(acc, ctx, &postfix_snippet, &receiver_text, receiver_ty);
     }
 
     match try_enum {
@@ -289,7 +294,7 @@ fn get_receiver_text(
 ///
 /// Note that we don't need to escape the other characters that can be escaped,
 /// because they wouldn't be treated as snippet-specific constructs without '$'.
-fn escape_snippet_bits(text: &mut String) {
+pub(crate) fn escape_snippet_bits(text: &mut String) {
     stdx::replace(text, '\\', "\\\\");
     stdx::replace(text, '$', "\\$");
 }
@@ -388,15 +393,23 @@ fn add_custom_postfix_completions(
     ctx: &CompletionContext<'_>,
     postfix_snippet: impl Fn(&str, &str, &str) -> Builder,
     receiver_text: &str,
+    receiver_ty: &hir::Type,
 ) -> Option<()> {
     ImportScope::find_insert_use_container(&ctx.token.parent()?, &ctx.sema)?;
-    ctx.config.postfix_snippets().filter(|(_, snip)| snip.scope == SnippetScope::Expr).for_each(
-        |(trigger, snippet)| {
+    ctx.config
+        .postfix_snippets()
+        .filter(|(_, snip)| {
+            snip.scope == SnippetScope::Expr
+                && !snip.is_structural()
+                && snip.is_applicable(ctx)
+                && snip.is_applicable_to_receiver(ctx, receiver_ty)
+        })
+        .for_each(|(trigger, snippet)| {
             let imports = match snippet.imports(ctx) {
                 Some(imports) => imports,
                 None => return,
             };
-            let body = snippet.postfix_snippet(receiver_text);
+            let body = snippet.fill_placeholders(ctx, snippet.postfix_snippet(receiver_text));
             let mut builder =
                 postfix_snippet(trigger, snippet.description.as_deref().unwrap_or_default(), &body);
             builder.documentation(Documentation::new(format!("```rust\n{body}\n```")));
@@ -404,8 +417,63 @@ fn add_custom_postfix_completions(
                 builder.add_import(import);
             }
             builder.add_to(acc, ctx.db);
+        });
+    None
+}
+
+/// Adds the user defined postfix snippets whose body is a structural template. These are rendered
+/// with the structural search and replace machinery, as the replacement of a rule whose pattern is
+/// just the receiver, which takes care of parenthesizing the receiver and of rendering the paths in
+/// the template for the completion position.
+fn add_structural_postfix_completions(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    postfix_snippet: impl Fn(&str, &str, &str) -> Builder,
+    receiver: &ast::Expr,
+    receiver_ty: &hir::Type,
+) -> Option<()> {
+    let snippets: Vec<_> = ctx
+        .config
+        .postfix_snippets()
+        .filter(|(_, snip)| {
+            snip.scope == SnippetScope::Expr
+                && snip.is_structural()
+                && snip.is_applicable(ctx)
+                && snip.is_applicable_to_receiver(ctx, receiver_ty)
+        })
+        .collect();
+    // Setting up the matcher isn't free, so only do it when there is a snippet to render.
+    if snippets.is_empty() {
+        return None;
+    }
+    let receiver_range = ctx.sema.original_range_opt(receiver.syntax())?;
+    let finder = MatchFinder::in_context(
+        ctx.db,
+        FilePosition {
+            file_id: receiver_range.file_id.into(),
+            offset: receiver_range.range.start(),
         },
-    );
+        vec![],
+    )
+    .ok()?;
+    snippets.into_iter().for_each(|(trigger, snippet)| {
+        let Some(imports) = snippet.imports(ctx) else { return };
+        let rule = match format!("$receiver ==>> {}", snippet.structural_template()).parse() {
+            Ok(rule) => rule,
+            Err(_) => return,
+        };
+        let Ok(Some(mut body)) = finder.render_at_range(rule, receiver_range.into()) else {
+            return;
+        };
+        escape_snippet_bits(&mut body);
+        let mut builder =
+            postfix_snippet(trigger, snippet.description.as_deref().unwrap_or_default(), &body);
+        builder.documentation(Documentation::new(format!("```rust\n{body}\n```")));
+        for import in imports.into_iter() {
+            builder.add_import(import);
+        }
+        builder.add_to(acc, ctx.db);
+    });
     None
 }
 
@@ -414,8 +482,10 @@ mod tests {
     use expect_test::expect;
 
     use crate::{
-        tests::{check, check_edit, check_edit_with_config, TEST_CONFIG},
-        CompletionConfig, Snippet,
+        tests::{
+            check, check_edit, check_edit_with_config, do_completion_with_config, TEST_CONFIG,
+        },
+        CompletionConfig, CompletionItemKind, Snippet,
     };
 
     #[test]
@@ -808,6 +878,75 @@ fn main() {
         );
     }
 
+    #[test]
+    fn structural_custom_postfix_completion() {
+        let mut for_each = Snippet::new(
+            &[],
+            &["fore".into()],
+            &["for it in ${receiver} {}".into()],
+            "",
+            &[],
+            crate::SnippetScope::Expr,
+        )
+        .unwrap();
+        for_each.make_structural();
+        for_each.require_receiver_impl("core::iter::IntoIterator");
+        let mut wrap = Snippet::new(
+            &[],
+            &["wrap".into()],
+            &["crate::wrapper::Wrapper::new(${receiver})".into()],
+            "",
+            &[],
+            crate::SnippetScope::Expr,
+        )
+        .unwrap();
+        wrap.make_structural();
+        let config = CompletionConfig { snippets: vec![for_each, wrap], ..TEST_CONFIG };
+
+        check_edit_with_config(
+            config.clone(),
+            "fore",
+            r#"
+//- minicore: iterator
+fn main(a: [i32; 2]) { a.$0 }
+"#,
+            r#"
+fn main(a: [i32; 2]) { for it in a {} }
+"#,
+        );
+        check_edit_with_config(
+            config.clone(),
+            "wrap",
+            r#"
+mod wrapper {
+    pub struct Wrapper(i32);
+    impl Wrapper { pub fn new(it: i32) -> Self { Wrapper(it) } }
+}
+mod m {
+    fn f(a: i32) { -a.$0 }
+}
+"#,
+            r#"
+mod wrapper {
+    pub struct Wrapper(i32);
+    impl Wrapper { pub fn new(it: i32) -> Self { Wrapper(it) } }
+}
+mod m {
+    fn f(a: i32) { -crate::wrapper::Wrapper::new(a) }
+}
+"#,
+        );
+        let items = do_completion_with_config(
+            config,
+            r#"
+//- minicore: iterator
+fn main() { 42.$0 }
+"#,
+            CompletionItemKind::Snippet,
+        );
+        assert!(items.iter().all(|it| it.label.primary != "fore"));
+    }
+
     #[test]
     fn postfix_custom_snippets_completion_for_references() {
         // https://github.com/rust-lang/rust-analyzer/issues/7929
@@ -3,7 +3,7 @@
 use ide_db::{documentation::Documentation, imports::insert_use::ImportScope, SnippetCap};
 
 use crate::{
-    context::{ItemListKind, PathCompletionCtx, PathExprCtx, Qualified},
+    context::{ItemListKind, PathCompletionCtx, PathExprCtx, PatternContext, Qualified},
     item::Builder,
     CompletionContext, CompletionItem, CompletionItemKind, Completions, SnippetScope,
 };
@@ -117,6 +117,36 @@ macro_rules! $1 {
     }
 }
 
+pub(crate) fn complete_type_snippet(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    path_ctx: &PathCompletionCtx,
+) {
+    if !matches!(path_ctx.qualified, Qualified::No) {
+        return;
+    }
+    let Some(cap) = ctx.config.snippet_cap else { return };
+
+    if !ctx.config.snippets.is_empty() {
+        add_custom_completions(acc, ctx, cap, SnippetScope::Type);
+    }
+}
+
+pub(crate) fn complete_pattern_snippet(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    pattern_ctx: &PatternContext,
+) {
+    if pattern_ctx.record_pat.is_some() {
+        return;
+    }
+    let Some(cap) = ctx.config.snippet_cap else { return };
+
+    if !ctx.config.snippets.is_empty() {
+        add_custom_completions(acc, ctx, cap, SnippetScope::Pat);
+    }
+}
+
 fn snippet(ctx: &CompletionContext<'_>, cap: SnippetCap, label: &str, snippet: &str) -> Builder {
     let mut item =
         CompletionItem::new(CompletionItemKind::Snippet, ctx.source_range(), label, ctx.edition);
@@ -131,13 +161,15 @@ fn add_custom_completions(
     scope: SnippetScope,
 ) -> Option<()> {
     ImportScope::find_insert_use_container(&ctx.token.parent()?, &ctx.sema)?;
-    ctx.config.prefix_snippets().filter(|(_, snip)| snip.scope == scope).for_each(
-        |(trigger, snip)| {
+    ctx.config
+        .prefix_snippets()
+        .filter(|(_, snip)| snip.scope == scope && snip.is_applicable(ctx))
+        .for_each(|(trigger, snip)| {
             let imports = match snip.imports(ctx) {
                 Some(imports) => imports,
                 None => return,
             };
-            let body = snip.snippet();
+            let body = snip.fill_placeholders(ctx, snip.snippet());
             let mut builder = snippet(ctx, cap, trigger, &body);
             builder.documentation(Documentation::new(format!("```rust\n{body}\n```")));
             for import in imports.into_iter() {
@@ -145,16 +177,15 @@ fn add_custom_completions(
             }
             builder.set_detail(snip.description.clone());
             builder.add_to(acc, ctx.db);
-        },
-    );
+        });
     None
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        tests::{check_edit_with_config, TEST_CONFIG},
-        CompletionConfig, Snippet,
+        tests::{check_edit_with_config, do_completion_with_config, TEST_CONFIG},
+        CompletionConfig, CompletionItemKind, Snippet, SnippetScope,
     };
 
     #[test]
@@ -181,6 +212,132 @@ fn main() { $0 }
 use core::ops::ControlFlow;
 
 fn main() { ControlFlow::Break(()) }
+"#,
+        );
+    }
+
+    #[test]
+    fn custom_snippet_placeholder_offers_locals_of_its_type() {
+        let mut snippet = Snippet::new(
+            &["wr".into()],
+            &[],
+            &["write!(${1:f}, \"$0\")".into()],
+            "",
+            &[],
+            SnippetScope::Expr,
+        )
+        .unwrap();
+        snippet.constrain_placeholder(1, "Formatter");
+        check_edit_with_config(
+            CompletionConfig { snippets: vec![snippet], ..TEST_CONFIG },
+            "wr",
+            r#"
+struct Formatter;
+fn fmt(out: &mut Formatter, fmt: &Formatter, n: u32) {
+    $0
+}
+"#,
+            r#"
+struct Formatter;
+fn fmt(out: &mut Formatter, fmt: &Formatter, n: u32) {
+    write!(${1|fmt,out|}, "$0")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn custom_snippet_requiring_trait_impl() {
+        let mut snippet = Snippet::new(
+            &["wr".into()],
+            &[],
+            &["write!(f, \"$0\")".into()],
+            "",
+            &[],
+            SnippetScope::Expr,
+        )
+        .unwrap();
+        snippet.require_impl_of("Display");
+        let config = CompletionConfig { snippets: vec![snippet], ..TEST_CONFIG };
+        check_edit_with_config(
+            config.clone(),
+            "wr",
+            r#"
+trait Display { fn fmt(&self); }
+struct S;
+impl Display for S {
+    fn fmt(&self) { $0 }
+}
+"#,
+            r#"
+trait Display { fn fmt(&self); }
+struct S;
+impl Display for S {
+    fn fmt(&self) { write!(f, "$0") }
+}
+"#,
+        );
+        let completions = do_completion_with_config(
+            config,
+            r#"
+trait Display { fn fmt(&self); }
+struct S;
+impl S {
+    fn fmt(&self) { $0 }
+}
+"#,
+            CompletionItemKind::Snippet,
+        );
+        assert!(completions.iter().all(|it| it.label.primary != "wr"));
+    }
+
+    #[test]
+    fn custom_snippet_in_type_and_pattern_position() {
+        let config = CompletionConfig {
+            snippets: vec![
+                Snippet::new(
+                    &["res".into()],
+                    &[],
+                    &["Result<$1, ${2:Error}>".into()],
+                    "",
+                    &[],
+                    SnippetScope::Type,
+                )
+                .unwrap(),
+                Snippet::new(
+                    &["pair".into()],
+                    &[],
+                    &["(${1:a}, ${2:b})".into()],
+                    "",
+                    &[],
+                    SnippetScope::Pat,
+                )
+                .unwrap(),
+            ],
+            ..TEST_CONFIG
+        };
+        check_edit_with_config(
+            config.clone(),
+            "res",
+            r#"
+fn f() -> $0 {}
+"#,
+            r#"
+fn f() -> Result<$1, ${2:Error}> {}
+"#,
+        );
+        check_edit_with_config(
+            config,
+            "pair",
+            r#"
+fn f() {
+    let $0
+}
+"#,
+            r#"
+fn f() {
+    let (${1:a}, ${2:b})
+}
 "#,
         );
     }
@@ -7,7 +7,7 @@
 use hir::ImportPathConfig;
 use ide_db::{imports::insert_use::InsertUseConfig, SnippetCap};
 
-use crate::{snippet::Snippet, CompletionFieldsToResolve};
+use crate::{snippet::Snippet, CompletionFieldsToResolve, CompletionUsageStats};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CompletionConfig<'a> {
@@ -20,6 +20,7 @@ pub struct CompletionConfig<'a> {
     pub enable_term_search: bool,
     pub term_search_fuel: u64,
     pub full_function_signatures: bool,
+    pub trait_impl_default_body: bool,
     pub callable: Option<CallableSnippets>,
     pub add_semicolon_to_unit: bool,
     pub snippet_cap: Option<SnippetCap>,
@@ -32,6 +33,21 @@ pub struct CompletionConfig<'a> {
     pub fields_to_resolve: CompletionFieldsToResolve,
     pub exclude_flyimport: Vec<(String, AutoImportExclusionType)>,
     pub exclude_traits: &'a [String],
+    pub derive_helper_args: Vec<DeriveHelperArg>,
+    /// How often items were accepted before, used to rank them. `None` disables usage based
+    /// ranking.
+    pub usage_stats: Option<&'a CompletionUsageStats>,
+}
+
+/// A known argument of a derive helper attribute, like `rename_all` in
+/// `#[serde(rename_all = "camelCase")]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeriveHelperArg {
+    /// The name of the helper attribute, like `serde`.
+    pub helper: String,
+    pub key: String,
+    /// The string values the key accepts, or `None` if the key is a flag without a value.
+    pub values: Option<Vec<String>>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -28,7 +28,7 @@ use crate::{
     CompletionConfig,
 };
 
-const COMPLETION_MARKER: &str = "raCompletionMarker";
+pub(crate) const COMPLETION_MARKER: &str = "raCompletionMarker";
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) enum PatternRefutability {
@@ -375,12 +375,30 @@ pub(crate) enum CompletionAnalysis {
         /// fake token
         expanded: Option<ast::String>,
     },
+    /// The cargo-script frontmatter the cursor is currently inside
+    Frontmatter(SyntaxToken),
     /// Set if we are currently completing in an unexpanded attribute, this usually implies a builtin attribute like `allow($0)`
     UnexpandedAttrTT {
         colon_prefix: bool,
         fake_attribute_under_caret: Option<ast::Attr>,
         extern_crate: Option<ast::ExternCrate>,
     },
+    /// Set if we are completing inside the rules of a `macro_rules!` definition
+    MacroRules(MacroRulesContext),
+}
+
+/// Where in a `macro_rules!` rule we are completing.
+#[derive(Debug)]
+pub(crate) enum MacroRulesContext {
+    /// `$name:$0` in a matcher
+    FragmentSpecifier,
+    /// The transcriber of a rule
+    Transcriber {
+        /// The metavariables bound by the rule's matcher
+        metavars: Vec<SmolStr>,
+        /// The `$` preceding the cursor, if any
+        dollar: Option<SyntaxToken>,
+    },
 }
 
 /// Information about the field or method access we are completing.
@@ -732,9 +750,14 @@ impl<'a> CompletionContext<'a> {
             // return if no prev token before colon
             let prev_token = original_token.prev_token()?;
 
-            // only has a single colon
+            // only has a single colon, unless it precedes the fragment specifier of a
+            // `macro_rules!` metavariable
             if prev_token.kind() != T![:] {
-                return None;
+                let is_metavar = prev_token.kind() == SyntaxKind::IDENT
+                    && prev_token.prev_token().is_some_and(|it| it.kind() == T![$]);
+                if !is_metavar {
+                    return None;
+                }
             }
 
             // has 3 colon or 2 coloncolon in a row
@@ -3,23 +3,23 @@ use std::iter;
 
 use hir::{ExpandResult, Semantics, Type, TypeInfo, Variant};
 use ide_db::{active_parameter::ActiveParameter, RootDatabase};
-use itertools::Either;
+use itertools::{Either, Itertools};
 use syntax::{
     algo::{self, ancestors_at_offset, find_node_at_offset, non_trivia_sibling},
     ast::{
         self, AttrKind, HasArgList, HasGenericArgs, HasGenericParams, HasLoopBody, HasName,
         NameOrNameRef,
     },
-    match_ast, AstNode, AstToken, Direction, NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
-    SyntaxToken, TextRange, TextSize, T,
+    match_ast, AstNode, AstToken, Direction, NodeOrToken, SmolStr, SyntaxElement, SyntaxKind,
+    SyntaxNode, SyntaxToken, TextRange, TextSize, T,
 };
 
 use crate::context::{
     AttrCtx, BreakableKind, CompletionAnalysis, DotAccess, DotAccessExprCtx, DotAccessKind,
-    ItemListKind, LifetimeContext, LifetimeKind, NameContext, NameKind, NameRefContext,
-    NameRefKind, ParamContext, ParamKind, PathCompletionCtx, PathExprCtx, PathKind, PatternContext,
-    PatternRefutability, Qualified, QualifierCtx, TypeAscriptionTarget, TypeLocation,
-    COMPLETION_MARKER,
+    ItemListKind, LifetimeContext, LifetimeKind, MacroRulesContext, NameContext, NameKind,
+    NameRefContext, NameRefKind, ParamContext, ParamKind, PathCompletionCtx, PathExprCtx, PathKind,
+    PatternContext, PatternRefutability, Qualified, QualifierCtx, TypeAscriptionTarget,
+    TypeLocation, COMPLETION_MARKER,
 };
 
 #[derive(Debug)]
@@ -488,6 +488,8 @@ fn analyze(
     let Some(name_like) = find_node_at_offset(&speculative_file, speculative_offset) else {
         let analysis = if let Some(original) = ast::String::cast(original_token.clone()) {
             CompletionAnalysis::String { original, expanded: ast::String::cast(self_token.clone()) }
+        } else if original_token.kind() == SyntaxKind::FRONTMATTER {
+            CompletionAnalysis::Frontmatter(original_token.clone())
         } else {
             // Fix up trailing whitespace problem
             // #[attr(foo = $0
@@ -506,6 +508,8 @@ fn analyze(
                     colon_prefix,
                     extern_crate: p.ancestors().find_map(ast::ExternCrate::cast),
                 }
+            } else if let Some(macro_ctx) = classify_macro_rules(original_token) {
+                CompletionAnalysis::MacroRules(macro_ctx)
             } else {
                 return None;
             }
@@ -555,6 +559,57 @@ fn analyze(
     Some((analysis, expected, qual_ctx))
 }
 
+/// Classifies the position of `original_token` inside the rules of a `macro_rules!` definition.
+fn classify_macro_rules(original_token: &SyntaxToken) -> Option<MacroRulesContext> {
+    let macro_rules = original_token.parent_ancestors().find_map(ast::MacroRules::cast)?;
+    let body = macro_rules.token_tree()?;
+    // The token tree of the matcher or transcriber the cursor is in.
+    let rule_part = original_token
+        .parent_ancestors()
+        .take_while(|it| it.kind() == SyntaxKind::TOKEN_TREE)
+        .find(|it| it.parent().as_ref() == Some(body.syntax()))?;
+
+    // The token preceding the identifier being completed, if there is one. Metavariables may be
+    // named like keywords, e.g. `$type`.
+    let prev = match original_token.kind() {
+        kind if kind.is_any_identifier() => previous_non_trivia_token(original_token.clone()),
+        _ => Some(original_token.clone()),
+    };
+    let prev_kind = prev.as_ref().map(|it| it.kind());
+
+    // Token trees keep the `=>` separating matcher and transcriber as separate `=` and `>`.
+    let is_transcriber = non_trivia_sibling(rule_part.clone().into(), Direction::Prev)
+        .is_some_and(|it| matches!(it.kind(), T![>] | T![=>]));
+    if !is_transcriber {
+        let prev = prev.filter(|it| it.kind() == T![:])?;
+        let name = previous_non_trivia_token(prev).filter(|it| it.kind().is_any_identifier())?;
+        previous_non_trivia_token(name).filter(|it| it.kind() == T![$])?;
+        return Some(MacroRulesContext::FragmentSpecifier);
+    }
+
+    let matcher = rule_part
+        .siblings(Direction::Prev)
+        .skip(1)
+        .find(|it| it.kind() == SyntaxKind::TOKEN_TREE)?;
+    let tokens: Vec<SyntaxToken> = matcher
+        .descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|it| !it.kind().is_trivia())
+        .collect();
+    let metavars = tokens
+        .windows(3)
+        .filter(|it| {
+            it[0].kind() == T![$] && it[1].kind().is_any_identifier() && it[2].kind() == T![:]
+        })
+        .map(|it| SmolStr::new(it[1].text()))
+        .unique()
+        .collect();
+    Some(MacroRulesContext::Transcriber {
+        metavars,
+        dollar: prev.filter(|_| prev_kind == Some(T![$])),
+    })
+}
+
 /// Calculate the expected type and name of the cursor position.
 fn expected_type_and_name(
     sema: &Semantics<'_, RootDatabase>,
@@ -83,6 +83,14 @@ pub struct CompletionItem {
 
     /// The import data to add to completion's edits.
     pub import_to_add: SmallVec<[String; 1]>,
+
+    /// Where this item was completed, like `.vec::Vec` for methods of `Vec`. Accepted items are
+    /// counted per context to rank them, see [`CompletionUsageStats`]. Only set when
+    /// [`CompletionConfig::usage_stats`] is.
+    ///
+    /// [`CompletionUsageStats`]: crate::CompletionUsageStats
+    /// [`CompletionConfig::usage_stats`]: crate::CompletionConfig::usage_stats
+    pub usage_context: Option<SmolStr>,
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -182,6 +190,8 @@ pub struct CompletionRelevance {
     pub function: Option<CompletionRelevanceFn>,
     /// true when there is an `await.method()` or `iter().method()` completion.
     pub is_skipping_completion: bool,
+    /// How often this item was accepted before when completing in the same context.
+    pub usage_count: u32,
 }
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct CompletionRelevanceTraitInfo {
@@ -273,6 +283,7 @@ impl CompletionRelevance {
             trait_,
             function,
             is_skipping_completion,
+            usage_count,
         } = self;
 
         // only applicable for completions within use items
@@ -306,8 +317,11 @@ impl CompletionRelevance {
             score -= 7;
         }
 
-        // lower rank for items that need an import
-        if requires_import {
+        // prefer items the user picked before, with diminishing returns
+        score += usage_count.checked_ilog2().map_or(0, |it| it + 1).min(10);
+
+        // lower rank for items that need an import, unless they are of the expected type
+        if requires_import && type_match.is_none() {
             score -= 1;
         }
         if exact_name_match {
@@ -592,6 +606,7 @@ impl Builder {
             relevance: self.relevance,
             ref_match: self.ref_match,
             import_to_add,
+            usage_context: None,
         }
     }
     pub(crate) fn lookup_by(&mut self, lookup: impl Into<SmolStr>) -> &mut Builder {
@@ -755,8 +770,10 @@ mod tests {
             vec![Cr { postfix_match: Some(CompletionRelevancePostfixMatch::NonExact), ..default }],
             vec![Cr { is_private_editable: true, ..default }],
             vec![default],
-            vec![Cr { is_local: true, ..default }],
+            vec![Cr { is_local: true, ..default }, Cr { usage_count: 1, ..default }],
+            vec![Cr { usage_count: 4, ..default }],
             vec![Cr { type_match: Some(CompletionRelevanceTypeMatch::CouldUnify), ..default }],
+            vec![Cr { usage_count: u32::MAX, ..default }],
             vec![Cr { type_match: Some(CompletionRelevanceTypeMatch::Exact), ..default }],
             vec![Cr { exact_name_match: true, ..default }],
             vec![Cr { exact_name_match: true, is_local: true, ..default }],
@@ -7,8 +7,10 @@ mod item;
 mod render;
 
 mod snippet;
+mod statement;
 #[cfg(test)]
 mod tests;
+mod usage;
 
 use ide_db::{
     base_db::salsa::AsDynDatabase,
@@ -28,13 +30,15 @@ use crate::{
 };
 
 pub use crate::{
-    config::{AutoImportExclusionType, CallableSnippets, CompletionConfig},
+    config::{AutoImportExclusionType, CallableSnippets, CompletionConfig, DeriveHelperArg},
     item::{
         CompletionItem, CompletionItemKind, CompletionItemRefMode, CompletionRelevance,
         CompletionRelevancePostfixMatch, CompletionRelevanceReturnType,
         CompletionRelevanceTypeMatch,
     },
     snippet::{Snippet, SnippetScope},
+    statement::StatementCompletion,
+    usage::CompletionUsageStats,
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -244,6 +248,11 @@ pub fn completions(
                 completions::extern_abi::complete_extern_abi(acc, ctx, expanded);
                 completions::format_string::format_string(acc, ctx, original, expanded);
                 completions::env_vars::complete_cargo_env_vars(acc, ctx, original, expanded);
+                completions::include_path::complete_include_path(acc, ctx, original, expanded);
+                completions::attribute::complete_derive_helper_value(acc, ctx, original);
+            }
+            CompletionAnalysis::Frontmatter(frontmatter) => {
+                completions::frontmatter::complete_frontmatter(acc, ctx, frontmatter);
             }
             CompletionAnalysis::UnexpandedAttrTT {
                 colon_prefix,
@@ -258,11 +267,33 @@ pub fn completions(
                     extern_crate.as_ref(),
                 );
             }
+            CompletionAnalysis::MacroRules(macro_ctx) => {
+                completions::macro_rules::complete_macro_rules(acc, ctx, macro_ctx);
+            }
             CompletionAnalysis::UnexpandedAttrTT { .. } | CompletionAnalysis::String { .. } => (),
         }
     }
 
-    Some(completions.into())
+    let mut completions: Vec<CompletionItem> = completions.into();
+    if let Some(stats) = config.usage_stats {
+        usage::apply_usage_stats(ctx, analysis, stats, &mut completions);
+    }
+    Some(completions)
+}
+
+/// Computes candidates for the whole rest of the line at the given position, like a tail
+/// expression of the expected type on an otherwise empty line. These are built by term search, so
+/// nothing is offered unless it is enabled.
+pub fn statement_completions(
+    db: &RootDatabase,
+    config: &CompletionConfig<'_>,
+    position: FilePosition,
+) -> Option<Vec<StatementCompletion>> {
+    if !config.enable_term_search {
+        return None;
+    }
+    let (ctx, analysis) = &CompletionContext::new(db, position, config)?;
+    statement::complete_statement(ctx, analysis)
 }
 
 /// Resolves additional completion data at the position given.
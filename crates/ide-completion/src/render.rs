@@ -822,8 +822,8 @@ fn main() {
 "#,
             expect![[r#"
                 st dep::test_mod_b::Struct {…} dep::test_mod_b::Struct {  } [type_could_unify]
-                ex dep::test_mod_b::Struct {  }  [type_could_unify]
                 st Struct Struct [type_could_unify+requires_import]
+                ex dep::test_mod_b::Struct {  }  [type_could_unify]
                 fn main() fn() []
                 fn test(…) fn(Struct) []
                 md dep  []
@@ -897,9 +897,9 @@ fn main() {
 }
 "#,
             expect![[r#"
-                ev dep::test_mod_b::Enum::variant dep::test_mod_b::Enum::variant [type_could_unify]
-                ex dep::test_mod_b::Enum::variant  [type_could_unify]
+                ev Enum::variant Enum::variant [type_could_unify+requires_import]
                 en Enum Enum [type_could_unify+requires_import]
+                ex dep::test_mod_b::Enum::variant  [type_could_unify]
                 fn main() fn() []
                 fn test(…) fn(Enum) []
                 md dep  []
@@ -935,7 +935,7 @@ fn main() {
 }
 "#,
             expect![[r#"
-                ev dep::test_mod_b::Enum::Variant dep::test_mod_b::Enum::Variant [type_could_unify]
+                ev Enum::Variant Enum::Variant [type_could_unify+requires_import]
                 ex dep::test_mod_b::Enum::Variant  [type_could_unify]
                 fn main() fn() []
                 fn test(…) fn(Enum) []
@@ -1171,6 +1171,7 @@ fn main() { Foo::Fo$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                         trigger_call_info: true,
                     },
@@ -1221,6 +1222,7 @@ fn main() { Foo::Fo$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                         trigger_call_info: true,
                     },
@@ -1317,6 +1319,7 @@ fn main() { Foo::Fo$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                         trigger_call_info: true,
                     },
@@ -1400,6 +1403,7 @@ fn main() { let _: m::Spam = S$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                         trigger_call_info: true,
                     },
@@ -1436,6 +1440,7 @@ fn main() { let _: m::Spam = S$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                         trigger_call_info: true,
                     },
@@ -1526,6 +1531,7 @@ fn foo() { A { the$0 } }
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                     },
                 ]
@@ -1585,6 +1591,7 @@ impl S {
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                     },
                     CompletionItem {
@@ -1676,6 +1683,7 @@ use self::E::*;
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                         trigger_call_info: true,
                     },
@@ -1746,6 +1754,7 @@ fn foo(s: S) { s.$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                     },
                 ]
@@ -1958,6 +1967,7 @@ fn f() -> i32 {
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                     },
                 ]
@@ -2743,6 +2753,7 @@ fn foo(f: Foo) { let _: &u32 = f.b$0 }
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                         ref_match: "&@107",
                     },
@@ -2830,6 +2841,7 @@ fn foo() {
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                     },
                 ]
@@ -2888,6 +2900,7 @@ fn main() {
                                 },
                             ),
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                         ref_match: "&@92",
                     },
@@ -3263,6 +3276,7 @@ fn main() {
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                     },
                     CompletionItem {
@@ -3297,6 +3311,7 @@ fn main() {
                             postfix_match: None,
                             function: None,
                             is_skipping_completion: false,
+                            usage_count: 0,
                         },
                     },
                 ]
@@ -2,20 +2,46 @@
 
 use hir::{AsAssocItem, HirDisplay};
 use ide_db::SymbolKind;
-use syntax::ToSmolStr;
+use syntax::{format_smolstr, SmolStr, ToSmolStr};
 
-use crate::{item::CompletionItem, render::RenderContext};
+use crate::{
+    item::{CompletionItem, CompletionRelevance},
+    render::{compute_type_match, RenderContext},
+};
 
 pub(crate) fn render_const(ctx: RenderContext<'_>, const_: hir::Const) -> Option<CompletionItem> {
     let _p = tracing::info_span!("render_const").entered();
-    render(ctx, const_)
+    render(ctx, const_, None)
 }
 
-fn render(ctx: RenderContext<'_>, const_: hir::Const) -> Option<CompletionItem> {
+/// Renders an associated const as `qualifier::CONST`.
+pub(crate) fn render_qualified_const(
+    ctx: RenderContext<'_>,
+    qualifier: SmolStr,
+    const_: hir::Const,
+) -> Option<CompletionItem> {
+    let _p = tracing::info_span!("render_qualified_const").entered();
+    render(ctx, const_, Some(qualifier))
+}
+
+fn render(
+    ctx: RenderContext<'_>,
+    const_: hir::Const,
+    qualifier: Option<SmolStr>,
+) -> Option<CompletionItem> {
     let db = ctx.db();
     let name = const_.name(db)?;
-    let (name, escaped_name) =
+    let (mut name, mut escaped_name) =
         (name.as_str().to_smolstr(), name.display(db, ctx.completion.edition).to_smolstr());
+    let mut relevance = ctx.completion_relevance();
+    if let Some(qualifier) = &qualifier {
+        name = format_smolstr!("{qualifier}::{name}");
+        escaped_name = format_smolstr!("{qualifier}::{escaped_name}");
+        relevance = CompletionRelevance {
+            type_match: compute_type_match(ctx.completion, &const_.ty(db)),
+            ..relevance
+        };
+    }
     let detail = const_.display(db, ctx.completion.display_target).to_string();
 
     let mut item =
@@ -23,14 +49,23 @@ fn render(ctx: RenderContext<'_>, const_: hir::Const) -> Option<CompletionItem>
     item.set_documentation(ctx.docs(const_))
         .set_deprecated(ctx.is_deprecated(const_) || ctx.is_deprecated_assoc_item(const_))
         .detail(detail)
-        .set_relevance(ctx.completion_relevance());
+        .set_relevance(relevance);
 
-    if let Some(actm) = const_.as_assoc_item(db) {
-        if let Some(trt) = actm.container_or_implemented_trait(db) {
-            item.trait_name(trt.name(db).display_no_db(ctx.completion.edition).to_smolstr());
+    match ctx.import_to_add {
+        Some(import_to_add) => {
+            item.add_import(import_to_add);
+        }
+        None => {
+            if let Some(actm) = const_.as_assoc_item(db) {
+                if let Some(trt) = actm.container_or_implemented_trait(db) {
+                    item.trait_name(
+                        trt.name(db).display_no_db(ctx.completion.edition).to_smolstr(),
+                    );
+                }
+            }
         }
     }
     item.insert_text(escaped_name);
 
-    Some(item.build(ctx.db()))
+    Some(item.build(db))
 }
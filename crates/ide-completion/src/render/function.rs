@@ -22,7 +22,8 @@ use crate::{
 
 #[derive(Debug)]
 enum FuncKind<'ctx> {
-    Function(&'ctx PathCompletionCtx),
+    /// A function, possibly qualified by the path to its `Self` type.
+    Function(&'ctx PathCompletionCtx, Option<SmolStr>),
     Method(&'ctx DotAccess, Option<SmolStr>),
 }
 
@@ -33,7 +34,18 @@ pub(crate) fn render_fn(
     func: hir::Function,
 ) -> Builder {
     let _p = tracing::info_span!("render_fn").entered();
-    render(ctx, local_name, func, FuncKind::Function(path_ctx))
+    render(ctx, local_name, func, FuncKind::Function(path_ctx, None))
+}
+
+/// Renders an associated function as `qualifier::func`.
+pub(crate) fn render_qualified_fn(
+    ctx: RenderContext<'_>,
+    path_ctx: &PathCompletionCtx,
+    qualifier: SmolStr,
+    func: hir::Function,
+) -> Builder {
+    let _p = tracing::info_span!("render_qualified_fn").entered();
+    render(ctx, None, func, FuncKind::Function(path_ctx, Some(qualifier)))
 }
 
 pub(crate) fn render_method(
@@ -62,8 +74,16 @@ fn render(
             format_smolstr!("{}.{}", receiver, name.as_str()),
             format_smolstr!("{}.{}", receiver, name.display(ctx.db(), completion.edition)),
         ),
+        FuncKind::Function(_, Some(qualifier)) => (
+            format_smolstr!("{}::{}", qualifier, name.as_str()),
+            format_smolstr!("{}::{}", qualifier, name.display(ctx.db(), completion.edition)),
+        ),
         _ => (name.as_str().to_smolstr(), name.display(db, completion.edition).to_smolstr()),
     };
+    let lookup = match func_kind {
+        FuncKind::Function(_, Some(_)) => call.clone(),
+        _ => name.as_str().to_smolstr(),
+    };
     let has_self_param = func.self_param(db).is_some();
     let mut item = CompletionItem::new(
         CompletionItemKind::SymbolKind(if has_self_param {
@@ -88,11 +108,10 @@ fn render(
         });
 
     let (has_dot_receiver, has_call_parens, cap) = match func_kind {
-        FuncKind::Function(&PathCompletionCtx {
-            kind: PathKind::Expr { .. },
-            has_call_parens,
-            ..
-        }) => (false, has_call_parens, ctx.completion.config.snippet_cap),
+        FuncKind::Function(
+            &PathCompletionCtx { kind: PathKind::Expr { .. }, has_call_parens, .. },
+            _,
+        ) => (false, has_call_parens, ctx.completion.config.snippet_cap),
         FuncKind::Method(&DotAccess { kind: DotAccessKind::Method { has_parens }, .. }, _) => {
             (true, has_parens, ctx.completion.config.snippet_cap)
         }
@@ -128,7 +147,7 @@ fn render(
     });
 
     match func_kind {
-        FuncKind::Function(path_ctx) => {
+        FuncKind::Function(path_ctx, _) => {
             super::path_ref_match(completion, path_ctx, &ret_type, &mut item);
         }
         FuncKind::Method(DotAccess { receiver: Some(receiver), .. }, _) => {
@@ -149,7 +168,7 @@ fn render(
     item.set_documentation(ctx.docs(func))
         .set_deprecated(ctx.is_deprecated(func) || ctx.is_deprecated_assoc_item(func))
         .detail(detail)
-        .lookup_by(name.as_str().to_smolstr());
+        .lookup_by(lookup);
 
     if let Some((cap, (self_param, params))) = complete_call_parens {
         add_call_parens(
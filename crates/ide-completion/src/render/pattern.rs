@@ -134,7 +134,11 @@ fn build_completion(
         Some(snippet_cap) => item.insert_snippet(snippet_cap, pat),
         None => item.insert_text(pat),
     };
-    item.build(ctx.db())
+    let db = ctx.db();
+    if let Some(import_to_add) = ctx.import_to_add {
+        item.add_import(import_to_add);
+    }
+    item.build(db)
 }
 
 fn render_pat(
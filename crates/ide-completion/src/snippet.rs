@@ -43,9 +43,22 @@
 // the items aren't yet in scope.
 //
 // * `scope` is an optional filter for when the snippet should be applicable. Possible values are:
-// ** for Snippet-Scopes: `expr`, `item` (default: `item`)
+// ** for Snippet-Scopes: `expr`, `item`, `type`, `pattern` (default: `item`)
 // ** for Postfix-Snippet-Scopes: `expr`, `type` (default: `expr`)
 //
+// * `placeholders` optionally maps placeholder numbers to type paths. When completing the snippet,
+// such a placeholder offers the locals in scope whose type matches as a choice.
+//
+// * `impl` is an optional trait path, the snippet will only be applicable inside an `impl` of that trait.
+//
+// * `receiver_impl` is an optional trait path, postfix snippets will only be applicable to receivers
+// implementing that trait.
+//
+// * `structural` makes the `body` of a postfix snippet a structural search and replace template
+// instead of plain text, with `${receiver}` standing for the receiver. The receiver gets
+// parenthesized where needed and the paths in the template are rendered as they are visible at the
+// completion position, so they have to resolve there. Structural templates can't contain tabstops.
+//
 // The `body` field also has access to placeholders as visible in the example as `$0`.
 // These placeholders take the form of `$number` or `${number:placeholder_text}` which can be traversed as tabstop in ascending order starting from 1,
 // with `$0` being a special case that always comes last.
@@ -53,6 +66,29 @@
 // There is also a special placeholder, `${receiver}`, which will be replaced by the receiver expression for postfix snippets, or a `$0` tabstop in case of normal snippets.
 // This replacement for normal snippets allows you to reuse a snippet for both post- and prefix in a single definition.
 //
+// Snippets can also be shared with everyone working on a project by putting them into a
+// `.rust-analyzer/snippets.toml` file, which applies to the source root containing it and the ones
+// nested in it. The file maps snippet names to definitions with the same fields as above:
+//
+// ```toml
+// ["fmt impl"]
+// prefix = "fmtw"
+// body = 'write!(${1:f}, "$0")'
+// placeholders = { 1 = "core::fmt::Formatter" }
+// impl = "core::fmt::Display"
+// scope = "expr"
+// ```
+//
+// A structural postfix template looping over anything that can be iterated looks like this:
+//
+// ```toml
+// ["for each"]
+// postfix = "fore"
+// body = "for it in ${receiver} {}"
+// receiver_impl = "core::iter::IntoIterator"
+// structural = true
+// ```
+//
 // For the VSCode editor, rust-analyzer also ships with a small set of defaults which can be removed
 // by overwriting the settings object mentioned above, the defaults are:
 //
@@ -100,9 +136,10 @@
 // }
 // ````
 
-use hir::{ModPath, Name, Symbol};
+use hir::{ItemInNs, ModPath, ModuleDef, Name, Symbol};
 use ide_db::imports::import_assets::LocatedImport;
 use itertools::Itertools;
+use syntax::{ast, AstNode};
 
 use crate::context::CompletionContext;
 
@@ -113,6 +150,7 @@ pub enum SnippetScope {
     Item,
     Expr,
     Type,
+    Pat,
 }
 
 /// A user supplied snippet.
@@ -124,6 +162,14 @@ pub struct Snippet {
     pub description: Option<Box<str>>,
     snippet: String,
     requires: Box<[ModPath]>,
+    /// The types of placeholders that are offered the matching locals in scope, by placeholder.
+    placeholder_types: Vec<(u32, ModPath)>,
+    /// The trait an enclosing impl has to implement for the snippet to be applicable.
+    impl_trait: Option<ModPath>,
+    /// The trait the receiver of a postfix snippet has to implement for it to be applicable.
+    receiver_trait: Option<ModPath>,
+    /// Whether the postfix expansion is a structural template rather than textual.
+    structural: bool,
 }
 
 impl Snippet {
@@ -146,9 +192,59 @@ impl Snippet {
             snippet,
             description,
             requires,
+            placeholder_types: Vec::new(),
+            impl_trait: None,
+            receiver_trait: None,
+            structural: false,
         })
     }
 
+    /// Offers the locals in scope of the type at `ty` as choices for the placeholder `$index`.
+    pub fn constrain_placeholder(&mut self, index: u32, ty: &str) {
+        self.placeholder_types.push((index, mod_path(ty)));
+    }
+
+    /// Makes the snippet only applicable inside impls of the trait at `trait_`.
+    pub fn require_impl_of(&mut self, trait_: &str) {
+        self.impl_trait = Some(mod_path(trait_));
+    }
+
+    /// Makes postfix snippets only applicable to receivers implementing the trait at `trait_`.
+    pub fn require_receiver_impl(&mut self, trait_: &str) {
+        self.receiver_trait = Some(mod_path(trait_));
+    }
+
+    /// Makes the postfix expansion a structural template, see
+    /// [`crate::completions::postfix`] for how it gets rendered.
+    pub fn make_structural(&mut self) {
+        self.structural = true;
+    }
+
+    pub(crate) fn is_structural(&self) -> bool {
+        self.structural
+    }
+
+    /// Whether the snippet's conditions on its surroundings hold at the completion position.
+    pub(crate) fn is_applicable(&self, ctx: &CompletionContext<'_>) -> bool {
+        let Some(trait_path) = &self.impl_trait else { return true };
+        let Some(trait_) = resolve_trait(ctx, trait_path) else { return false };
+        ctx.original_token
+            .parent_ancestors()
+            .filter_map(ast::Impl::cast)
+            .filter_map(|impl_| ctx.sema.to_def(&impl_))
+            .any(|impl_| impl_.trait_(ctx.db) == Some(trait_))
+    }
+
+    /// Whether `receiver_ty` implements the trait required of postfix receivers, if any.
+    pub(crate) fn is_applicable_to_receiver(
+        &self,
+        ctx: &CompletionContext<'_>,
+        receiver_ty: &hir::Type,
+    ) -> bool {
+        let Some(trait_path) = &self.receiver_trait else { return true };
+        resolve_trait(ctx, trait_path).is_some_and(|it| receiver_ty.impls_trait(ctx.db, it, &[]))
+    }
+
     /// Returns [`None`] if the required items do not resolve.
     pub(crate) fn imports(&self, ctx: &CompletionContext<'_>) -> Option<Vec<LocatedImport>> {
         import_edits(ctx, &self.requires)
@@ -161,6 +257,100 @@ impl Snippet {
     pub fn postfix_snippet(&self, receiver: &str) -> String {
         self.snippet.replace("${receiver}", receiver)
     }
+
+    /// The snippet body as the replacement template of a structural search and replace rule,
+    /// with `${receiver}` being the placeholder for the receiver.
+    pub(crate) fn structural_template(&self) -> &str {
+        &self.snippet
+    }
+
+    /// Fills the type-constrained placeholders of `snippet` with the locals that fit them.
+    pub(crate) fn fill_placeholders(&self, ctx: &CompletionContext<'_>, snippet: String) -> String {
+        let mut snippet = snippet;
+        for (index, ty) in &self.placeholder_types {
+            let Some(ty) = resolve_type(ctx, ty) else { continue };
+            let mut candidates: Vec<String> = ctx
+                .locals
+                .iter()
+                .filter(|(_, local)| {
+                    let local_ty = local.ty(ctx.db);
+                    local_ty.could_unify_with(ctx.db, &ty)
+                        || local_ty
+                            .as_reference()
+                            .is_some_and(|(it, _)| it.could_unify_with(ctx.db, &ty))
+                })
+                .map(|(name, _)| name.display_no_db(ctx.edition).to_string())
+                .collect();
+            if candidates.is_empty() {
+                continue;
+            }
+            candidates.sort();
+            let choice = match &*candidates {
+                [single] => format!("${{{index}:{single}}}"),
+                _ => format!("${{{index}|{}|}}", candidates.join(",")),
+            };
+            snippet = replace_placeholder(&snippet, *index, &choice);
+        }
+        snippet
+    }
+}
+
+fn resolve_trait(ctx: &CompletionContext<'_>, path: &ModPath) -> Option<hir::Trait> {
+    ctx.scope.resolve_mod_path(path).find_map(|it| match it {
+        ItemInNs::Types(ModuleDef::Trait(it)) => Some(it),
+        _ => None,
+    })
+}
+
+fn resolve_type(ctx: &CompletionContext<'_>, path: &ModPath) -> Option<hir::Type> {
+    ctx.scope.resolve_mod_path(path).find_map(|it| match it {
+        ItemInNs::Types(ModuleDef::Adt(it)) => Some(it.ty(ctx.db)),
+        ItemInNs::Types(ModuleDef::BuiltinType(it)) => Some(it.ty(ctx.db)),
+        ItemInNs::Types(ModuleDef::TypeAlias(it)) => Some(it.ty(ctx.db)),
+        _ => None,
+    })
+}
+
+/// Replaces the first occurrence of the placeholder `$index` in `snippet`, in either of its
+/// `$1`, `${1}` or `${1:default}` forms, with `replacement`.
+fn replace_placeholder(snippet: &str, index: u32, replacement: &str) -> String {
+    let index = index.to_string();
+    let mut offset = 0;
+    while let Some(pos) = snippet[offset..].find('$') {
+        let start = offset + pos;
+        let rest = &snippet[start + 1..];
+        let end = if let Some(digits) = rest.strip_prefix(&*index) {
+            // `$10` is not `$1`
+            (!digits.starts_with(|c: char| c.is_ascii_digit())).then(|| start + 1 + index.len())
+        } else if let Some(inner) = rest.strip_prefix('{') {
+            inner.strip_prefix(&*index).and_then(|after| {
+                let mut depth = 0;
+                let after_start = start + 2 + index.len();
+                if after.starts_with('}') {
+                    return Some(after_start + 1);
+                }
+                if !after.starts_with(':') {
+                    return None;
+                }
+                for (idx, c) in after.char_indices() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' if depth == 0 => return Some(after_start + idx + 1),
+                        '}' => depth -= 1,
+                        _ => (),
+                    }
+                }
+                None
+            })
+        } else {
+            None
+        };
+        match end {
+            Some(end) => return format!("{}{replacement}{}", &snippet[..start], &snippet[end..]),
+            None => offset = start + 1,
+        }
+    }
+    snippet.to_owned()
 }
 
 fn import_edits(ctx: &CompletionContext<'_>, requires: &[ModPath]) -> Option<Vec<LocatedImport>> {
@@ -193,11 +383,7 @@ fn validate_snippet(
 ) -> Option<(Box<[ModPath]>, String, Option<Box<str>>)> {
     let mut imports = Vec::with_capacity(requires.len());
     for path in requires.iter() {
-        let use_path = ModPath::from_segments(
-            hir::PathKind::Plain,
-            path.split("::").map(Symbol::intern).map(Name::new_symbol_root),
-        );
-        imports.push(use_path);
+        imports.push(mod_path(path));
     }
     let snippet = snippet.iter().join("\n");
     let description = (!description.is_empty())
@@ -206,3 +392,10 @@ fn validate_snippet(
         .map(Into::into);
     Some((imports.into_boxed_slice(), snippet, description))
 }
+
+fn mod_path(path: &str) -> ModPath {
+    ModPath::from_segments(
+        hir::PathKind::Plain,
+        path.split("::").map(Symbol::intern).map(Name::new_symbol_root),
+    )
+}
@@ -0,0 +1,213 @@
+//! Whole statement completions, "complete this line" candidates built by term search.
+//!
+//! These are offered for an empty line in tail position of a block with a known expected type, and
+//! for the initializer of a `let` statement with a type annotation. Unlike regular completions,
+//! each candidate is the whole rest of the line, which is meant to be shown as an inline
+//! completion.
+
+// Feature: Inline Statement Completion
+//
+// With term search completions enabled (`rust-analyzer.completion.termSearch.enable`), editors
+// supporting inline completions are offered whole lines built from the locals, functions and
+// constructors in scope, cheapest first: a tail expression of the expected type on an otherwise
+// empty line, or the rest of a `let` statement with a type annotation.
+//
+// ```rust
+// fn make(verbose: bool) -> Config {
+//     $0 // Config::new(verbose)
+// }
+// ```
+
+use hir::term_search::{term_search, Expr, TermSearchConfig, TermSearchCtx};
+use ide_db::{base_db::SourceDatabase, FxHashSet};
+use syntax::{ast, AstNode, TextRange};
+
+use crate::context::{
+    CompletionAnalysis, CompletionContext, NameRefContext, NameRefKind, PathCompletionCtx, PathKind,
+};
+
+/// How many candidates are offered at most.
+const MAX_CANDIDATES: usize = 10;
+
+/// A candidate for the rest of the line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatementCompletion {
+    /// The range on the completion line that gets replaced.
+    pub range: TextRange,
+    pub text: String,
+}
+
+pub(crate) fn complete_statement(
+    ctx: &CompletionContext<'_>,
+    analysis: &CompletionAnalysis,
+) -> Option<Vec<StatementCompletion>> {
+    let _p = tracing::info_span!("complete_statement").entered();
+    let CompletionAnalysis::NameRef(NameRefContext {
+        kind: NameRefKind::Path(path_ctx @ PathCompletionCtx { kind: PathKind::Expr { .. }, .. }),
+        ..
+    }) = analysis
+    else {
+        return None;
+    };
+    if !path_ctx.is_trivial_path() {
+        return None;
+    }
+    let path_expr = path_ctx.path.syntax().parent().and_then(ast::PathExpr::cast)?;
+    let parent = path_expr.syntax().parent()?;
+    let (goal, terminator) = if let Some(let_stmt) = ast::LetStmt::cast(parent.clone()) {
+        // The fake identifier confuses inference of the initializer, so go by the annotation of
+        // the `let` in the original file.
+        let root = ctx.original_token.parent_ancestors().last()?;
+        let original_let = syntax::algo::find_node_at_offset::<ast::LetStmt>(
+            &root,
+            let_stmt.syntax().text_range().start(),
+        )?;
+        let goal = ctx.sema.resolve_type(&original_let.ty()?)?;
+        (goal, if let_stmt.semicolon_token().is_some() { "" } else { ";" })
+    } else {
+        let stmt_list = ast::StmtList::cast(parent)?;
+        if stmt_list.tail_expr()?.syntax() != path_expr.syntax() || !line_is_empty_before(ctx) {
+            return None;
+        }
+        (ctx.expected_type.clone()?, "")
+    };
+    if goal.is_unit() || goal.is_unknown() {
+        return None;
+    }
+    let term_search_ctx = TermSearchCtx {
+        sema: &ctx.sema,
+        scope: &ctx.scope,
+        goal,
+        config: TermSearchConfig {
+            enable_borrowcheck: false,
+            // Alternatives squashed into one hole can't be shown inline, so keep them apart.
+            many_alternatives_threshold: MAX_CANDIDATES,
+            fuel: ctx.config.term_search_fuel,
+        },
+    };
+    let traits_in_scope = ctx.traits_in_scope();
+    let enclosing_fn = ctx
+        .original_token
+        .parent_ancestors()
+        .find_map(ast::Fn::cast)
+        .and_then(|it| ctx.sema.to_def(&it));
+    let cfg = ctx.config.import_path_config(ctx.is_nightly);
+    let mut candidates: Vec<_> = term_search(&term_search_ctx)
+        .into_iter()
+        .filter(|expr| !matches!(expr, Expr::Function { func, .. } if Some(*func) == enclosing_fn))
+        // Inline completions can't add imports, so the traits have to be in scope already.
+        .filter(|expr| {
+            expr.traits_used(ctx.db).into_iter().all(|it| traits_in_scope.contains(&it.into()))
+        })
+        .filter_map(|expr| {
+            let mut has_holes = false;
+            let code = expr
+                .gen_source_code(
+                    &ctx.scope,
+                    &mut |_| {
+                        has_holes = true;
+                        String::new()
+                    },
+                    cfg,
+                    ctx.display_target,
+                )
+                .ok()?;
+            (!has_holes).then(|| (expr.cost(), code))
+        })
+        .collect();
+    candidates.sort();
+    let mut seen = FxHashSet::default();
+    let range = ctx.source_range();
+    Some(
+        candidates
+            .into_iter()
+            .filter(|(_, code)| seen.insert(code.clone()))
+            .take(MAX_CANDIDATES)
+            .map(|(_, code)| StatementCompletion { range, text: format!("{code}{terminator}") })
+            .collect(),
+    )
+}
+
+/// Whether there is only whitespace between the start of the line and the completion range.
+fn line_is_empty_before(ctx: &CompletionContext<'_>) -> bool {
+    let text = ctx.db.file_text(ctx.position.file_id).text(ctx.db);
+    let before = &text[..usize::from(ctx.source_range().start())];
+    let line_start = before.rfind('\n').map_or(0, |it| it + 1);
+    before[line_start..].trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{position, TEST_CONFIG};
+
+    fn check(#[rust_analyzer::rust_fixture] ra_fixture: &str, expect: Expect) {
+        let (db, position) = position(ra_fixture);
+        let actual = crate::statement_completions(&db, &TEST_CONFIG, position)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|it| format!("{:?} {}\n", it.range, it.text))
+            .collect::<String>();
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn tail_expression_of_return_type() {
+        check(
+            r#"
+struct Config { verbose: bool }
+impl Config {
+    fn new(verbose: bool) -> Config { Config { verbose } }
+}
+fn make(verbose: bool) -> Config {
+    let _unused = 0;
+    $0
+}
+"#,
+            expect![[r#"
+                167..167 Config { verbose: verbose }
+                167..167 Config::new(verbose)
+                167..167 Config { verbose: false }
+                167..167 Config { verbose: true }
+                167..167 Config::new(false)
+                167..167 Config::new(true)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn let_initializer_of_annotated_type() {
+        check(
+            r#"
+struct Wrapper(u32);
+fn f(inner: u32) {
+    let w: Wrapper = $0
+}
+"#,
+            expect![[r#"
+                61..61 Wrapper(inner);
+            "#]],
+        );
+    }
+
+    #[test]
+    fn no_candidates_mid_line_or_without_expected_type() {
+        check(
+            r#"
+fn f(x: u32) -> u32 {
+    let y = x; $0
+}
+"#,
+            expect![[r#""#]],
+        );
+        check(
+            r#"
+fn f(x: u32) {
+    $0
+}
+"#,
+            expect![[r#""#]],
+        );
+    }
+}
@@ -69,6 +69,7 @@ pub(crate) const TEST_CONFIG: CompletionConfig<'_> = CompletionConfig {
     enable_term_search: true,
     term_search_fuel: 200,
     full_function_signatures: false,
+    trait_impl_default_body: false,
     callable: Some(CallableSnippets::FillArguments),
     add_semicolon_to_unit: true,
     snippet_cap: SnippetCap::new(true),
@@ -87,6 +88,8 @@ pub(crate) const TEST_CONFIG: CompletionConfig<'_> = CompletionConfig {
     fields_to_resolve: CompletionFieldsToResolve::empty(),
     exclude_flyimport: vec![],
     exclude_traits: &[],
+    derive_helper_args: Vec::new(),
+    usage_stats: None,
     enable_auto_await: true,
     enable_auto_iter: true,
 };
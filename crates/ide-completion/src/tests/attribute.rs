@@ -1241,3 +1241,152 @@ extern crate dep;
         )
     }
 }
+
+mod derive_helper {
+    use expect_test::Expect;
+
+    use crate::{
+        tests::{check_edit_with_config, completion_list_with_config, TEST_CONFIG},
+        CompletionConfig, DeriveHelperArg,
+    };
+
+    use super::*;
+
+    const MAC: &str = r#"
+//- /mac.rs crate:mac
+#![crate_type = "proc-macro"]
+
+#[proc_macro_derive(Serialize, attributes(serde))]
+pub fn serialize() {}
+
+//- /lib.rs crate:lib deps:mac
+#[rustc_builtin_macro]
+pub macro derive($item:item) {}
+"#;
+
+    fn config() -> CompletionConfig<'static> {
+        let arg = |key: &str, values: Option<&[&str]>| DeriveHelperArg {
+            helper: "serde".to_owned(),
+            key: key.to_owned(),
+            values: values.map(|it| it.iter().map(|&it| it.to_owned()).collect()),
+        };
+        CompletionConfig {
+            derive_helper_args: vec![
+                arg("default", None),
+                arg("rename", Some(&[])),
+                arg("rename_all", Some(&["camelCase", "snake_case"])),
+            ],
+            ..TEST_CONFIG
+        }
+    }
+
+    fn check(#[rust_analyzer::rust_fixture] ra_fixture: &str, expect: Expect) {
+        let actual =
+            completion_list_with_config(config(), &format!("{MAC}{ra_fixture}"), true, None);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_keys() {
+        check(
+            r#"
+#[derive(mac::Serialize)]
+#[serde(default, $0)]
+pub struct Foo;
+"#,
+            expect![[r#"
+                ba rename = "…"
+                ba rename_all = "…"
+            "#]],
+        );
+        check(
+            r#"
+#[derive(mac::Serialize)]
+pub struct Foo {
+    #[serde($0)]
+    field: i32,
+}
+"#,
+            expect![[r#"
+                ba default
+                ba rename = "…"
+                ba rename_all = "…"
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_values() {
+        check(
+            r#"
+#[derive(mac::Serialize)]
+#[serde(rename_all = $0)]
+pub struct Foo;
+"#,
+            expect![[r#"
+                ba "camelCase"
+                ba "snake_case"
+            "#]],
+        );
+        check(
+            r#"
+#[derive(mac::Serialize)]
+#[serde(rename_all = "cam$0")]
+pub struct Foo;
+"#,
+            expect![[r#"
+                ba "camelCase"
+                ba "snake_case"
+            "#]],
+        );
+    }
+
+    #[test]
+    fn requires_the_derive() {
+        check(
+            r#"
+#[serde($0)]
+pub struct Foo;
+"#,
+            expect![[r#""#]],
+        );
+    }
+
+    #[test]
+    fn inserts_key_snippets() {
+        check_edit_with_config(
+            config(),
+            "rename",
+            &format!("{MAC}#[derive(mac::Serialize)]\n#[serde(re$0)]\npub struct Foo;"),
+            r#"#[rustc_builtin_macro]
+pub macro derive($item:item) {}
+#[derive(mac::Serialize)]
+#[serde(rename = "$0")]
+pub struct Foo;"#,
+        );
+        check_edit_with_config(
+            config(),
+            "camelCase",
+            &format!(
+                "{MAC}#[derive(mac::Serialize)]\n#[serde(rename_all = cam$0)]\npub struct Foo;"
+            ),
+            r#"#[rustc_builtin_macro]
+pub macro derive($item:item) {}
+#[derive(mac::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Foo;"#,
+        );
+        check_edit_with_config(
+            config(),
+            "\"camelCase\"",
+            &format!(
+                "{MAC}#[derive(mac::Serialize)]\n#[serde(rename_all = \"cam$0\")]\npub struct Foo;"
+            ),
+            r#"#[rustc_builtin_macro]
+pub macro derive($item:item) {}
+#[derive(mac::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Foo;"#,
+        );
+    }
+}
@@ -2051,3 +2051,61 @@ fn foo() {
         "#]],
     );
 }
+
+#[test]
+fn expected_type_constructors_from_other_crate() {
+    let fixture = r#"
+//- minicore: default, derive
+//- /dep.rs crate:dep
+pub mod config {
+    #[derive(Default)]
+    pub struct Config(u32);
+    impl Config {
+        pub const DEFAULT: Config = Config(0);
+        pub fn new(n: u32) -> Self { Config(n) }
+        pub fn len(&self) -> u32 { self.0 }
+    }
+}
+//- /main.rs crate:main deps:dep
+fn run(config: dep::config::Config) {}
+fn main() {
+    run($0);
+}
+"#;
+    check_edit(
+        "Config::new",
+        fixture,
+        r#"
+use dep::config::Config;
+
+fn run(config: dep::config::Config) {}
+fn main() {
+    run(Config::new(${1:n})$0);
+}
+"#,
+    );
+    check_edit(
+        "Config::DEFAULT",
+        fixture,
+        r#"
+use dep::config::Config;
+
+fn run(config: dep::config::Config) {}
+fn main() {
+    run(Config::DEFAULT);
+}
+"#,
+    );
+    check_edit(
+        "Config::default",
+        fixture,
+        r#"
+use dep::config::Config;
+
+fn run(config: dep::config::Config) {}
+fn main() {
+    run(Config::default()$0);
+}
+"#,
+    );
+}
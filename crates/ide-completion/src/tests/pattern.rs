@@ -936,3 +936,31 @@ fn main() {
         "#]],
     );
 }
+
+#[test]
+fn expected_enum_variants_from_other_crate_in_match_arm() {
+    check_edit(
+        "Level::Warn",
+        r#"
+//- /dep.rs crate:dep
+pub mod log {
+    pub enum Level { Warn, Error }
+}
+//- /main.rs crate:main deps:dep
+fn f(level: dep::log::Level) {
+    match level {
+        $0
+    }
+}
+"#,
+        r#"
+use dep::log::Level;
+
+fn f(level: dep::log::Level) {
+    match level {
+        Level::Warn$0
+    }
+}
+"#,
+    );
+}
@@ -0,0 +1,160 @@
+//! Ranking of completion items by how often they were accepted before.
+//!
+//! The editor tells the server which items were accepted, and the server hands the counts back to
+//! us through [`CompletionConfig::usage_stats`]. Counts are kept per context, so that the methods
+//! picked on one type don't rank the methods offered on another.
+//!
+//! [`CompletionConfig::usage_stats`]: crate::CompletionConfig::usage_stats
+
+use hir::{HirDisplay, ModuleDef, PathResolution};
+use ide_db::FxHashMap;
+use syntax::{format_smolstr, SmolStr};
+
+use crate::{
+    context::{
+        CompletionAnalysis, CompletionContext, DotAccess, NameRefContext, NameRefKind,
+        PathCompletionCtx, PathKind, Qualified,
+    },
+    CompletionItem,
+};
+
+/// How often completion items were accepted, by the context they were completed in and their
+/// label.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompletionUsageStats {
+    counts: FxHashMap<SmolStr, FxHashMap<SmolStr, u32>>,
+}
+
+impl CompletionUsageStats {
+    /// Records that the item labelled `label` was accepted `count` more times in `context`.
+    pub fn record(&mut self, context: &str, label: &str, count: u32) {
+        let entry = self.counts.entry(context.into()).or_default().entry(label.into()).or_default();
+        *entry = entry.saturating_add(count);
+    }
+
+    pub fn count(&self, context: &str, label: &str) -> u32 {
+        self.counts.get(context).and_then(|labels| labels.get(label)).copied().unwrap_or(0)
+    }
+
+    /// Iterates over the `(context, label, count)` triples recorded so far.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, u32)> + '_ {
+        self.counts.iter().flat_map(|(context, labels)| {
+            labels.iter().map(move |(label, &count)| (context.as_str(), label.as_str(), count))
+        })
+    }
+}
+
+/// Marks `items` with the context they were completed in, and ranks them by how often they were
+/// accepted there.
+pub(crate) fn apply_usage_stats(
+    ctx: &CompletionContext<'_>,
+    analysis: &CompletionAnalysis,
+    stats: &CompletionUsageStats,
+    items: &mut [CompletionItem],
+) {
+    let Some(context) = usage_context(ctx, analysis) else { return };
+    for item in items {
+        item.relevance.usage_count = stats.count(&context, &item.label.primary);
+        item.usage_context = Some(context.clone());
+    }
+}
+
+fn usage_context(ctx: &CompletionContext<'_>, analysis: &CompletionAnalysis) -> Option<SmolStr> {
+    let CompletionAnalysis::NameRef(NameRefContext { kind, .. }) = analysis else {
+        return None;
+    };
+    match kind {
+        NameRefKind::DotAccess(DotAccess { receiver_ty: Some(ty), .. }) => {
+            Some(format_smolstr!(".{}", type_key(ctx, &ty.original)?))
+        }
+        NameRefKind::Path(PathCompletionCtx { qualified, kind, .. }) => match qualified {
+            Qualified::With {
+                resolution:
+                    Some(PathResolution::Def(
+                        def @ (ModuleDef::Adt(_) | ModuleDef::Trait(_) | ModuleDef::Module(_)),
+                    )),
+                ..
+            } => Some(format_smolstr!("{}::", def.canonical_path(ctx.db, ctx.edition)?)),
+            Qualified::No => Some(SmolStr::new_static(match kind {
+                PathKind::Expr { .. } => "expr",
+                PathKind::Type { .. } => "type",
+                PathKind::Pat { .. } => "pat",
+                PathKind::Item { .. } => "item",
+                _ => return None,
+            })),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Identifies the receiver type of a method call or field access, ignoring its generic arguments.
+fn type_key(ctx: &CompletionContext<'_>, ty: &hir::Type) -> Option<String> {
+    let ty = ty.strip_references();
+    if ty.contains_unknown() || ty.as_type_param(ctx.db).is_some() {
+        return None;
+    }
+    match ty.as_adt() {
+        Some(adt) => ModuleDef::from(adt).canonical_path(ctx.db, ctx.edition),
+        None => Some(ty.display(ctx.db, ctx.display_target).to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+    use ide_db::SymbolKind;
+    use itertools::Itertools;
+
+    use crate::{
+        tests::{get_all_items, TEST_CONFIG},
+        CompletionConfig, CompletionItemKind, CompletionUsageStats,
+    };
+
+    fn check(stats: &CompletionUsageStats, ra_fixture: &str, expect: Expect) {
+        let config = CompletionConfig { usage_stats: Some(stats), ..TEST_CONFIG };
+        let actual = get_all_items(config, ra_fixture, None)
+            .into_iter()
+            .filter(|it| it.kind == CompletionItemKind::SymbolKind(SymbolKind::Method))
+            .sorted_by_key(|it| std::cmp::Reverse(it.relevance.score()))
+            .map(|it| {
+                format!(
+                    "{} {} {}\n",
+                    it.label.primary,
+                    it.usage_context.unwrap_or_default(),
+                    it.relevance.usage_count
+                )
+            })
+            .collect::<String>();
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn ranks_methods_by_usage_on_the_receiver_type() {
+        let mut stats = CompletionUsageStats::default();
+        stats.record(".foo::Stack", "peek()", 5);
+        stats.record(".foo::Queue", "pop()", 9);
+        check(
+            &stats,
+            r#"
+mod foo {
+    pub struct Stack;
+    impl Stack {
+        pub fn pop(&self) {}
+        pub fn peek(&self) {}
+        pub fn push(&self) {}
+    }
+    pub struct Queue;
+}
+fn f(stack: &foo::Stack) {
+    stack.$0
+}
+"#,
+            expect![[r#"
+                peek() .foo::Stack 5
+                pop() .foo::Stack 0
+                push() .foo::Stack 0
+            "#]],
+        );
+    }
+}
@@ -93,6 +93,7 @@ impl RootDatabase {
             // hir::db::ConstEvalDiscriminantQuery
             // hir::db::ConstEvalQuery
             // hir::db::ConstEvalStaticQuery
+            // hir::db::ConstEvalWithLimitQuery
             // hir::db::ConstParamTyQuery
             // hir::db::DynCompatibilityOfTraitQuery
             // hir::db::FieldTypesQuery
@@ -0,0 +1,63 @@
+//! Helpers for the manifest embedded in the frontmatter of cargo scripts.
+
+use hir::{db::HirDatabase, Crate};
+use syntax::{SyntaxToken, TextRange, TextSize};
+
+use crate::FxHashSet;
+
+/// Returns the manifest inside of a `FRONTMATTER` token, that is the text between the opening and
+/// the closing fence, together with its range in the file.
+pub fn manifest(frontmatter: &SyntaxToken) -> Option<(TextRange, &str)> {
+    let text = frontmatter.text();
+    let start = text.find('\n')? + 1;
+    let body = &text[start..];
+    // An unterminated frontmatter extends to the end of the file.
+    let end = match body.rfind('\n') {
+        Some(it) if is_fence(&body[it + 1..]) => start + it + 1,
+        None if is_fence(body) => start,
+        _ => text.len(),
+    };
+    let offset = frontmatter.text_range().start();
+    let range =
+        TextRange::new(offset + TextSize::new(start as u32), offset + TextSize::new(end as u32));
+    Some((range, &text[start..end]))
+}
+
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_end();
+    line.len() >= 3 && line.bytes().all(|b| b == b'-')
+}
+
+/// The crates the script's `cargo metadata` resolved, excluding the sysroot.
+pub fn script_dependencies(db: &dyn HirDatabase, krate: Crate) -> Vec<Crate> {
+    let mut seen = FxHashSet::default();
+    let mut res = Vec::new();
+    let mut stack = vec![krate];
+    while let Some(krate) = stack.pop() {
+        for dep in krate.dependencies(db) {
+            let dep = dep.krate;
+            if dep.origin(db).is_lang() || !seen.insert(dep) {
+                continue;
+            }
+            res.push(dep);
+            stack.push(dep);
+        }
+    }
+    res
+}
+
+/// Finds the resolved crate for the dependency called `name` in the manifest.
+pub fn find_dependency(db: &dyn HirDatabase, krate: Crate, name: &str) -> Option<Crate> {
+    let name = name.replace('_', "-");
+    script_dependencies(db, krate).into_iter().find(|krate| {
+        krate
+            .display_name(db)
+            .is_some_and(|it| it.canonical_name().as_str().replace('_', "-") == name)
+    })
+}
+
+/// Whether `table` is one of the tables listing dependencies, `[dependencies]`,
+/// `[dev-dependencies]` or `[build-dependencies]`.
+pub fn is_dependency_table(table: &str) -> bool {
+    matches!(table, "dependencies" | "dev-dependencies" | "build-dependencies")
+}
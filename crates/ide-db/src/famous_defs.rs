@@ -134,6 +134,14 @@ impl FamousDefs<'_, '_> {
         self.find_function("core:mem:drop")
     }
 
+    pub fn core_mem_size_of(&self) -> Option<Function> {
+        self.find_function("core:mem:size_of")
+    }
+
+    pub fn core_mem_align_of(&self) -> Option<Function> {
+        self.find_function("core:mem:align_of")
+    }
+
     pub fn core_macros_todo(&self) -> Option<Macro> {
         self.find_macro("core:todo")
     }
@@ -478,10 +478,13 @@ fn insert_use_(scope: &ImportScope, use_item: ast::Use, group_imports: bool) {
         .skip(l_curly.is_some() as usize)
         .take_while(|child| match child {
             NodeOrToken::Node(node) => is_inner_attribute(node.clone()),
-            NodeOrToken::Token(token) => {
-                [SyntaxKind::WHITESPACE, SyntaxKind::COMMENT, SyntaxKind::SHEBANG]
-                    .contains(&token.kind())
-            }
+            NodeOrToken::Token(token) => [
+                SyntaxKind::WHITESPACE,
+                SyntaxKind::COMMENT,
+                SyntaxKind::SHEBANG,
+                SyntaxKind::FRONTMATTER,
+            ]
+            .contains(&token.kind()),
         })
         .filter(|child| child.as_token().is_none_or(|t| t.kind() != SyntaxKind::WHITESPACE))
         .last()
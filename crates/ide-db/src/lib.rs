@@ -6,6 +6,7 @@ mod apply_change;
 
 pub mod active_parameter;
 pub mod assists;
+pub mod cargo_script;
 pub mod defs;
 pub mod documentation;
 pub mod famous_defs;
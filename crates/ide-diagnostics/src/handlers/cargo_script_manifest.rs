@@ -0,0 +1,167 @@
+//! Diagnostics for the manifest embedded in the frontmatter of cargo scripts.
+
+use std::collections::BTreeMap;
+
+use hir::Crate;
+use ide_db::{cargo_script, EditionedFileId, FileRange, RootDatabase};
+use serde_derive::Deserialize;
+use syntax::{ast, TextRange, TextSize};
+use toml::Spanned;
+
+use crate::{Diagnostic, DiagnosticCode, Severity};
+
+type Dependencies = BTreeMap<Spanned<String>, toml::Value>;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Manifest {
+    #[serde(default)]
+    dependencies: Dependencies,
+    #[serde(default)]
+    dev_dependencies: Dependencies,
+    #[serde(default)]
+    build_dependencies: Dependencies,
+    #[serde(default)]
+    target: BTreeMap<String, TargetDependencies>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TargetDependencies {
+    #[serde(default)]
+    dependencies: Dependencies,
+    #[serde(default)]
+    dev_dependencies: Dependencies,
+    #[serde(default)]
+    build_dependencies: Dependencies,
+}
+
+// Diagnostic: invalid-cargo-script-manifest
+//
+// This diagnostic is shown for manifests in the frontmatter of cargo scripts that are not valid
+// TOML.
+
+// Diagnostic: unknown-dependency-feature
+//
+// This diagnostic is shown for features enabled in the manifest of a cargo script that the
+// dependency does not have.
+pub(crate) fn cargo_script_manifest(
+    acc: &mut Vec<Diagnostic>,
+    db: &RootDatabase,
+    file_id: EditionedFileId,
+    source_file: &ast::SourceFile,
+    krate: Option<Crate>,
+) -> Option<()> {
+    let frontmatter = source_file.frontmatter_token()?;
+    let (range, manifest) = cargo_script::manifest(&frontmatter)?;
+    let to_file_range = |span: std::ops::Range<usize>| FileRange {
+        file_id: file_id.into(),
+        range: TextRange::new(
+            range.start() + TextSize::new(span.start as u32),
+            range.start() + TextSize::new(span.end as u32),
+        ),
+    };
+
+    let manifest = match toml::from_str::<Manifest>(manifest) {
+        Ok(it) => it,
+        Err(err) => {
+            let range = match err.span() {
+                Some(span) => to_file_range(span),
+                None => FileRange { file_id: file_id.into(), range },
+            };
+            acc.push(Diagnostic::new(
+                DiagnosticCode::Ra("invalid-cargo-script-manifest", Severity::Error),
+                format!("invalid manifest: {}", err.message().trim_end().replace('\n', ", ")),
+                range,
+            ));
+            return Some(());
+        }
+    };
+
+    // Features can only be checked for the dependencies `cargo metadata` resolved.
+    let krate = krate?;
+    let dependencies =
+        [&manifest.dependencies, &manifest.dev_dependencies, &manifest.build_dependencies]
+            .into_iter()
+            .chain(
+                manifest.target.values().flat_map(|it| {
+                    [&it.dependencies, &it.dev_dependencies, &it.build_dependencies]
+                }),
+            )
+            .flatten();
+    for (name, dependency) in dependencies {
+        let Some(features) = dependency.get("features").and_then(toml::Value::as_array) else {
+            continue;
+        };
+        let span = name.span();
+        let name = dependency.get("package").and_then(toml::Value::as_str).unwrap_or(name.as_ref());
+        let Some(dependency) = cargo_script::find_dependency(db, krate, name) else { continue };
+        let known =
+            dependency.potential_cfg(db).get_cfg_values("feature").cloned().collect::<Vec<_>>();
+        for feature in features.iter().filter_map(toml::Value::as_str) {
+            // `dep/feature` enables a feature of one of the dependencies of the dependency.
+            if feature.contains('/') || known.iter().any(|it| it.as_str() == feature) {
+                continue;
+            }
+            acc.push(Diagnostic::new(
+                DiagnosticCode::Ra("unknown-dependency-feature", Severity::Error),
+                format!("`{name}` has no feature `{feature}`"),
+                to_file_range(span.clone()),
+            ));
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_diagnostics;
+
+    #[test]
+    fn invalid_toml() {
+        check_diagnostics(
+            r#"
+//- /main.rs crate:main
+---
+[dependencies]
+   serde = { version = "1"
+#//                       ^ error: invalid manifest: invalid inline table, expected `}`
+---
+fn main() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn unknown_dependency_feature() {
+        check_diagnostics(
+            r#"
+//- /main.rs crate:main deps:serde
+---
+[dependencies]
+   serde = { version = "1", features = ["derive", "nope"] }
+#//^^^^^ error: `serde` has no feature `nope`
+---
+fn main() {}
+//- /serde.rs crate:serde cfg:feature=derive,feature=std
+"#,
+        );
+    }
+
+    #[test]
+    fn unknown_feature_of_renamed_dependency() {
+        check_diagnostics(
+            r#"
+//- /main.rs crate:main deps:serde
+---
+[target.'cfg(unix)'.dev-dependencies.ser]
+#//                                  ^^^ error: `serde` has no feature `nope`
+package = "serde"
+features = ["std", "derive/full", "nope"]
+---
+fn main() {}
+//- /serde.rs crate:serde cfg:feature=derive,feature=std
+"#,
+        );
+    }
+}
@@ -100,7 +100,7 @@ fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::TypedHole) -> Option<Vec<Assist>
 #[cfg(test)]
 mod tests {
     use crate::tests::{
-        check_diagnostics, check_fixes_unordered, check_has_fix, check_has_single_fix,
+        check_diagnostics, check_fixes_unordered, check_has_fix, check_has_single_fix, check_no_fix,
     };
 
     #[test]
@@ -380,6 +380,277 @@ fn main() {
         );
     }
 
+    #[test]
+    fn generic_fn_with_trait_bound() {
+        check_has_fix(
+            r#"
+trait Describe {}
+struct Foo;
+impl Describe for Foo {}
+struct Bar;
+fn describe<T: Describe>(it: T) -> i32 { 0 }
+fn main() {
+    let foo = Foo;
+    let bar = Bar;
+    let c: i32 = _$0;
+}"#,
+            r#"
+trait Describe {}
+struct Foo;
+impl Describe for Foo {}
+struct Bar;
+fn describe<T: Describe>(it: T) -> i32 { 0 }
+fn main() {
+    let foo = Foo;
+    let bar = Bar;
+    let c: i32 = describe(foo);
+}"#,
+        );
+    }
+
+    #[test]
+    fn generic_fn_with_unsatisfied_trait_bound() {
+        check_diagnostics(
+            r#"
+trait Describe {}
+struct Bar;
+fn describe<T: Describe>(_: T) -> i32 { 0 }
+fn main(_bar: Bar) {
+    let _: i32 = _;
+               //^ error: invalid `_` expression, expected type `i32`
+}"#,
+        );
+    }
+
+    #[test]
+    fn generic_fn_with_generic_trait_bound() {
+        check_has_fix(
+            r#"
+//- minicore: from
+struct Name;
+struct Id;
+impl From<Id> for Name { fn from(_: Id) -> Self { Name } }
+fn describe<T: Into<Name>>(it: T) -> i32 { 0 }
+fn main() {
+    let id = Id;
+    let c: i32 = _$0;
+}"#,
+            r#"
+struct Name;
+struct Id;
+impl From<Id> for Name { fn from(_: Id) -> Self { Name } }
+fn describe<T: Into<Name>>(it: T) -> i32 { 0 }
+fn main() {
+    let id = Id;
+    let c: i32 = describe(id);
+}"#,
+        );
+    }
+
+    #[test]
+    fn generic_fn_with_unsatisfied_generic_trait_bound() {
+        check_diagnostics(
+            r#"
+//- minicore: from
+struct Name;
+struct Id;
+fn describe<T: Into<Name>>(_: T) -> i32 { 0 }
+fn main(_id: Id) {
+    let _: i32 = _;
+               //^ error: invalid `_` expression, expected type `i32`
+}"#,
+        );
+    }
+
+    #[test]
+    fn method_with_closure() {
+        check_has_fix(
+            r#"
+//- minicore: option, fn
+struct Name(u32);
+fn main() {
+    let id: Option<u32> = None;
+    let c: Option<Name> = _$0;
+}"#,
+            r#"
+struct Name(u32);
+fn main() {
+    let id: Option<u32> = None;
+    let c: Option<Name> = id.map(|it| Name(it));
+}"#,
+        );
+    }
+
+    #[test]
+    fn method_with_closure_calling_method() {
+        check_has_fix(
+            r#"
+//- minicore: option, fn
+struct Foo;
+impl Foo { fn len(&self) -> usize { 0 } }
+fn main() {
+    let foo: Option<Foo> = None;
+    let c: Option<usize> = _$0;
+}"#,
+            r#"
+struct Foo;
+impl Foo { fn len(&self) -> usize { 0 } }
+fn main() {
+    let foo: Option<Foo> = None;
+    let c: Option<usize> = foo.map(|it| it.len());
+}"#,
+        );
+    }
+
+    #[test]
+    fn method_with_closure_without_params() {
+        check_has_fix(
+            r#"
+//- minicore: option, fn
+struct Foo(u8);
+fn main(default: Foo) {
+    let foo: Option<Foo> = None;
+    let c: Foo = _$0;
+}"#,
+            r#"
+struct Foo(u8);
+fn main(default: Foo) {
+    let foo: Option<Foo> = None;
+    let c: Foo = foo.unwrap_or_else(|| default);
+}"#,
+        );
+    }
+
+    #[test]
+    fn iterator_adapter_with_closure() {
+        check_has_fix(
+            r#"
+//- minicore: iterators, fn
+use core::iter::Map;
+struct Name(u32);
+struct Ids;
+impl Iterator for Ids { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+fn name_of(id: u32) -> Name { Name(id) }
+struct Names { inner: Map<Ids, fn(u32) -> Name> }
+fn names(ids: Ids) -> Names {
+    Names { inner: _$0 }
+}"#,
+            r#"
+use core::iter::Map;
+struct Name(u32);
+struct Ids;
+impl Iterator for Ids { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+fn name_of(id: u32) -> Name { Name(id) }
+struct Names { inner: Map<Ids, fn(u32) -> Name> }
+fn names(ids: Ids) -> Names {
+    Names { inner: Iterator::map(ids, |it| name_of(it)) }
+}"#,
+        );
+    }
+
+    #[test]
+    fn try_operator_with_from_conversion() {
+        check_has_fix(
+            r#"
+//- minicore: result, try, from
+struct IoError;
+enum Error { Io(IoError) }
+impl From<IoError> for Error { fn from(it: IoError) -> Self { Error::Io(it) } }
+struct Config;
+fn read() -> Result<Config, IoError> { Err(IoError) }
+fn load() -> Result<(), Error> {
+    let config: Config = _$0;
+    Ok(())
+}"#,
+            r#"
+struct IoError;
+enum Error { Io(IoError) }
+impl From<IoError> for Error { fn from(it: IoError) -> Self { Error::Io(it) } }
+struct Config;
+fn read() -> Result<Config, IoError> { Err(IoError) }
+fn load() -> Result<(), Error> {
+    let config: Config = read()?;
+    Ok(())
+}"#,
+        );
+    }
+
+    #[test]
+    fn try_operator_not_applicable_in_closures_and_async_blocks() {
+        check_no_fix(
+            r#"
+//- minicore: result, try, from, fn
+struct IoError;
+enum Error { Io(IoError) }
+impl From<IoError> for Error { fn from(it: IoError) -> Self { Error::Io(it) } }
+struct Config(u8);
+fn read() -> Result<Config, IoError> { Err(IoError) }
+fn load() -> Result<(), Error> {
+    let _later = || {
+        let _config: Config = _$0;
+    };
+    Ok(())
+}"#,
+        );
+        check_no_fix(
+            r#"
+//- minicore: result, try, from, future
+struct IoError;
+enum Error { Io(IoError) }
+impl From<IoError> for Error { fn from(it: IoError) -> Self { Error::Io(it) } }
+struct Config(u8);
+fn read() -> Result<Config, IoError> { Err(IoError) }
+fn load() -> Result<(), Error> {
+    let _later = async {
+        let _config: Config = _$0;
+    };
+    Ok(())
+}"#,
+        );
+    }
+
+    #[test]
+    fn try_operator_not_applicable_without_conversion() {
+        check_diagnostics(
+            r#"
+//- minicore: result, try, from
+struct IoError;
+struct Error;
+struct Config(u8);
+fn read() -> Result<Config, IoError> { Err(IoError) }
+fn load() -> Result<(), Error> {
+    let _: Config = _;
+                  //^ error: invalid `_` expression, expected type `Config`
+    Ok(())
+}"#,
+        );
+    }
+
+    #[test]
+    fn try_operator_with_map_err() {
+        check_has_fix(
+            r#"
+//- minicore: result, try, from, fn
+struct IoError;
+enum Error { Io(IoError) }
+struct Config;
+fn read() -> Result<Config, IoError> { Err(IoError) }
+fn load() -> Result<(), Error> {
+    let config: Config = _$0;
+    Ok(())
+}"#,
+            r#"
+struct IoError;
+enum Error { Io(IoError) }
+struct Config;
+fn read() -> Result<Config, IoError> { Err(IoError) }
+fn load() -> Result<(), Error> {
+    let config: Config = read().map_err(|it| Error::Io(it))?;
+    Ok(())
+}"#,
+        );
+    }
+
     // FIXME
     #[test]
     fn local_shadow_fn() {
@@ -69,6 +69,7 @@ mod handlers {
     pub(crate) mod unused_variables;
 
     // The handlers below are unusual, the implement the diagnostics as well.
+    pub(crate) mod cargo_script_manifest;
     pub(crate) mod field_shorthand;
     pub(crate) mod json_is_not_rust;
     pub(crate) mod unlinked_file;
@@ -384,6 +385,13 @@ pub fn semantic_diagnostics(
     }
 
     let module = sema.file_to_module_def(file_id);
+    handlers::cargo_script_manifest::cargo_script_manifest(
+        &mut res,
+        db,
+        editioned_file_id,
+        &parse,
+        module.map(|it| it.krate()),
+    );
 
     let is_nightly = matches!(
         module.and_then(|m| db.toolchain_channel(m.krate().into())),
@@ -220,6 +220,60 @@ impl<'db> MatchFinder<'db> {
         nester::nest_and_remove_collisions(matches, &self.sema)
     }
 
+    /// Applies `rule` to just the node at `range`, returning the text it would be replaced with, or
+    /// `None` if the rule's pattern doesn't match that node. Unlike `edits`, this doesn't search
+    /// for matches, so a pattern consisting of only a placeholder can be used to render a template
+    /// for a node that's already known, e.g. `$a ==>> foo($a)`.
+    pub fn render_at_range(
+        &self,
+        rule: SsrRule,
+        range: ide_db::FileRange,
+    ) -> Result<Option<String>, SsrError> {
+        let rules = rule
+            .parsed_rules
+            .into_iter()
+            .enumerate()
+            .map(|(index, rule)| ResolvedRule::new(rule, &self.resolution_scope, index))
+            .collect::<Result<Vec<_>, _>>()?;
+        let file_id = self
+            .sema
+            .attach_first_edition(range.file_id)
+            .unwrap_or_else(|| EditionedFileId::current_edition(range.file_id));
+        let editioned_file_id_wrapper =
+            ide_db::base_db::EditionedFileId::new(self.sema.db.as_dyn_database(), file_id);
+        let file = self.sema.parse(editioned_file_id_wrapper);
+        let node = match file.syntax().covering_element(range.range) {
+            syntax::NodeOrToken::Node(it) => it,
+            syntax::NodeOrToken::Token(it) => match it.parent() {
+                Some(it) => it,
+                None => return Ok(None),
+            },
+        };
+        let file_text = self.sema.db.file_text(range.file_id).text(self.sema.db);
+        for node in node.ancestors().take_while(|it| it.text_range() == range.range) {
+            for rule in &rules {
+                // Same as in `output_debug_for_nodes_at_range`, a placeholder would match any
+                // node, so only consider rules of the node's kind.
+                if rule.pattern.node.kind() != node.kind()
+                    && !(ast::Expr::can_cast(rule.pattern.node.kind())
+                        && ast::Expr::can_cast(node.kind()))
+                {
+                    continue;
+                }
+                if let Ok(m) = matching::get_match(false, rule, &node, &None, &self.sema) {
+                    return Ok(Some(replacing::render_replace(
+                        self.sema.db,
+                        &m,
+                        &file_text,
+                        &rules,
+                        file_id.edition(),
+                    )));
+                }
+            }
+        }
+        Ok(None)
+    }
+
     /// Finds all nodes in `file_id` whose text is exactly equal to `snippet` and attempts to match
     /// them, while recording reasons why they don't match. This API is useful for command
     /// line-based debugging where providing a range is difficult.
@@ -58,7 +58,7 @@ struct ReplacementRenderer<'a> {
     edition: Edition,
 }
 
-fn render_replace(
+pub(crate) fn render_replace(
     db: &dyn hir::db::ExpandDatabase,
     match_info: &Match,
     file_src: &str,
@@ -1417,3 +1417,27 @@ fn replace_autoref_mut() {
         "#]],
     );
 }
+
+#[test]
+fn render_placeholder_only_rule_at_range() {
+    let (db, position, selections) = single_file(
+        r#"
+mod collections { pub struct Set; impl Set { pub fn from_iter<T>(t: T) -> Set { Set } } }
+mod m {
+    fn f(a: i32, b: i32) {
+        $0a + b$0;
+    }
+}
+"#,
+    );
+    let match_finder = MatchFinder::in_context(&db, position.into(), vec![]).unwrap();
+    let rendered = |rule: &str| {
+        match_finder.render_at_range(rule.parse().unwrap(), selections[0].into()).unwrap()
+    };
+    assert_eq!(rendered("$a ==>> $a.abs()").as_deref(), Some("(a + b).abs()"));
+    assert_eq!(
+        rendered("$a ==>> crate::collections::Set::from_iter($a)").as_deref(),
+        Some("crate::collections::Set::from_iter(a + b)")
+    );
+    assert_eq!(rendered("$a.abs() ==>> $a").as_deref(), None);
+}
@@ -0,0 +1,370 @@
+//! Explains how type inference arrived at the type of an expression, using the resolutions,
+//! adjustments and coercion sources inference recorded for the enclosing body.
+
+use either::Either;
+use hir::{
+    Adjust, AsAssocItem, AssocItemContainer, AutoBorrow, CoercionSource, DisplayTarget, Function,
+    GenericDef, GenericSubstitution, HirDisplay, Mutability, PathResolution, PointerCast,
+    Semantics, Type,
+};
+use ide_db::{famous_defs::FamousDefs, FileRange, RootDatabase};
+use stdx::format_to;
+use syntax::{ast, AstNode};
+
+// Feature: Explain Type
+//
+// Shows how the type of the expression under the cursor was inferred: the function or method
+// the expression resolved to with the generic arguments inferred for it, the trait obligations on
+// those arguments, the expected type of the expression and where it came from, and the coercions
+// applied to the expression. Hovers over expressions with an `{unknown}` type or a type mismatch offer this as an action.
+//
+// | Editor  | Action Name |
+// |---------|-------------|
+// | VS Code | **rust-analyzer: Explain Type** |
+pub(crate) fn explain_type(db: &RootDatabase, frange: FileRange) -> Option<String> {
+    let sema = Semantics::new(db);
+    let expr = expr_at(&sema, frange)?;
+    let krate = sema.scope(expr.syntax())?.krate();
+    let display_target = krate.to_display_target(db);
+    let famous_defs = FamousDefs(&sema, krate);
+    let ty = sema.type_of_expr(&expr)?.original;
+
+    let mut res = String::new();
+    format_to!(res, "`{}`: `{}`\n", short_text(&expr), ty.display(db, display_target));
+    if let Some((func, subst)) = resolved_function(&sema, &expr) {
+        explain_function(&famous_defs, display_target, func, subst, &mut res);
+    }
+    if let Some((source, expected)) = sema.coercion_source_of_expr(&expr) {
+        format_to!(
+            res,
+            "- expected `{}` from {}\n",
+            expected.display(db, display_target),
+            describe_source(&sema, display_target, source)
+        );
+    }
+    for adjustment in sema.expr_adjustments(&expr).unwrap_or_default() {
+        let target = adjustment.target.display(db, display_target);
+        let how = match adjustment.kind {
+            Adjust::NeverToAny => "the never type was coerced",
+            Adjust::Deref(None) => "dereferenced",
+            Adjust::Deref(Some(_)) => "dereferenced with `Deref`",
+            Adjust::Borrow(AutoBorrow::Ref(Mutability::Shared)) => "auto-borrowed",
+            Adjust::Borrow(AutoBorrow::Ref(Mutability::Mut)) => "mutably auto-borrowed",
+            Adjust::Borrow(AutoBorrow::RawPtr(_)) => "auto-borrowed as a raw pointer",
+            Adjust::Pointer(PointerCast::Unsize) => "unsized",
+            Adjust::Pointer(PointerCast::ReifyFnPointer) => "coerced to a function pointer",
+            Adjust::Pointer(PointerCast::UnsafeFnPointer) => {
+                "coerced to an unsafe function pointer"
+            }
+            Adjust::Pointer(PointerCast::ClosureFnPointer(_)) => {
+                "the closure was coerced to a function pointer"
+            }
+            Adjust::Pointer(PointerCast::MutToConstPointer) => "coerced to a const pointer",
+            Adjust::Pointer(PointerCast::ArrayToPointer) => "coerced to an element pointer",
+        };
+        format_to!(res, "- coercion: {how} to `{target}`\n");
+    }
+    if let Some((expected, actual)) = sema.type_mismatch_of_expr(&expr) {
+        format_to!(
+            res,
+            "- mismatch: expected `{}`, found `{}`\n",
+            expected.display(db, display_target),
+            actual.display(db, display_target)
+        );
+    }
+    if ty.contains_unknown() {
+        res.push_str(
+            "- `{unknown}` is a type that could not be inferred, or that failed to resolve\n",
+        );
+    }
+    Some(res)
+}
+
+/// Whether hovering `expr` should offer to explain its type.
+pub(crate) fn needs_explanation(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> bool {
+    sema.type_of_expr(expr).is_some_and(|ty| ty.original.contains_unknown())
+        || sema.type_mismatch_of_expr(expr).is_some()
+}
+
+fn expr_at(sema: &Semantics<'_, RootDatabase>, frange: FileRange) -> Option<ast::Expr> {
+    let file = sema.parse_guess_edition(frange.file_id);
+    file.syntax().covering_element(frange.range).ancestors().find_map(ast::Expr::cast)
+}
+
+fn resolved_function(
+    sema: &Semantics<'_, RootDatabase>,
+    expr: &ast::Expr,
+) -> Option<(Function, Option<GenericSubstitution>)> {
+    let path = match expr {
+        ast::Expr::MethodCallExpr(call) => {
+            return match sema.resolve_method_call_fallback(call)? {
+                (Either::Left(func), subst) => Some((func, subst)),
+                (Either::Right(_), _) => None,
+            };
+        }
+        ast::Expr::CallExpr(call) => match call.expr()? {
+            ast::Expr::PathExpr(path) => path,
+            _ => return None,
+        },
+        ast::Expr::PathExpr(path) => path.clone(),
+        _ => return None,
+    };
+    match sema.resolve_path_with_subst(&path.path()?)? {
+        (PathResolution::Def(hir::ModuleDef::Function(func)), subst) => Some((func, subst)),
+        _ => None,
+    }
+}
+
+fn describe_source(
+    sema: &Semantics<'_, RootDatabase>,
+    display_target: DisplayTarget,
+    source: CoercionSource,
+) -> String {
+    let db = sema.db;
+    match source {
+        CoercionSource::LetAnnotation => "the type annotation of the `let`".to_owned(),
+        CoercionSource::Argument { call, index } => {
+            let params = resolved_function(sema, &call).map(|(func, _)| match call {
+                ast::Expr::MethodCallExpr(_) => (func, func.params_without_self(db)),
+                _ => (func, func.assoc_fn_params(db)),
+            });
+            let param = params.as_ref().and_then(|(func, params)| {
+                let name = params.get(index)?.name(db)?;
+                Some((func.name(db), name))
+            });
+            match param {
+                Some((func, param)) => format!(
+                    "parameter `{}` of `{}`",
+                    param.display(db, display_target.edition),
+                    func.display(db, display_target.edition)
+                ),
+                None => format!("argument {} of `{}`", index + 1, short_text(&call)),
+            }
+        }
+        CoercionSource::Return => "the return type".to_owned(),
+        CoercionSource::Branches(ast::Expr::IfExpr(_)) => "the other branch of the `if`".to_owned(),
+        CoercionSource::Branches(ast::Expr::MatchExpr(_)) => {
+            "the other arms of the `match`".to_owned()
+        }
+        CoercionSource::Branches(_) => "the other elements of the array".to_owned(),
+        CoercionSource::Field(field) => {
+            format!("field `{}`", field.name(db).display(db, display_target.edition))
+        }
+    }
+}
+
+fn explain_function(
+    famous_defs @ FamousDefs(sema, _): &FamousDefs<'_, '_>,
+    display_target: DisplayTarget,
+    func: Function,
+    subst: Option<GenericSubstitution>,
+    res: &mut String,
+) {
+    let db = sema.db;
+    let name = func.name(db);
+    let name = name.display(db, display_target.edition);
+    let impl_ = func.as_assoc_item(db).and_then(|it| match it.container(db) {
+        AssocItemContainer::Impl(impl_) => Some(impl_),
+        AssocItemContainer::Trait(_) => None,
+    });
+    match impl_ {
+        Some(impl_) => format_to!(
+            res,
+            "- resolved to `{}::{name}`\n",
+            impl_.self_ty(db).display(db, display_target)
+        ),
+        None => format_to!(res, "- resolved to `{name}`\n"),
+    }
+
+    let args = subst.map(|it| it.types(db)).unwrap_or_default();
+    for (param, ty) in &args {
+        if ty.contains_unknown() {
+            format_to!(res, "- `{}` could not be inferred\n", param.as_str());
+        } else {
+            format_to!(
+                res,
+                "- inferred `{} = {}`\n",
+                param.as_str(),
+                ty.display(db, display_target)
+            );
+        }
+    }
+
+    let sized = famous_defs.core_marker_Sized();
+    let params = impl_
+        .map(|impl_| GenericDef::from(impl_).type_or_const_params(db))
+        .into_iter()
+        .flatten()
+        .chain(GenericDef::from(func).type_or_const_params(db))
+        .filter_map(|param| param.as_type_param(db));
+    for param in params {
+        let param_name = param.name(db);
+        let Some((_, ty)) = args.iter().find(|(name, _)| name == param_name.symbol()) else {
+            continue;
+        };
+        let param_name = param_name.display(db, display_target.edition);
+        for trait_ in param.trait_bounds(db).into_iter().filter(|&it| Some(it) != sized) {
+            let trait_name = trait_.name(db);
+            let trait_name = trait_name.display(db, display_target.edition);
+            format_to!(
+                res,
+                "- obligation `{}: {trait_name}` from `{param_name}: {trait_name}`{}\n",
+                ty.display(db, display_target),
+                obligation_status(db, ty, trait_)
+            );
+        }
+    }
+}
+
+fn obligation_status(db: &RootDatabase, ty: &Type, trait_: hir::Trait) -> &'static str {
+    // Without the arguments of generic traits, the trait solver can't tell whether they hold.
+    if ty.contains_unknown() || trait_.type_or_const_param_count(db, false) != 0 {
+        ""
+    } else if ty.impls_trait(db, trait_, &[]) {
+        " holds"
+    } else {
+        " is not satisfied"
+    }
+}
+
+/// The text of `expr`, shortened to its first line.
+fn short_text(expr: &ast::Expr) -> String {
+    let text = expr.syntax().text().to_string();
+    match text.split_once('\n') {
+        Some((first_line, _)) => format!("{}…", first_line.trim_end()),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::fixture;
+
+    fn check(#[rust_analyzer::rust_fixture] ra_fixture: &str, expect: Expect) {
+        let (analysis, frange) = fixture::range(ra_fixture);
+        let res = analysis.explain_type(frange).unwrap().unwrap_or_default();
+        expect.assert_eq(&res);
+    }
+
+    #[test]
+    fn generic_method_with_obligation() {
+        check(
+            r#"
+//- minicore: default
+struct Builder<T>(T);
+impl<T: Default> Builder<T> {
+    fn new() -> Self {
+        Builder(T::default())
+    }
+    fn finish(self) -> T {
+        self.0
+    }
+}
+struct Config;
+impl Default for Config {
+    fn default() -> Self {
+        Config
+    }
+}
+fn f() {
+    let n: Config = $0Builder::new().finish()$0;
+}
+"#,
+            expect![[r#"
+                `Builder::new().finish()`: `Config`
+                - resolved to `Builder<T>::finish`
+                - inferred `T = Config`
+                - obligation `Config: Default` from `T: Default` holds
+                - expected `Config` from the type annotation of the `let`
+            "#]],
+        );
+    }
+
+    #[test]
+    fn uninferred_parameter() {
+        check(
+            r#"
+//- minicore: option
+struct Wrapper<T>(T);
+impl<T> Wrapper<T> {
+    fn empty() -> Option<T> {
+        None
+    }
+}
+fn f() {
+    let w = $0Wrapper::empty()$0;
+}
+"#,
+            expect![[r#"
+                `Wrapper::empty()`: `Option<{unknown}>`
+                - resolved to `Wrapper<T>::empty`
+                - `T` could not be inferred
+                - `{unknown}` is a type that could not be inferred, or that failed to resolve
+            "#]],
+        );
+    }
+
+    #[test]
+    fn coercion_and_mismatch() {
+        check(
+            r#"
+//- minicore: coerce_unsized
+fn takes_slice(s: &[u8]) {}
+fn f(arr: [u8; 3]) {
+    takes_slice($0&arr$0);
+}
+"#,
+            expect![[r#"
+                `&arr`: `&[u8; 3]`
+                - expected `&[u8]` from parameter `s` of `takes_slice`
+                - coercion: dereferenced to `[u8; 3]`
+                - coercion: auto-borrowed to `&[u8; 3]`
+                - coercion: unsized to `&[u8]`
+            "#]],
+        );
+        check(
+            r#"
+fn takes_u32(n: u32) {}
+fn f() {
+    takes_u32($0"one"$0);
+}
+"#,
+            expect![[r#"
+                `"one"`: `&str`
+                - expected `u32` from parameter `n` of `takes_u32`
+                - mismatch: expected `u32`, found `&str`
+            "#]],
+        );
+    }
+
+    #[test]
+    fn expected_type_sources() {
+        check(
+            r#"
+struct S {
+    n: u64,
+}
+fn f(b: bool) -> S {
+    S { n: $0if b { 1 } else { 2 }$0 }
+}
+"#,
+            expect![[r#"
+                `if b { 1 } else { 2 }`: `u64`
+                - expected `u64` from field `n`
+            "#]],
+        );
+        check(
+            r#"
+fn f() -> u8 {
+    return $00$0;
+}
+"#,
+            expect![[r#"
+                `0`: `u8`
+                - expected `u8` from the return type
+            "#]],
+        );
+    }
+}
@@ -22,6 +22,7 @@ use syntax::{ast, AstNode, SyntaxKind::*, SyntaxNode, T};
 
 use crate::{
     doc_links::token_as_doc_comment,
+    explain_type,
     markdown_remove::remove_markdown,
     markup::Markup,
     navigation_target::UpmappingResult,
@@ -76,6 +77,8 @@ pub enum HoverAction {
     Implementation(FilePosition),
     Reference(FilePosition),
     GoToType(Vec<HoverGotoTypeData>),
+    /// Explain how the type of the expression at this range was inferred.
+    ExplainType(FileRange),
 }
 
 impl HoverAction {
@@ -373,6 +376,8 @@ fn hover_offset(
             acc
         })
         .map(|mut res: HoverResult| {
+            let expr = original_token.parent_ancestors().find_map(ast::Expr::cast);
+            res.actions.extend(expr.and_then(|expr| explain_type_action(sema, file_id, &expr)));
             res.actions = dedupe_or_merge_hover_actions(res.actions);
             RangeInfo::new(original_token.text_range(), res)
         })
@@ -380,7 +385,7 @@ fn hover_offset(
 
 fn hover_ranged(
     sema: &Semantics<'_, RootDatabase>,
-    FileRange { range, .. }: FileRange,
+    FileRange { file_id, range }: FileRange,
     file: SyntaxNode,
     config: &HoverConfig,
     edition: Edition,
@@ -405,15 +410,27 @@ fn hover_ranged(
     };
     let res =
         res.or_else(|| render::type_info_of(sema, config, &expr_or_pat, edition, display_target));
-    res.map(|it| {
+    res.map(|mut it| {
         let range = match expr_or_pat {
-            Either::Left(it) => it.syntax().text_range(),
+            Either::Left(expr) => {
+                it.actions.extend(explain_type_action(sema, file_id, &expr));
+                expr.syntax().text_range()
+            }
             Either::Right(it) => it.syntax().text_range(),
         };
         RangeInfo::new(range, it)
     })
 }
 
+fn explain_type_action(
+    sema: &Semantics<'_, RootDatabase>,
+    file_id: FileId,
+    expr: &ast::Expr,
+) -> Option<HoverAction> {
+    explain_type::needs_explanation(sema, expr)
+        .then(|| HoverAction::ExplainType(FileRange { file_id, range: expr.syntax().text_range() }))
+}
+
 // FIXME: Why is this pub(crate)?
 pub(crate) fn hover_for_definition(
     sema: &Semantics<'_, RootDatabase>,
@@ -645,6 +662,7 @@ fn dedupe_or_merge_hover_actions(actions: Vec<HoverAction>) -> Vec<HoverAction>
     let mut seen_implementation = false;
     let mut seen_reference = false;
     let mut seen_runnable = false;
+    let mut seen_explain_type = false;
     for action in actions {
         match action {
             HoverAction::GoToType(targets) => {
@@ -668,6 +686,12 @@ fn dedupe_or_merge_hover_actions(actions: Vec<HoverAction>) -> Vec<HoverAction>
                     deduped_actions.push(action);
                 }
             }
+            HoverAction::ExplainType(..) => {
+                if !seen_explain_type {
+                    seen_explain_type = true;
+                    deduped_actions.push(action);
+                }
+            }
         };
     }
 
@@ -2579,6 +2579,14 @@ fn test() {
                         offset: 16,
                     },
                 ),
+                ExplainType(
+                    FileRangeWrapper {
+                        file_id: FileId(
+                            0,
+                        ),
+                        range: 54..55,
+                    },
+                ),
                 GoToType(
                     [
                         HoverGotoTypeData {
@@ -3539,6 +3547,79 @@ fn foo_$0test() {}
     );
 }
 
+#[test]
+fn test_hover_unknown_type_has_explain_action() {
+    check_actions(
+        r#"
+struct Wrapper<T>(T);
+impl<T> Wrapper<T> {
+    fn empty() -> Wrapper<T> {
+        loop {}
+    }
+}
+fn f() {
+    let w = Wrapper::emp$0ty();
+}
+"#,
+        expect![[r#"
+            [
+                Reference(
+                    FilePositionWrapper {
+                        file_id: FileId(
+                            0,
+                        ),
+                        offset: 50,
+                    },
+                ),
+                ExplainType(
+                    FileRangeWrapper {
+                        file_id: FileId(
+                            0,
+                        ),
+                        range: 119..133,
+                    },
+                ),
+                GoToType(
+                    [
+                        HoverGotoTypeData {
+                            mod_path: "ra_test_fixture::Wrapper",
+                            nav: NavigationTarget {
+                                file_id: FileId(
+                                    0,
+                                ),
+                                full_range: 0..21,
+                                focus_range: 7..14,
+                                name: "Wrapper",
+                                kind: Struct,
+                                description: "struct Wrapper<T>(T)",
+                            },
+                        },
+                    ],
+                ),
+            ]
+        "#]],
+    );
+    check_actions(
+        r#"
+fn f() {
+    let n: u32 = $0"one"$0;
+}
+"#,
+        expect![[r#"
+            [
+                ExplainType(
+                    FileRangeWrapper {
+                        file_id: FileId(
+                            0,
+                        ),
+                        range: 26..31,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn test_hover_test_mod_has_action() {
     check_actions(
@@ -29,6 +29,7 @@ mod chaining;
 mod closing_brace;
 mod closure_captures;
 mod closure_ret;
+mod const_value;
 mod discriminant;
 mod extern_block;
 mod generic_param;
@@ -222,6 +223,7 @@ fn hints(
             ast::Expr(expr) => {
                 chaining::hints(hints, famous_defs, config, display_target, &expr);
                 adjustment::hints(hints, famous_defs, config, display_target, &expr);
+                const_value::expr_hints(hints, famous_defs, config, display_target, &expr);
                 match expr {
                     ast::Expr::CallExpr(it) => param_name::hints(hints, famous_defs, config, file_id, ast::Expr::from(it)),
                     ast::Expr::MethodCallExpr(it) => {
@@ -262,7 +264,10 @@ fn hints(
                     }
                     implicit_static::hints(hints, famous_defs, config, file_id, Either::Left(it))
                 },
-                ast::Item::Const(it) => implicit_static::hints(hints, famous_defs, config, file_id, Either::Right(it)),
+                ast::Item::Const(it) => {
+                    const_value::const_hints(hints, famous_defs, config, display_target, &it);
+                    implicit_static::hints(hints, famous_defs, config, file_id, Either::Right(it))
+                },
                 ast::Item::Enum(it) => discriminant::enum_hints(hints, famous_defs, config, file_id, it),
                 ast::Item::ExternBlock(it) => extern_block::extern_block_hints(hints, famous_defs, config, file_id, it),
                 _ => None,
@@ -271,6 +276,7 @@ fn hints(
             ast::Type(ty) => match ty {
                 ast::Type::FnPtrType(ptr) => lifetime::fn_ptr_hints(hints, ctx, famous_defs, config, file_id, ptr),
                 ast::Type::PathType(path) => lifetime::fn_path_hints(hints, ctx, famous_defs, config, file_id, path),
+                ast::Type::ArrayType(array) => const_value::array_type_hints(hints, famous_defs, config, &array),
                 _ => Some(()),
             },
             ast::GenericParamList(it) => bounds::hints(hints, famous_defs, config, file_id, it),
@@ -293,6 +299,7 @@ pub struct InlayHintsConfig {
     pub adjustment_hints_hide_outside_unsafe: bool,
     pub closure_return_type_hints: ClosureReturnTypeHints,
     pub closure_capture_hints: bool,
+    pub const_value_hints: bool,
     pub binding_mode_hints: bool,
     pub implicit_drop_hints: bool,
     pub lifetime_elision_hints: LifetimeElisionHints,
@@ -432,6 +439,7 @@ pub enum InlayKind {
     Chaining,
     ClosingBrace,
     ClosureCapture,
+    ConstValue,
     Discriminant,
     GenericParamList,
     Lifetime,
@@ -861,6 +869,7 @@ mod tests {
         lifetime_elision_hints: LifetimeElisionHints::Never,
         closure_return_type_hints: ClosureReturnTypeHints::Never,
         closure_capture_hints: false,
+        const_value_hints: false,
         adjustment_hints: AdjustmentHints::Never,
         adjustment_hints_mode: AdjustmentHintsMode::Prefix,
         adjustment_hints_hide_outside_unsafe: false,
@@ -0,0 +1,257 @@
+//! Implementation of "const value" inlay hints:
+//! ```no_run
+//! const SIZE: usize = 4 * 1024/* = 4096 (0x1000)*/;
+//! let buf: [u8; SIZE * 2/* = 8192 (0x2000)*/];
+//! let n = size_of::<u64>()/* = 8*/;
+//! ```
+use hir::{AsAssocItem, DisplayTarget, EvaluatedConst, PathResolution, Semantics};
+use ide_db::{famous_defs::FamousDefs, RootDatabase};
+use syntax::{
+    ast::{self, AstNode},
+    TextRange,
+};
+
+use crate::{InlayHint, InlayHintLabel, InlayHintPosition, InlayHintsConfig, InlayKind};
+
+/// The number of interpreter steps after which we give up on evaluating a value. Hints are
+/// computed on every keystroke, so this is much lower than the limit for the other const
+/// evaluations.
+const EXECUTION_LIMIT: usize = 100_000;
+
+pub(super) fn const_hints(
+    acc: &mut Vec<InlayHint>,
+    FamousDefs(sema, _): &FamousDefs<'_, '_>,
+    config: &InlayHintsConfig,
+    display_target: DisplayTarget,
+    const_: &ast::Const,
+) -> Option<()> {
+    if !config.const_value_hints {
+        return None;
+    }
+    let body = const_.body()?;
+    if is_literal(&body) {
+        return None;
+    }
+    let value = sema.to_def(const_)?.eval_with_limit(sema.db, EXECUTION_LIMIT).ok()?;
+    push_hint(acc, config, body.syntax().text_range(), render(sema, display_target, &value));
+    Some(())
+}
+
+pub(super) fn array_type_hints(
+    acc: &mut Vec<InlayHint>,
+    FamousDefs(sema, _): &FamousDefs<'_, '_>,
+    config: &InlayHintsConfig,
+    array: &ast::ArrayType,
+) -> Option<()> {
+    if !config.const_value_hints {
+        return None;
+    }
+    let len = array.const_arg()?.expr()?;
+    if is_literal(&len) {
+        return None;
+    }
+    let (_, len_value) = sema.resolve_type(&ast::Type::from(array.clone()))?.as_array(sema.db)?;
+    push_hint(acc, config, len.syntax().text_range(), render_usize(len_value as u64));
+    Some(())
+}
+
+pub(super) fn expr_hints(
+    acc: &mut Vec<InlayHint>,
+    famous_defs @ FamousDefs(sema, _): &FamousDefs<'_, '_>,
+    config: &InlayHintsConfig,
+    display_target: DisplayTarget,
+    expr: &ast::Expr,
+) -> Option<()> {
+    if !config.const_value_hints {
+        return None;
+    }
+    let label = match expr {
+        ast::Expr::ArrayExpr(array) => {
+            let ast::ArrayExprKind::Repeat { repeat: Some(len), .. } = array.kind() else {
+                return None;
+            };
+            if is_literal(&len) {
+                return None;
+            }
+            let (_, len_value) = sema.type_of_expr(expr)?.original.as_array(sema.db)?;
+            push_hint(acc, config, len.syntax().text_range(), render_usize(len_value as u64));
+            return Some(());
+        }
+        ast::Expr::CallExpr(call) => {
+            let callable = sema.resolve_expr_as_callable(&call.expr()?)?;
+            let hir::CallableKind::Function(func) = callable.kind() else { return None };
+            // Only look up the layout once we know it is needed, most calls are neither.
+            let is_size_of = Some(func) == famous_defs.core_mem_size_of();
+            if !is_size_of && Some(func) != famous_defs.core_mem_align_of() {
+                return None;
+            }
+            let layout = callable.type_arguments().next()?.layout(sema.db).ok()?;
+            render_usize(if is_size_of { layout.size() } else { layout.align() })
+        }
+        ast::Expr::PathExpr(path) => {
+            let PathResolution::Def(hir::ModuleDef::Const(const_)) =
+                sema.resolve_path(&path.path()?)?
+            else {
+                return None;
+            };
+            // Free consts already show their value on their definition.
+            const_.as_assoc_item(sema.db)?;
+            let value = const_.eval_with_limit(sema.db, EXECUTION_LIMIT).ok()?;
+            render(sema, display_target, &value)
+        }
+        _ => return None,
+    };
+    push_hint(acc, config, expr.syntax().text_range(), label);
+    Some(())
+}
+
+fn is_literal(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::Literal(_) => true,
+        ast::Expr::PrefixExpr(prefix) => prefix.expr().is_some_and(|it| is_literal(&it)),
+        _ => false,
+    }
+}
+
+fn render(
+    sema: &Semantics<'_, RootDatabase>,
+    display_target: DisplayTarget,
+    value: &EvaluatedConst,
+) -> String {
+    value.render_int().unwrap_or_else(|| value.render(sema.db, display_target))
+}
+
+fn render_usize(value: u64) -> String {
+    if value >= 10 {
+        format!("{value} ({value:#X})")
+    } else {
+        value.to_string()
+    }
+}
+
+fn push_hint(acc: &mut Vec<InlayHint>, config: &InlayHintsConfig, range: TextRange, value: String) {
+    let label = InlayHintLabel::simple(
+        format!("= {value}"),
+        Some(
+            config.lazy_tooltip(|| crate::InlayTooltip::String("evaluated constant value".into())),
+        ),
+        None,
+    );
+    acc.push(InlayHint {
+        range,
+        kind: InlayKind::ConstValue,
+        label,
+        text_edit: None,
+        position: InlayHintPosition::After,
+        pad_left: true,
+        pad_right: false,
+        resolve_parent: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inlay_hints::{
+        tests::{check_with_config, DISABLED_CONFIG},
+        InlayHintsConfig,
+    };
+
+    #[track_caller]
+    fn check_const_values(#[rust_analyzer::rust_fixture] ra_fixture: &str) {
+        check_with_config(
+            InlayHintsConfig { const_value_hints: true, ..DISABLED_CONFIG },
+            ra_fixture,
+        );
+    }
+
+    #[test]
+    fn const_items() {
+        check_const_values(
+            r#"
+const LITERAL: usize = 4;
+const NEGATIVE: i32 = -4;
+const PRODUCT: usize = LITERAL * 1024;
+                     //^^^^^^^^^^^^^^ = 4096 (0x1000)
+const SMALL: i8 = NEGATIVE as i8 + 1;
+                //^^^^^^^^^^^^^^^^^^ = -3 (0xFD)
+const FLAG: bool = PRODUCT > 10;
+                 //^^^^^^^^^^^^ = true
+const fn triple(x: u32) -> u32 {
+    x * 3
+}
+const TRIPLED: u32 = triple(3);
+                   //^^^^^^^^^ = 9
+const LOOPS: u32 = {
+    let mut i = 0;
+    loop {
+        i += 1;
+    }
+};
+"#,
+        );
+    }
+
+    #[test]
+    fn array_lengths() {
+        check_const_values(
+            r#"
+const N: usize = 2;
+struct Buffer {
+    header: [u8; 4],
+    body: [u8; N * 4],
+             //^^^^^ = 8
+}
+fn f() {
+    let zeroes = [0u16; N + 10];
+                      //^^^^^^ = 12 (0xC)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn size_of_calls() {
+        check_const_values(
+            r#"
+//- minicore: size_of
+use core::mem::{align_of, size_of};
+struct Pair(u32, u8);
+fn size_of_pair<T>() -> usize {
+    size_of::<(T, T)>()
+}
+fn f() {
+    let size = size_of::<Pair>();
+             //^^^^^^^^^^^^^^^^^ = 8
+    let align = align_of::<Pair>();
+              //^^^^^^^^^^^^^^^^^^ = 4
+    let generic = size_of_pair::<u64>();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn assoc_consts() {
+        check_const_values(
+            r#"
+struct Page;
+impl Page {
+    const SIZE: usize = 1 << 12;
+                      //^^^^^^^ = 4096 (0x1000)
+}
+trait Bits {
+    const BITS: u32;
+}
+impl Bits for u16 {
+    const BITS: u32 = 16;
+}
+const FREE: usize = 3;
+fn f() {
+    let size = Page::SIZE;
+             //^^^^^^^^^^ = 4096 (0x1000)
+    let free = FREE;
+}
+"#,
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use hir::{ConstEvalError, DefWithBody, DisplayTarget, Semantics};
-use ide_db::{base_db::SourceDatabase, FilePosition, LineIndexDatabase, RootDatabase};
+use ide_db::{
+    base_db::SourceDatabase, FileId, FilePosition, FileRange, LineIndexDatabase, RootDatabase,
+};
 use std::time::{Duration, Instant};
 use stdx::format_to;
 use syntax::{algo::ancestors_at_offset, ast, AstNode, TextRange};
@@ -9,8 +11,8 @@ use syntax::{algo::ancestors_at_offset, ast, AstNode, TextRange};
 // | Editor  | Action Name |
 // |---------|-------------|
 // | VS Code | **rust-analyzer: Interpret** |
-pub(crate) fn interpret(db: &RootDatabase, position: FilePosition) -> String {
-    match find_and_interpret(db, position) {
+pub(crate) fn interpret(db: &RootDatabase, position: FilePosition, check_ub: bool) -> String {
+    match find_and_interpret(db, position, check_ub) {
         Some((duration, mut result)) => {
             result.push('\n');
             format_to!(result, "----------------------\n");
@@ -21,7 +23,11 @@ pub(crate) fn interpret(db: &RootDatabase, position: FilePosition) -> String {
     }
 }
 
-fn find_and_interpret(db: &RootDatabase, position: FilePosition) -> Option<(Duration, String)> {
+fn find_and_interpret(
+    db: &RootDatabase,
+    position: FilePosition,
+    check_ub: bool,
+) -> Option<(Duration, String)> {
     let sema = Semantics::new(db);
     let source_file = sema.parse_guess_edition(position.file_id);
 
@@ -34,21 +40,11 @@ fn find_and_interpret(db: &RootDatabase, position: FilePosition) -> Option<(Dura
         ast::Item::Static(it) => sema.to_def(&it)?.into(),
         _ => return None,
     };
-    let span_formatter = |file_id, text_range: TextRange| {
-        let source_root = db.file_source_root(file_id).source_root_id(db);
-        let source_root = db.source_root(source_root).source_root(db);
-
-        let path = source_root.path_for_file(&file_id).map(|x| x.to_string());
-        let path = path.as_deref().unwrap_or("<unknown file>");
-        match db.line_index(file_id).try_line_col(text_range.start()) {
-            Some(line_col) => format!("file://{path}:{}:{}", line_col.line + 1, line_col.col),
-            None => format!("file://{path} range {text_range:?}"),
-        }
-    };
+    let span_formatter = |file_id, text_range| format_span(db, file_id, text_range);
     let display_target = def.module(db).krate().to_display_target(db);
     let start_time = Instant::now();
     let res = match def {
-        DefWithBody::Function(it) => it.eval(db, span_formatter),
+        DefWithBody::Function(it) => it.eval(db, span_formatter, check_ub),
         DefWithBody::Static(it) => it.eval(db).map(|it| it.render(db, display_target)),
         DefWithBody::Const(it) => it.eval(db).map(|it| it.render(db, display_target)),
         _ => unreachable!(),
@@ -58,22 +54,138 @@ fn find_and_interpret(db: &RootDatabase, position: FilePosition) -> Option<(Dura
     Some((duration, res))
 }
 
+// Feature: Evaluate Selection
+//
+// Evaluates the selected expression with the MIR interpreter, like in a REPL. The expression may
+// use consts, literals and calls, but no locals declared outside of it.
+pub(crate) fn evaluate_expression(db: &RootDatabase, frange: FileRange) -> String {
+    find_and_evaluate(db, frange)
+        .unwrap_or_else(|| "Not an expression inside a function, const or static".to_owned())
+}
+
+fn find_and_evaluate(db: &RootDatabase, frange: FileRange) -> Option<String> {
+    let sema = Semantics::new(db);
+    let source_file = sema.parse_guess_edition(frange.file_id);
+    let expr = source_file
+        .syntax()
+        .covering_element(frange.range)
+        .ancestors()
+        .find_map(ast::Expr::cast)?;
+    let display_target = sema.scope(expr.syntax())?.krate().to_display_target(db);
+    let res = match sema.eval_expr(&expr)? {
+        Ok(value) => value.render_debug(db).unwrap_or_else(|_| value.render(db, display_target)),
+        Err(e) => render_const_eval_error(db, e, display_target),
+    };
+    Some(res)
+}
+
 pub(crate) fn render_const_eval_error(
     db: &RootDatabase,
     e: ConstEvalError,
     display_target: DisplayTarget,
 ) -> String {
-    let span_formatter = |file_id, text_range: TextRange| {
-        let source_root = db.file_source_root(file_id).source_root_id(db);
-        let source_root = db.source_root(source_root).source_root(db);
-        let path = source_root.path_for_file(&file_id).map(|x| x.to_string());
-        let path = path.as_deref().unwrap_or("<unknown file>");
-        match db.line_index(file_id).try_line_col(text_range.start()) {
-            Some(line_col) => format!("file://{path}:{}:{}", line_col.line + 1, line_col.col),
-            None => format!("file://{path} range {text_range:?}"),
-        }
-    };
+    let span_formatter = |file_id, text_range| format_span(db, file_id, text_range);
     let mut r = String::new();
     _ = e.pretty_print(&mut r, db, span_formatter, display_target);
     r
 }
+
+/// Renders a source location of a mir eval error as a `file://` link.
+pub(crate) fn format_span(db: &RootDatabase, file_id: FileId, text_range: TextRange) -> String {
+    let source_root = db.file_source_root(file_id).source_root_id(db);
+    let source_root = db.source_root(source_root).source_root(db);
+    let path = source_root.path_for_file(&file_id).map(|x| x.to_string());
+    let path = path.as_deref().unwrap_or("<unknown file>");
+    match db.line_index(file_id).try_line_col(text_range.start()) {
+        Some(line_col) => format!("file://{path}:{}:{}", line_col.line + 1, line_col.col),
+        None => format!("file://{path} range {text_range:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::fixture;
+
+    fn check_evaluate(#[rust_analyzer::rust_fixture] ra_fixture: &str, expect: Expect) {
+        let (analysis, frange) = fixture::range(ra_fixture);
+        let res = analysis.evaluate_expression(frange).unwrap();
+        expect.assert_eq(&res);
+    }
+
+    #[test]
+    fn evaluate_arithmetic() {
+        check_evaluate(
+            r#"
+const MAX: u32 = 0xFFFF_FFFF;
+fn f() {
+    let x = $0(MAX / 3) >> 7 | 1 << 31$0;
+}
+"#,
+            expect!["2158668458 (0x80AAAAAA)"],
+        );
+    }
+
+    #[test]
+    fn evaluate_const_fn_call() {
+        check_evaluate(
+            r#"
+const BASE: u64 = 10;
+const fn pow(base: u64, exp: u32) -> u64 {
+    let mut result = 1;
+    let mut i = 0;
+    while i < exp {
+        result = result * base;
+        i = i + 1;
+    }
+    result
+}
+fn f(exp: u32) -> u64 {
+    pow(BASE, exp) + $0pow(BASE, 3)$0
+}
+"#,
+            expect!["1000 (0x3E8)"],
+        );
+    }
+
+    #[test]
+    fn evaluate_block_with_own_locals() {
+        check_evaluate(
+            r#"
+fn f() {
+    let v = $0{
+        let a = 6;
+        let b = 7;
+        a * b
+    }$0;
+}
+"#,
+            expect!["42 (0x2A)"],
+        );
+    }
+
+    #[test]
+    fn evaluate_uses_outer_local() {
+        check_evaluate(
+            r#"
+fn f(x: u32) {
+    let v = $0x + 1$0;
+}
+"#,
+            expect![[r#"
+                Use of a local variable declared outside of the evaluated code
+            "#]],
+        );
+    }
+
+    #[test]
+    fn evaluate_outside_of_body() {
+        check_evaluate(
+            r#"
+struct S$0;$0
+"#,
+            expect!["Not an expression inside a function, const or static"],
+        );
+    }
+}
@@ -22,6 +22,7 @@ mod annotations;
 mod call_hierarchy;
 mod doc_links;
 mod expand_macro;
+mod explain_type;
 mod extend_selection;
 mod fetch_crates;
 mod file_structure;
@@ -57,7 +58,7 @@ mod view_memory_layout;
 mod view_mir;
 mod view_syntax_tree;
 
-use std::panic::UnwindSafe;
+use std::{panic::UnwindSafe, sync::atomic::AtomicBool};
 
 use cfg::CfgOptions;
 use fetch_crates::CrateInfo;
@@ -113,7 +114,7 @@ pub use crate::{
         tags::{Highlight, HlMod, HlMods, HlOperator, HlPunct, HlTag},
         HighlightConfig, HlRange,
     },
-    test_explorer::{TestItem, TestItemKind},
+    test_explorer::{InterpretedTestResult, TestItem, TestItemKind},
 };
 pub use hir::Semantics;
 pub use ide_assists::{
@@ -121,7 +122,8 @@ pub use ide_assists::{
 };
 pub use ide_completion::{
     CallableSnippets, CompletionConfig, CompletionFieldsToResolve, CompletionItem,
-    CompletionItemKind, CompletionItemRefMode, CompletionRelevance, Snippet, SnippetScope,
+    CompletionItemKind, CompletionItemRefMode, CompletionRelevance, CompletionUsageStats,
+    DeriveHelperArg, Snippet, SnippetScope, StatementCompletion,
 };
 pub use ide_db::text_edit::{Indel, TextEdit};
 pub use ide_db::{
@@ -353,8 +355,24 @@ impl Analysis {
         self.with_db(|db| view_mir::view_mir(db, position))
     }
 
-    pub fn interpret_function(&self, position: FilePosition) -> Cancellable<String> {
-        self.with_db(|db| interpret::interpret(db, position))
+    /// Runs the function, const or static at `position` with the mir interpreter. `check_ub`
+    /// enables the detection of undefined behavior in functions.
+    pub fn interpret_function(
+        &self,
+        position: FilePosition,
+        check_ub: bool,
+    ) -> Cancellable<String> {
+        self.with_db(|db| interpret::interpret(db, position, check_ub))
+    }
+
+    /// Evaluates the expression covering `frange` with the mir interpreter, and renders its value.
+    pub fn evaluate_expression(&self, frange: FileRange) -> Cancellable<String> {
+        self.with_db(|db| interpret::evaluate_expression(db, frange))
+    }
+
+    /// Explains how the type of the expression covering `frange` was inferred.
+    pub fn explain_type(&self, frange: FileRange) -> Cancellable<Option<String>> {
+        self.with_db(|db| explain_type::explain_type(db, frange))
     }
 
     pub fn view_item_tree(&self, file_id: FileId) -> Cancellable<String> {
@@ -377,6 +395,25 @@ impl Analysis {
         self.with_db(|db| test_explorer::discover_tests_in_file(db, file_id))
     }
 
+    pub fn tests_to_interpret(
+        &self,
+        include: Option<&[String]>,
+        exclude: &[String],
+    ) -> Cancellable<Vec<String>> {
+        self.with_db(|db| test_explorer::tests_to_interpret(db, include, exclude))
+    }
+
+    /// Runs a test with the mir interpreter, until it finishes or `stop` is set. `check_ub`
+    /// fails the test on undefined behavior.
+    pub fn interpret_test(
+        &self,
+        test_id: &str,
+        stop: &AtomicBool,
+        check_ub: bool,
+    ) -> Cancellable<Option<InterpretedTestResult>> {
+        self.with_db(|db| test_explorer::interpret_test(db, test_id, stop, check_ub))
+    }
+
     /// Renders the crate graph to GraphViz "dot" syntax.
     pub fn view_crate_graph(&self, full: bool) -> Cancellable<Result<String, String>> {
         self.with_db(|db| view_crate_graph::view_crate_graph(db, full))
@@ -696,6 +733,17 @@ impl Analysis {
         self.with_db(|db| ide_completion::completions(db, config, position, trigger_character))
     }
 
+    /// Computes candidates for the whole rest of the line at the given position.
+    pub fn statement_completions(
+        &self,
+        config: &CompletionConfig<'_>,
+        position: FilePosition,
+    ) -> Cancellable<Vec<StatementCompletion>> {
+        Ok(self
+            .with_db(|db| ide_completion::statement_completions(db, config, position))?
+            .unwrap_or_default())
+    }
+
     /// Resolves additional completion data at the position given.
     pub fn resolve_completion_edits(
         &self,
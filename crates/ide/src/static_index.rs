@@ -162,6 +162,7 @@ impl StaticIndex<'_> {
                     binding_mode_hints: false,
                     max_length: Some(25),
                     closure_capture_hints: false,
+                    const_value_hints: false,
                     closing_brace_hints_min_lines: Some(25),
                     fields_to_resolve: InlayFieldsToResolve::empty(),
                     range_exclusive_hints: false,
@@ -1,11 +1,23 @@
-//! Discovers tests
+//! Discovers tests, and runs them with the mir interpreter.
 
-use hir::{Crate, Module, ModuleDef, Semantics};
-use ide_db::base_db;
-use ide_db::{base_db::RootQueryDb, FileId, RootDatabase};
+use std::{
+    ops::ControlFlow,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use hir::{
+    mir::{DebugState, MirDebugger},
+    Crate, Function, Module, ModuleDef, Semantics,
+};
+use ide_db::base_db::{self, salsa::Database as _};
+use ide_db::{base_db::RootQueryDb, FileId, FxHashSet, RootDatabase};
 use syntax::TextRange;
 
-use crate::{runnables::runnable_fn, NavigationTarget, Runnable, TryToNav};
+use crate::{
+    interpret::{format_span, render_const_eval_error},
+    runnables::runnable_fn,
+    NavigationTarget, Runnable, TryToNav,
+};
 
 #[derive(Debug)]
 pub enum TestItemKind {
@@ -25,6 +37,18 @@ pub struct TestItem {
     pub runnable: Option<Runnable>,
 }
 
+/// The result of running a test with the mir interpreter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpretedTestResult {
+    Passed,
+    Ignored,
+    /// The test failed, `message` contains the panic message or the interpreter error with its
+    /// backtrace, followed by the output of the test.
+    Failed {
+        message: String,
+    },
+}
+
 pub(crate) fn discover_test_roots(db: &RootDatabase) -> Vec<TestItem> {
     db.all_crates()
         .iter()
@@ -194,3 +218,221 @@ pub(crate) fn discover_tests_in_crate(
     r.extend(discover_tests_in_module(db, module, crate_test_id, false));
     r
 }
+
+/// Finds the ids of the test functions selected by `include` and `exclude`, which may contain the
+/// id of any [`TestItem`]. Every test is included if `include` is `None`.
+pub(crate) fn tests_to_interpret(
+    db: &RootDatabase,
+    include: Option<&[String]>,
+    exclude: &[String],
+) -> Vec<String> {
+    let selects = |ids: &[String], test_id: &str| {
+        ids.iter().any(|id| {
+            test_id
+                .strip_prefix(id.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+    };
+    let roots: Vec<String> = match include {
+        Some(include) => include
+            .iter()
+            .map(|id| id.split_once("::").map_or(id.as_str(), |(root, _)| root).to_owned())
+            .collect::<FxHashSet<_>>()
+            .into_iter()
+            .collect(),
+        None => discover_test_roots(db).into_iter().map(|it| it.id).collect(),
+    };
+    let mut tests: Vec<String> = roots
+        .iter()
+        .flat_map(|root| discover_tests_in_crate_by_test_id(db, root))
+        .filter(|it| matches!(it.kind, TestItemKind::Function))
+        .map(|it| it.id)
+        .filter(|id| include.is_none_or(|include| selects(include, id)) && !selects(exclude, id))
+        .collect();
+    tests.sort();
+    tests
+}
+
+/// Runs the test with the given id with the mir interpreter. The evaluation is stopped, failing
+/// the test, as soon as `stop` is set.
+pub(crate) fn interpret_test(
+    db: &RootDatabase,
+    test_id: &str,
+    stop: &AtomicBool,
+    check_ub: bool,
+) -> Option<InterpretedTestResult> {
+    let test = find_test_by_id(db, test_id)?;
+    if test.is_ignore(db) {
+        return Some(InterpretedTestResult::Ignored);
+    }
+    let span_formatter = |file_id, text_range| format_span(db, file_id, text_range);
+    let mut debugger = StopOnRequest { db, stop };
+    let result = match test.eval_test(db, span_formatter, &mut debugger, check_ub) {
+        Ok(it) if it.passed => InterpretedTestResult::Passed,
+        Ok(it) => InterpretedTestResult::Failed { message: it.output },
+        Err(e) => {
+            let display_target = test.module(db).krate().to_display_target(db);
+            InterpretedTestResult::Failed {
+                message: render_const_eval_error(db, e, display_target),
+            }
+        }
+    };
+    Some(result)
+}
+
+fn find_test_by_id(db: &RootDatabase, test_id: &str) -> Option<Function> {
+    let (path, fn_name) = test_id.rsplit_once("::")?;
+    let mut segments = path.split("::");
+    let krate: Crate = find_crate_by_id(db, segments.next()?)?.into();
+    let mut module = krate.root_module();
+    for segment in segments {
+        module = module
+            .children(db)
+            .find(|it| it.name(db).is_some_and(|name| name.as_str() == segment))?;
+    }
+    module.declarations(db).into_iter().find_map(|def| match def {
+        ModuleDef::Function(f) if f.is_test(db) && f.name(db).as_str() == fn_name => Some(f),
+        _ => None,
+    })
+}
+
+/// Stops the interpreter when the test run is aborted or the database is changed.
+struct StopOnRequest<'a> {
+    db: &'a RootDatabase,
+    stop: &'a AtomicBool,
+}
+
+impl MirDebugger for StopOnRequest<'_> {
+    fn on_step(&mut self, _: &DebugState<'_, '_>) -> ControlFlow<()> {
+        self.db.unwind_if_revision_cancelled();
+        if self.stop.load(Ordering::Relaxed) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use expect_test::{expect, Expect};
+
+    use crate::{fixture, InterpretedTestResult};
+
+    fn check_interpret(
+        #[rust_analyzer::rust_fixture] ra_fixture: &str,
+        include: Option<&[&str]>,
+        exclude: &[&str],
+        expect: Expect,
+    ) {
+        let (analysis, _) = fixture::file(ra_fixture);
+        let include: Option<Vec<String>> =
+            include.map(|it| it.iter().map(|it| (*it).to_owned()).collect());
+        let exclude: Vec<String> = exclude.iter().map(|it| (*it).to_owned()).collect();
+        let stop = AtomicBool::new(false);
+        let mut actual = String::new();
+        for test_id in analysis.tests_to_interpret(include.as_deref(), &exclude).unwrap() {
+            match analysis.interpret_test(&test_id, &stop, false).unwrap() {
+                Some(InterpretedTestResult::Failed { message }) => {
+                    actual += &format!("{test_id}: Failed\n");
+                    for line in message.lines() {
+                        // Function ids depend on the salsa database, leave them out.
+                        let line = match line.split_once(" (FunctionId(") {
+                            Some((start, _)) => start,
+                            None => line,
+                        };
+                        actual += &format!("    {line}\n");
+                    }
+                }
+                result => actual += &format!("{test_id}: {result:?}\n"),
+            }
+        }
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn interpret_tests() {
+        check_interpret(
+            r#"
+//- minicore: fmt, panic
+//- /lib.rs crate:foo
+fn add(a: i32, b: i32) -> i32 { a + b }
+
+#[test]
+fn passes() {
+    if add(2, 2) != 4 {
+        panic!("wrong sum");
+    }
+}
+
+#[test]
+fn fails() {
+    if add(2, 2) != 5 {
+        panic!("wrong sum");
+    }
+}
+
+#[test]
+#[should_panic]
+fn should_panic() {
+    panic!("expected");
+}
+
+#[test]
+#[ignore]
+fn ignored() {}
+
+mod nested {
+    #[test]
+    fn nested_test() {}
+}
+"#,
+            None,
+            &[],
+            expect![[r#"
+                foo::fails: Failed
+                    Mir eval error:
+                    In function const_panic_fmt
+                    file:///lib.rs:1:1
+                    Panic with message:
+                    "wrong sum"
+                foo::ignored: Some(Ignored)
+                foo::nested::nested_test: Some(Passed)
+                foo::passes: Some(Passed)
+                foo::should_panic: Some(Passed)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn interpret_selected_tests() {
+        check_interpret(
+            r#"
+//- /lib.rs crate:foo
+#[test]
+fn outer() {}
+
+mod nested {
+    #[test]
+    fn first() {}
+
+    #[test]
+    fn second() {}
+}
+
+mod nested_other {
+    #[test]
+    fn third() {}
+}
+"#,
+            Some(&["foo::nested", "foo::outer"]),
+            &["foo::nested::second"],
+            expect![[r#"
+                foo::nested::first: Some(Passed)
+                foo::outer: Some(Passed)
+            "#]],
+        );
+    }
+}
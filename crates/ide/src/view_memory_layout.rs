@@ -1,6 +1,6 @@
 use std::fmt;
 
-use hir::{DisplayTarget, Field, HirDisplay, Layout, Semantics, Type};
+use hir::{Adt, DisplayTarget, Enum, Field, HirDisplay, Layout, Semantics, Type};
 use ide_db::{
     defs::Definition,
     helpers::{get_definition, pick_best_token},
@@ -112,7 +112,11 @@ pub(crate) fn view_memory_layout(
         parent_idx: usize,
         display_target: DisplayTarget,
     ) {
-        let mut fields = ty
+        if let Some(Adt::Enum(e)) = ty.as_adt() {
+            read_enum_layout(nodes, db, ty, e, layout, parent_idx, display_target);
+            return;
+        }
+        let fields = ty
             .fields(db)
             .into_iter()
             .map(|(f, ty)| (FieldOrTupleIdx::Field(f), ty))
@@ -123,7 +127,17 @@ pub(crate) fn view_memory_layout(
                     .map(|(i, ty)| (FieldOrTupleIdx::TupleIdx(i), ty)),
             )
             .collect::<Vec<_>>();
+        read_fields_layout(nodes, db, layout, fields, parent_idx, display_target);
+    }
 
+    fn read_fields_layout(
+        nodes: &mut Vec<MemoryLayoutNode>,
+        db: &RootDatabase,
+        layout: &Layout,
+        mut fields: Vec<(FieldOrTupleIdx, Type)>,
+        parent_idx: usize,
+        display_target: DisplayTarget,
+    ) {
         if fields.is_empty() {
             return;
         }
@@ -175,6 +189,85 @@ pub(crate) fn view_memory_layout(
         }
     }
 
+    // Enums get a child for their tag, if they have one, followed by a child for each variant
+    // holding the fields of that variant.
+    fn read_enum_layout(
+        nodes: &mut Vec<MemoryLayoutNode>,
+        db: &RootDatabase,
+        ty: &Type,
+        e: Enum,
+        layout: &Layout,
+        parent_idx: usize,
+        display_target: DisplayTarget,
+    ) {
+        let tag = layout.enum_tag();
+        let variants = e
+            .variants(db)
+            .into_iter()
+            .filter_map(|v| Some((v, layout.enum_variant(db, v)?)))
+            .collect::<Vec<_>>();
+
+        let children_start = nodes.len();
+        nodes[parent_idx].children_start = children_start as i64;
+        nodes[parent_idx].children_len = (variants.len() + tag.is_some() as usize) as u64;
+
+        if let Some(tag) = tag {
+            nodes.push(MemoryLayoutNode {
+                item_name: if tag.is_niche { "[niche]" } else { "[tag]" }.to_owned(),
+                typename: format!("u{}", tag.size * 8),
+                size: tag.size,
+                alignment: tag.align,
+                offset: tag.offset,
+                parent_idx: parent_idx as i64,
+                children_start: -1,
+                children_len: 0,
+            });
+        }
+
+        let enum_name = ty.display(db, display_target).to_string();
+        for (variant, variant_layout) in variants.iter() {
+            let name = variant.name(db);
+            let encoding = match tag {
+                Some(tag) if tag.is_niche => match layout.enum_niche_value(db, *variant) {
+                    Some(value) => format!(" (niche = {value})"),
+                    None => " (untagged)".to_owned(),
+                },
+                Some(_) => match variant.eval(db) {
+                    Ok(discriminant) => format!(" (tag = {discriminant})"),
+                    Err(_) => String::new(),
+                },
+                None => String::new(),
+            };
+            nodes.push(MemoryLayoutNode {
+                item_name: format!("{}{encoding}", name.as_str()),
+                typename: format!("{enum_name}::{}", name.as_str()),
+                size: variant_layout.size(),
+                alignment: variant_layout.align(),
+                offset: 0,
+                parent_idx: parent_idx as i64,
+                children_start: -1,
+                children_len: 0,
+            });
+        }
+
+        let variant_start = children_start + tag.is_some() as usize;
+        for (i, (variant, variant_layout)) in variants.into_iter().enumerate() {
+            let fields = variant
+                .fields(db)
+                .into_iter()
+                .map(|f| (FieldOrTupleIdx::Field(f), f.ty_with_args(db, ty.type_arguments())))
+                .collect();
+            read_fields_layout(
+                nodes,
+                db,
+                &variant_layout,
+                fields,
+                variant_start + i,
+                display_target,
+            );
+        }
+    }
+
     ty.layout(db)
         .map(|layout| {
             let item_name = match def {
@@ -369,6 +462,83 @@ struct Oof {
         );
     }
 
+    #[test]
+    fn view_memory_layout_enum() {
+        expect![[r#"
+            [ROOT]: Message (size: 16, align: 8, field offset: 0)
+            	[tag]: u8 (size: 1, align: 1, field offset: 0)
+            	Ping (tag = 2): Message::Ping (size: 1, align: 1, field offset: 0)
+            	Data (tag = 3): Message::Data (size: 16, align: 8, field offset: 0)
+            		id: u16 (size: 2, align: 2, field offset: 2)
+            		payload: u64 (size: 8, align: 8, field offset: 8)
+            	Close (tag = 4): Message::Close (size: 2, align: 1, field offset: 0)
+            		0: u8 (size: 1, align: 1, field offset: 1)
+        "#]]
+        .assert_eq(
+            &make_memory_layout(
+                r#"
+enum Message$0 {
+    Ping = 2,
+    Data { id: u16, payload: u64 },
+    Close(u8),
+}
+"#,
+            )
+            .unwrap()
+            .to_string(),
+        );
+    }
+
+    #[test]
+    fn view_memory_layout_enum_niche() {
+        expect![[r#"
+            [ROOT]: Packet (size: 2, align: 1, field offset: 0)
+            	[niche]: u8 (size: 1, align: 1, field offset: 0)
+            	Empty (niche = 2): Packet::Empty (size: 0, align: 1, field offset: 0)
+            	Flagged (untagged): Packet::Flagged (size: 2, align: 1, field offset: 0)
+            		0: bool (size: 1, align: 1, field offset: 0)
+            		1: u8 (size: 1, align: 1, field offset: 1)
+            	Closed (niche = 4): Packet::Closed (size: 0, align: 1, field offset: 0)
+        "#]]
+        .assert_eq(
+            &make_memory_layout(
+                r#"
+enum Packet$0 {
+    Empty,
+    Flagged(bool, u8),
+    Closed,
+}
+"#,
+            )
+            .unwrap()
+            .to_string(),
+        );
+    }
+
+    #[test]
+    fn view_memory_layout_generic_enum_field() {
+        expect![[r#"
+            [ROOT]: Wrapper (size: 8, align: 4, field offset: 0)
+            	inner: Option<u32> (size: 8, align: 4, field offset: 0)
+            		[tag]: u32 (size: 4, align: 4, field offset: 0)
+            		None (tag = 0): Option<u32>::None (size: 4, align: 1, field offset: 0)
+            		Some (tag = 1): Option<u32>::Some (size: 8, align: 4, field offset: 0)
+            			0: u32 (size: 4, align: 4, field offset: 4)
+        "#]]
+        .assert_eq(
+            &make_memory_layout(
+                r#"
+//- minicore: option
+struct Wrapper$0 {
+    inner: Option<u32>,
+}
+"#,
+            )
+            .unwrap()
+            .to_string(),
+        );
+    }
+
     #[test]
     fn view_memory_layout_alias() {
         let ml_a = make_memory_layout(
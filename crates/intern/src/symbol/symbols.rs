@@ -424,6 +424,7 @@ define_symbols! {
     rem_assign,
     rem,
     repr,
+    Residual,
     result,
     Result,
     ResumeTy,
@@ -460,6 +461,7 @@ define_symbols! {
     shl,
     shr_assign,
     shr,
+    should_panic,
     simd,
     sized,
     skip,
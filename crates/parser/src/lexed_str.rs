@@ -32,12 +32,31 @@ struct LexError {
 
 impl<'a> LexedStr<'a> {
     pub fn new(edition: Edition, text: &'a str) -> LexedStr<'a> {
+        LexedStr::lex(edition, text, false)
+    }
+
+    /// Lexes the text of a whole source file. Unlike other fragments of Rust code, these may
+    /// start with the frontmatter of a cargo script.
+    pub fn new_source_file(edition: Edition, text: &'a str) -> LexedStr<'a> {
+        LexedStr::lex(edition, text, true)
+    }
+
+    fn lex(edition: Edition, text: &'a str, allow_frontmatter: bool) -> LexedStr<'a> {
         let _p = tracing::info_span!("LexedStr::new").entered();
         let mut conv = Converter::new(edition, text);
         if let Some(shebang_len) = rustc_lexer::strip_shebang(text) {
             conv.res.push(SHEBANG, conv.offset);
             conv.offset = shebang_len;
         };
+        if let Some((ws_len, frontmatter_len, terminated)) =
+            frontmatter(&text[conv.offset..]).filter(|_| allow_frontmatter)
+        {
+            if ws_len != 0 {
+                conv.push(WHITESPACE, ws_len, None);
+            }
+            let err = (!terminated).then_some("Missing closing fence to terminate the frontmatter");
+            conv.push(FRONTMATTER, frontmatter_len, err);
+        }
 
         // Re-create the tokenizer from scratch every token because `GuardedStrPrefix` is one token in the lexer
         // but we want to split it to two in edition <2024.
@@ -129,6 +148,42 @@ impl<'a> LexedStr<'a> {
     }
 }
 
+/// Detects a cargo-script frontmatter block (a manifest fenced by lines of three or more `-`)
+/// at the start of `text`.
+///
+/// Returns the length of the whitespace preceding the opening fence, the length of the block
+/// itself (up to, but excluding, the newline after the closing fence) and whether the block was
+/// closed.
+fn frontmatter(text: &str) -> Option<(usize, usize, bool)> {
+    // Only blank lines may precede the opening fence.
+    let ws_len = text.len() - text.trim_start().len();
+    let ws_len = text[..ws_len].rfind('\n').map_or(0, |it| it + 1);
+    let rest = &text[ws_len..];
+
+    let fence_len = rest.bytes().take_while(|&b| b == b'-').count();
+    if fence_len < 3 {
+        return None;
+    }
+    let fence = &rest[..fence_len];
+
+    // The opening fence may only be followed by an infostring, like `---cargo`.
+    let opening_end = rest.find('\n').unwrap_or(rest.len());
+    let infostring = rest[fence_len..opening_end].trim();
+    if !infostring.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ',')) {
+        return None;
+    }
+
+    let mut line_start = opening_end + 1;
+    while line_start <= rest.len() {
+        let line_end = rest[line_start..].find('\n').map_or(rest.len(), |it| line_start + it);
+        if rest[line_start..line_end].trim_end() == fence {
+            return Some((ws_len, line_end, true));
+        }
+        line_start = line_end + 1;
+    }
+    Some((ws_len, rest.len(), false))
+}
+
 struct Converter<'a> {
     res: LexedStr<'a>,
     offset: usize,
@@ -43,7 +43,7 @@ fn lex_err() {
 }
 
 fn lex(text: &str, edition: Edition) -> String {
-    let lexed = LexedStr::new(edition, text);
+    let lexed = LexedStr::new_source_file(edition, text);
 
     let mut res = String::new();
     for i in 0..lexed.len() {
@@ -78,7 +78,10 @@ fn parse_err() {
 }
 
 fn parse(entry: TopEntryPoint, text: &str, edition: Edition) -> (String, bool) {
-    let lexed = LexedStr::new(edition, text);
+    let lexed = match entry {
+        TopEntryPoint::SourceFile => LexedStr::new_source_file(edition, text),
+        _ => LexedStr::new(edition, text),
+    };
     let input = lexed.to_input(edition);
     let output = entry.parse(&input, edition);
 
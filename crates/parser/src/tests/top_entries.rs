@@ -45,6 +45,24 @@ fn source_file() {
             error 6: expected an item
         "#]],
     );
+
+    check(
+        TopEntryPoint::SourceFile,
+        "#!/usr/bin/env cargo\n---\n[dependencies]\n---\nstruct S;",
+        expect![[r##"
+            SOURCE_FILE
+              SHEBANG "#!/usr/bin/env cargo"
+              WHITESPACE "\n"
+              FRONTMATTER "---\n[dependencies]\n---"
+              WHITESPACE "\n"
+              STRUCT
+                STRUCT_KW "struct"
+                WHITESPACE " "
+                NAME
+                  IDENT "S"
+                SEMICOLON ";"
+        "##]],
+    );
 }
 
 #[test]
@@ -116,6 +134,33 @@ fn macro_items() {
             error 0: expected an item
         "##]],
     );
+    // Only whole source files can start with a frontmatter.
+    check(
+        TopEntryPoint::MacroItems,
+        "---\n---",
+        expect![[r#"
+            MACRO_ITEMS
+              ERROR
+                MINUS "-"
+              ERROR
+                MINUS "-"
+              ERROR
+                MINUS "-"
+              WHITESPACE "\n"
+              ERROR
+                MINUS "-"
+              ERROR
+                MINUS "-"
+              ERROR
+                MINUS "-"
+            error 0: expected an item
+            error 1: expected an item
+            error 2: expected an item
+            error 4: expected an item
+            error 5: expected an item
+            error 6: expected an item
+        "#]],
+    );
     check(
         TopEntryPoint::MacroItems,
         "struct S; foo!{}",
@@ -0,0 +1,7 @@
+#!/usr/bin/env -S cargo +nightly -Zscript
+---cargo
+[dependencies]
+clap = { version = "4.2", features = ["derive"] }
+---
+
+fn main() {}
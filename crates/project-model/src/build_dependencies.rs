@@ -6,7 +6,14 @@
 //! but if enabled we will also use `RUSTC_WRAPPER` to only compile the build scripts and
 //! proc-macros and skip everything else.
 
-use std::{cell::RefCell, io, mem, process::Command};
+use std::{
+    cell::RefCell,
+    fs,
+    hash::{Hash, Hasher},
+    io, mem,
+    process::Command,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
 use base_db::Env;
 use cargo_metadata::{camino::Utf8Path, Message};
@@ -14,8 +21,8 @@ use cfg::CfgAtom;
 use itertools::Itertools;
 use la_arena::ArenaMap;
 use paths::{AbsPath, AbsPathBuf, Utf8PathBuf};
-use rustc_hash::{FxHashMap, FxHashSet};
-use serde::Deserialize as _;
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
+use serde::{Deserialize, Serialize};
 use toolchain::Tool;
 
 use crate::{
@@ -73,7 +80,11 @@ impl WorkspaceBuildScripts {
             current_dir,
             sysroot,
         )?;
-        Self::run_per_ws(cmd, workspace, progress)
+        let res = Self::run_per_ws(cmd, workspace, progress)?;
+        if config.build_scripts_cache {
+            res.store_cache(config, workspace);
+        }
+        Ok(res)
     }
 
     /// Runs the build scripts by invoking the configured command *once*.
@@ -148,6 +159,12 @@ impl WorkspaceBuildScripts {
             }
         }
 
+        if config.build_scripts_cache {
+            for (workspace, res) in workspaces.iter().zip(&res) {
+                res.store_cache(config, workspace);
+            }
+        }
+
         Ok(res)
     }
 
@@ -159,6 +176,96 @@ impl WorkspaceBuildScripts {
         self.outputs.get(idx)
     }
 
+    /// Loads the outputs persisted by a previous session for the packages of `workspace`. Packages
+    /// whose fingerprint changed since then are left without outputs.
+    pub(crate) fn load_cache(config: &CargoConfig, workspace: &CargoWorkspace) -> Self {
+        let path = cache_path(workspace);
+        match Self::read_cache(&path, config, workspace) {
+            Ok(it) => it,
+            Err(e) => {
+                tracing::debug!("failed to load build script outputs from {path}: {e:#}");
+                WorkspaceBuildScripts::default()
+            }
+        }
+    }
+
+    /// Persists the outputs of the packages of `workspace`, to be loaded by the next session
+    /// while the build scripts are rerun.
+    fn store_cache(&self, config: &CargoConfig, workspace: &CargoWorkspace) {
+        let path = cache_path(workspace);
+        if let Err(e) = self.write_cache(&path, config, workspace) {
+            tracing::warn!("failed to store build script outputs in {path}: {e:#}");
+        }
+    }
+
+    fn read_cache(
+        path: &AbsPath,
+        config: &CargoConfig,
+        workspace: &CargoWorkspace,
+    ) -> anyhow::Result<Self> {
+        let cache: BuildScriptCache = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if cache.version != BuildScriptCache::VERSION {
+            anyhow::bail!("unsupported cache version {}", cache.version);
+        }
+
+        let config_fingerprint = config_fingerprint(config);
+        let mut res = WorkspaceBuildScripts::default();
+        for package in workspace.packages() {
+            let cached = has_outputs(workspace, package)
+                .then(|| cache.packages.get(&workspace[package].id))
+                .flatten();
+            let data = cached.and_then(|cached| {
+                if cached.fingerprint != fingerprint(config_fingerprint, workspace, package) {
+                    return None;
+                }
+                cached.to_output()
+            });
+            res.outputs.insert(package, data.unwrap_or_default());
+        }
+        Ok(res)
+    }
+
+    fn write_cache(
+        &self,
+        path: &AbsPath,
+        config: &CargoConfig,
+        workspace: &CargoWorkspace,
+    ) -> anyhow::Result<()> {
+        let config_fingerprint = config_fingerprint(config);
+        let packages = self
+            .outputs
+            .iter()
+            .filter(|&(package, data)| has_outputs(workspace, package) && !data.is_empty())
+            .map(|(package, data)| {
+                let fingerprint = fingerprint(config_fingerprint, workspace, package);
+                (workspace[package].id.clone(), CachedBuildScriptOutput::new(fingerprint, data))
+            })
+            .collect();
+        let cache = BuildScriptCache { version: BuildScriptCache::VERSION, packages };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Write to a temporary file first, other instances might be reading or writing the cache.
+        let contents = serde_json::to_vec(&cache)?;
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let tmp = format!(
+            "{path}.{}-{}.tmp",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let res = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp)
+            .and_then(|mut file| io::Write::write_all(&mut file, &contents))
+            .and_then(|()| fs::rename(&tmp, path));
+        if res.is_err() {
+            _ = fs::remove_file(&tmp);
+        }
+        Ok(res?)
+    }
+
     /// Assembles build script outputs for the rustc crates via `--print target-libdir`.
     pub(crate) fn rustc_crates(
         rustc: &CargoWorkspace,
@@ -417,6 +524,10 @@ impl WorkspaceBuildScripts {
                     cmd.args(["--target", target]);
                 }
 
+                if config.offline {
+                    cmd.arg("--offline");
+                }
+
                 match &config.features {
                     CargoFeatures::All => {
                         cmd.arg("--all-features");
@@ -461,6 +572,115 @@ impl WorkspaceBuildScripts {
     }
 }
 
+/// The build script outputs of a workspace, as persisted between sessions.
+#[derive(Serialize, Deserialize)]
+struct BuildScriptCache {
+    version: u32,
+    /// Outputs keyed by package id.
+    packages: FxHashMap<String, CachedBuildScriptOutput>,
+}
+
+impl BuildScriptCache {
+    const VERSION: u32 = 1;
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedBuildScriptOutput {
+    fingerprint: u64,
+    cfgs: Vec<String>,
+    envs: Vec<(String, String)>,
+    out_dir: Option<Utf8PathBuf>,
+    proc_macro_dylib_path: Option<Utf8PathBuf>,
+}
+
+impl CachedBuildScriptOutput {
+    fn new(fingerprint: u64, data: &BuildScriptOutput) -> Self {
+        CachedBuildScriptOutput {
+            fingerprint,
+            cfgs: data
+                .cfgs
+                .iter()
+                .map(|cfg| match cfg {
+                    CfgAtom::Flag(name) => name.as_str().to_owned(),
+                    CfgAtom::KeyValue { key, value } => format!("{key}=\"{value}\""),
+                })
+                .collect(),
+            envs: data.envs.clone().into(),
+            out_dir: data.out_dir.clone().map(Into::into),
+            proc_macro_dylib_path: data.proc_macro_dylib_path.clone().map(Into::into),
+        }
+    }
+
+    /// Converts the cached output back, unless the files it refers to are gone, e.g. after a
+    /// `cargo clean`.
+    fn to_output(&self) -> Option<BuildScriptOutput> {
+        let path = |path: &Option<Utf8PathBuf>| match path {
+            Some(path) => {
+                let path = AbsPathBuf::try_from(path.clone()).ok()?;
+                fs::metadata(&path).is_ok().then_some(Some(path))
+            }
+            None => Some(None),
+        };
+        Some(BuildScriptOutput {
+            cfgs: self
+                .cfgs
+                .iter()
+                .map(|cfg| crate::parse_cfg(cfg))
+                .collect::<Result<_, _>>()
+                .ok()?,
+            envs: self.envs.iter().cloned().collect(),
+            out_dir: path(&self.out_dir)?,
+            proc_macro_dylib_path: path(&self.proc_macro_dylib_path)?,
+        })
+    }
+}
+
+fn cache_path(workspace: &CargoWorkspace) -> AbsPathBuf {
+    workspace.target_directory().join("rust-analyzer").join("build-scripts.json")
+}
+
+/// Whether the package has a build script or is a proc-macro, the only packages with outputs worth
+/// persisting.
+fn has_outputs(workspace: &CargoWorkspace, package: Package) -> bool {
+    workspace[package].targets.iter().any(|&target| {
+        let kind = workspace[target].kind;
+        kind == TargetKind::BuildScript || kind.is_proc_macro()
+    })
+}
+
+/// Hashes the parts of the config that affect the outputs of all build scripts.
+fn config_fingerprint(config: &CargoConfig) -> u64 {
+    let mut hasher = FxHasher::default();
+    config.all_targets.hash(&mut hasher);
+    config.target.hash(&mut hasher);
+    config.target_dir.hash(&mut hasher);
+    config.extra_args.hash(&mut hasher);
+    config.run_build_script_command.hash(&mut hasher);
+    let mut extra_env = config.extra_env.iter().collect::<Vec<_>>();
+    extra_env.sort();
+    extra_env.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Approximates cargo's fingerprint of a package's build script. Files the build script asks to
+/// be rerun for are not considered, the outputs are refreshed by running the build scripts anyway.
+fn fingerprint(config_fingerprint: u64, workspace: &CargoWorkspace, package: Package) -> u64 {
+    let mut hasher = FxHasher::default();
+    config_fingerprint.hash(&mut hasher);
+    let data = &workspace[package];
+    data.id.hash(&mut hasher);
+    let mut features = data.active_features.clone();
+    features.sort();
+    features.hash(&mut hasher);
+    fs::read(&data.manifest).ok().hash(&mut hasher);
+    for &target in &data.targets {
+        if workspace[target].kind == TargetKind::BuildScript {
+            fs::read(&workspace[target].root).ok().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
 // FIXME: Find a better way to know if it is a dylib.
 fn is_dylib(path: &Utf8Path) -> bool {
     match path.extension().map(|e| e.to_owned().to_lowercase()) {
@@ -468,3 +688,69 @@ fn is_dylib(path: &Utf8Path) -> bool {
         Some(ext) => matches!(ext.as_str(), "dll" | "dylib" | "so"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use cargo_metadata::Metadata;
+    use intern::Symbol;
+
+    use super::*;
+
+    fn hello_world() -> CargoWorkspace {
+        let root = if cfg!(windows) { r#"C:\\ROOT\\"# } else { "/ROOT/" };
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_data/hello-world-metadata.json");
+        let meta = fs::read_to_string(path).unwrap().replace("$ROOT$", root);
+        let meta: Metadata = serde_json::from_str(&meta).unwrap();
+        let manifest_path =
+            ManifestPath::try_from(AbsPathBuf::assert(meta.workspace_root.clone())).unwrap();
+        CargoWorkspace::new(meta, manifest_path, Default::default())
+    }
+
+    #[test]
+    fn cache_round_trip() {
+        let workspace = hello_world();
+        let libc = workspace.packages().find(|&it| workspace[it].name == "libc").unwrap();
+        let dir = AbsPathBuf::assert_utf8(std::env::temp_dir())
+            .join(format!("ra-build-scripts-cache-{}", std::process::id()));
+        let out_dir = dir.join("out");
+        fs::create_dir_all(&out_dir).unwrap();
+        let path = dir.join("build-scripts.json");
+
+        let mut bs = WorkspaceBuildScripts::default();
+        for package in workspace.packages() {
+            bs.outputs.insert(package, BuildScriptOutput::default());
+        }
+        bs.outputs[libc] = BuildScriptOutput {
+            cfgs: vec![
+                CfgAtom::Flag(Symbol::intern("libc_union")),
+                CfgAtom::KeyValue { key: Symbol::intern("freebsd"), value: Symbol::intern("11") },
+            ],
+            envs: [("OUT_DIR".to_owned(), out_dir.to_string())].into_iter().collect(),
+            out_dir: Some(out_dir.clone()),
+            proc_macro_dylib_path: None,
+        };
+        // Packages without build scripts and proc-macros are not persisted.
+        let hello_world =
+            workspace.packages().find(|&it| workspace[it].name == "hello-world").unwrap();
+        bs.outputs[hello_world].cfgs = vec![CfgAtom::Flag(Symbol::intern("hello"))];
+        let config = CargoConfig::default();
+        bs.write_cache(&path, &config, &workspace).unwrap();
+
+        let loaded = WorkspaceBuildScripts::read_cache(&path, &config, &workspace).unwrap();
+        bs.outputs[hello_world] = BuildScriptOutput::default();
+        assert_eq!(loaded, bs);
+
+        // A different target invalidates the outputs of all packages.
+        let other_target =
+            CargoConfig { target: Some("wasm32-unknown-unknown".to_owned()), ..config.clone() };
+        let loaded = WorkspaceBuildScripts::read_cache(&path, &other_target, &workspace).unwrap();
+        assert!(loaded.outputs.iter().all(|(_, it)| it.is_empty()));
+
+        // Outputs referring to files that are gone are dropped as well.
+        fs::remove_dir(&out_dir).unwrap();
+        let loaded = WorkspaceBuildScripts::read_cache(&path, &config, &workspace).unwrap();
+        assert!(loaded.outputs[libc].is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
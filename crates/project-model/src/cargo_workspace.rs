@@ -14,8 +14,8 @@ use serde_json::from_value;
 use span::Edition;
 use toolchain::Tool;
 
+use crate::{vendored, ManifestPath, Sysroot};
 use crate::{CfgOverrides, InvocationStrategy};
-use crate::{ManifestPath, Sysroot};
 
 /// [`CargoWorkspace`] represents the logical structure of, well, a Cargo
 /// workspace. It pretty closely mirrors `cargo metadata` output.
@@ -90,6 +90,9 @@ pub struct CargoConfig {
     /// Sysroot loading behavior
     pub sysroot: Option<RustLibSource>,
     pub sysroot_src: Option<AbsPathBuf>,
+    /// A `rust-project.json`-style file describing the sysroot crates, used instead of the crates
+    /// found in `sysroot_src`.
+    pub sysroot_project: Option<ManifestPath>,
     /// rustc private crate source
     pub rustc_source: Option<RustLibSource>,
     /// Extra includes to add to the VFS.
@@ -99,10 +102,16 @@ pub struct CargoConfig {
     pub wrap_rustc_in_build_scripts: bool,
     /// The command to run instead of `cargo check` for building build scripts.
     pub run_build_script_command: Option<Vec<String>>,
+    /// Persist the build script outputs in the target directory, and start out with the outputs
+    /// of the previous session when loading a workspace.
+    pub build_scripts_cache: bool,
     /// Extra args to pass to the cargo command.
     pub extra_args: Vec<String>,
     /// Extra env vars to set when invoking the cargo command
     pub extra_env: FxHashMap<String, String>,
+    /// Run cargo without accessing the network, resolving dependencies from `Cargo.lock` and
+    /// vendored sources if `cargo metadata` fails.
+    pub offline: bool,
     pub invocation_strategy: InvocationStrategy,
     /// Optional path to use instead of `target` when building
     pub target_dir: Option<Utf8PathBuf>,
@@ -229,7 +238,7 @@ pub enum TargetKind {
 }
 
 impl TargetKind {
-    fn new(kinds: &[String]) -> TargetKind {
+    pub(crate) fn new(kinds: &[String]) -> TargetKind {
         for kind in kinds {
             return match kind.as_str() {
                 "bin" => TargetKind::Bin,
@@ -264,6 +273,8 @@ pub struct CargoMetadataConfig {
     pub extra_args: Vec<String>,
     /// Extra env vars to set when invoking the cargo command
     pub extra_env: FxHashMap<String, String>,
+    /// Whether to run cargo without accessing the network.
+    pub offline: bool,
 }
 
 // Deserialize helper for the cargo metadata
@@ -362,8 +373,11 @@ impl CargoWorkspace {
             // opt into it themselves.
             other_options.push("-Zscript".to_owned());
         }
-        if locked {
-            other_options.push("--locked".to_owned());
+        match (locked, config.offline) {
+            (true, true) => other_options.push("--frozen".to_owned()),
+            (true, false) => other_options.push("--locked".to_owned()),
+            (false, true) => other_options.push("--offline".to_owned()),
+            (false, false) => (),
         }
         if no_deps {
             other_options.push("--no-deps".to_owned());
@@ -394,6 +408,22 @@ impl CargoWorkspace {
                         true,
                         progress,
                     ) {
+                        if !config.offline {
+                            return Ok((metadata, Some(error)));
+                        }
+                        // Offline, the dependencies may still be available as vendored sources.
+                        let metadata = match vendored::resolve_vendored_dependencies(
+                            &metadata,
+                            cargo_toml,
+                            config,
+                            sysroot,
+                        ) {
+                            Ok(resolved) => resolved,
+                            Err(e) => {
+                                tracing::warn!(%cargo_toml, ?e, "failed to resolve vendored dependencies");
+                                metadata
+                            }
+                        };
                         return Ok((metadata, Some(error)));
                     }
                 }
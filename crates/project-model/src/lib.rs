@@ -19,6 +19,7 @@ pub mod project_json;
 pub mod toolchain_info {
     pub mod rustc_cfg;
     pub mod target_data_layout;
+    mod target_spec;
     pub mod target_tuple;
     pub mod version;
 
@@ -34,6 +35,17 @@ pub mod toolchain_info {
         /// If this fails, falls back to invoking `rustc` directly.
         Cargo(&'a Sysroot, &'a ManifestPath),
     }
+
+    impl QueryConfig<'_> {
+        /// The directory the queries run in, relative target specification paths are resolved
+        /// against it.
+        fn current_dir(&self) -> &Path {
+            match self {
+                QueryConfig::Rustc(_, current_dir) => current_dir,
+                QueryConfig::Cargo(_, cargo_toml) => cargo_toml.parent().as_ref(),
+            }
+        }
+    }
 }
 
 mod build_dependencies;
@@ -41,6 +53,7 @@ mod cargo_workspace;
 mod env;
 mod manifest_path;
 mod sysroot;
+mod vendored;
 mod workspace;
 
 #[cfg(test)]
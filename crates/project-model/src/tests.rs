@@ -2,7 +2,7 @@ use base_db::{CrateGraphBuilder, ProcMacroPaths};
 use cargo_metadata::Metadata;
 use cfg::{CfgAtom, CfgDiff};
 use expect_test::{expect_file, ExpectFile};
-use intern::sym;
+use intern::{sym, Symbol};
 use paths::{AbsPath, AbsPathBuf, Utf8Path, Utf8PathBuf};
 use rustc_hash::FxHashMap;
 use serde::de::DeserializeOwned;
@@ -10,9 +10,11 @@ use span::FileId;
 use triomphe::Arc;
 
 use crate::{
-    sysroot::RustLibSrcWorkspace, workspace::ProjectWorkspaceKind, CargoWorkspace, CfgOverrides,
-    ManifestPath, ProjectJson, ProjectJsonData, ProjectWorkspace, RustSourceWorkspaceConfig,
-    Sysroot, WorkspaceBuildScripts,
+    sysroot::RustLibSrcWorkspace,
+    workspace::ProjectWorkspaceKind,
+    workspace::{load_sysroot_project, sysroot_source_config},
+    CargoWorkspace, CfgOverrides, ManifestPath, ProjectJson, ProjectJsonData, ProjectWorkspace,
+    RustSourceWorkspaceConfig, Sysroot, WorkspaceBuildScripts,
 };
 
 fn load_cargo(file: &str) -> (CrateGraphBuilder, ProcMacroPaths) {
@@ -291,3 +293,101 @@ fn smoke_test_real_sysroot_cargo() {
         &Default::default(),
     );
 }
+
+#[test]
+fn crate_graph_ad_hoc_crate() {
+    let mut file_map = Default::default();
+    let mut root = "$ROOT$".to_owned();
+    replace_root(&mut root, true);
+    let file = ManifestPath::try_from(
+        AbsPathBuf::assert(Utf8PathBuf::from(root)).join("hello-world/src/scratch.rs"),
+    )
+    .unwrap();
+
+    let workspace = load_workspace_from_metadata("hello-world-metadata.json");
+    let add_ad_hoc_crate =
+        |crate_graph: &mut CrateGraphBuilder,
+         package_deps,
+         file_map: &mut FxHashMap<AbsPathBuf, FileId>| {
+            workspace
+                .add_ad_hoc_crate(crate_graph, &file, package_deps, &mut |path| {
+                    let len = file_map.len() + 1;
+                    Some(
+                        *file_map.entry(path.to_path_buf()).or_insert(FileId::from_raw(len as u32)),
+                    )
+                })
+                .unwrap()
+        };
+    let dependencies = |crate_graph: &CrateGraphBuilder, krate| {
+        crate_graph[krate]
+            .basic
+            .dependencies
+            .iter()
+            .map(|dep| dep.name.to_string())
+            .collect::<Vec<_>>()
+    };
+    let (crate_graph, _proc_macros) = to_crate_graph(workspace.clone(), &mut file_map);
+    let n_crates = crate_graph.iter().count();
+
+    let mut with_package_deps = crate_graph.clone();
+    let ad_hoc_crate = add_ad_hoc_crate(&mut with_package_deps, true, &mut file_map);
+    assert_eq!(with_package_deps.iter().count(), n_crates + 1);
+    assert_eq!(with_package_deps[ad_hoc_crate].basic.root_file_id, file_map[&*file]);
+    assert_eq!(dependencies(&with_package_deps, ad_hoc_crate), ["hello_world", "libc"]);
+
+    let mut without_package_deps = crate_graph;
+    let ad_hoc_crate = add_ad_hoc_crate(&mut without_package_deps, false, &mut file_map);
+    assert_eq!(without_package_deps.iter().count(), n_crates + 1);
+    assert!(dependencies(&without_package_deps, ad_hoc_crate).is_empty());
+}
+
+#[test]
+fn custom_sysroot_project() {
+    let sysroot_project = ManifestPath::try_from(AbsPathBuf::assert(get_test_path(
+        "custom-sysroot/sysroot-project.json",
+    )))
+    .unwrap();
+    let sysroot_project = load_sysroot_project(&sysroot_project).unwrap();
+    let mut sysroot = Sysroot::new(None, Some(sysroot_project.project_root().to_path_buf()));
+    let loaded_sysroot = sysroot.load_workspace(&sysroot_source_config(
+        Some(sysroot_project),
+        &Default::default(),
+        &[],
+    ));
+    sysroot.set_workspace(loaded_sysroot.unwrap());
+    assert_eq!(sysroot.error(), None);
+    assert_eq!(sysroot.num_packages(), 2);
+
+    let workspace =
+        ProjectWorkspace { sysroot, ..load_workspace_from_metadata("hello-world-metadata.json") };
+    let (crate_graph, _proc_macros) = to_crate_graph(workspace, &mut Default::default());
+    let krate = |name: &str| {
+        crate_graph
+            .iter()
+            .find(|&krate| {
+                crate_graph[krate]
+                    .extra
+                    .display_name
+                    .as_ref()
+                    .is_some_and(|it| it.to_string() == name)
+            })
+            .unwrap()
+    };
+    let cfg_options = &crate_graph[krate("core")].cfg_options;
+    let key_value = |key: &str, value: &str| CfgAtom::KeyValue {
+        key: Symbol::intern(key),
+        value: Symbol::intern(value),
+    };
+    assert!(cfg_options.check_atom(&key_value("feature", "patched")), "{cfg_options:?}");
+    assert!(cfg_options.check_atom(&key_value("target_arch", "arm")), "{cfg_options:?}");
+    assert!(crate_graph[krate("alloc")]
+        .basic
+        .dependencies
+        .iter()
+        .any(|dep| dep.name.as_str() == "core"));
+    assert!(crate_graph[krate("hello_world")]
+        .basic
+        .dependencies
+        .iter()
+        .any(|dep| dep.name.as_str() == "core"));
+}
@@ -5,9 +5,13 @@ use cfg::CfgAtom;
 use rustc_hash::FxHashMap;
 use toolchain::Tool;
 
-use crate::{toolchain_info::QueryConfig, utf8_stdout};
+use crate::{
+    toolchain_info::{target_spec::TargetSpec, QueryConfig},
+    utf8_stdout,
+};
 
-/// Uses `rustc --print cfg` to fetch the builtin cfgs.
+/// Uses `rustc --print cfg` to fetch the builtin cfgs, or reads them from the target
+/// specification if `target` is a path to one.
 pub fn get(
     config: QueryConfig<'_>,
     target: Option<&str>,
@@ -62,6 +66,9 @@ fn rustc_print_cfg(
     config: QueryConfig<'_>,
 ) -> anyhow::Result<String> {
     const RUSTC_ARGS: [&str; 2] = ["--print", "cfg"];
+    if let Some(spec) = target.and_then(|target| TargetSpec::load(target, config.current_dir())) {
+        return spec.map(|spec| spec.print_cfg());
+    }
     let (sysroot, current_dir) = match config {
         QueryConfig::Cargo(sysroot, cargo_toml) => {
             let mut cmd = sysroot.tool(Tool::Cargo, cargo_toml.parent());
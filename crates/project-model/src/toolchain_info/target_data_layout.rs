@@ -4,9 +4,13 @@ use anyhow::Context;
 use rustc_hash::FxHashMap;
 use toolchain::Tool;
 
-use crate::{toolchain_info::QueryConfig, utf8_stdout, Sysroot};
+use crate::{
+    toolchain_info::{target_spec::TargetSpec, QueryConfig},
+    utf8_stdout, Sysroot,
+};
 
-/// Uses `rustc --print target-spec-json`.
+/// Uses `rustc --print target-spec-json`, or reads the target specification directly if `target`
+/// is a path to one.
 pub fn get(
     config: QueryConfig<'_>,
     target: Option<&str>,
@@ -19,6 +23,9 @@ pub fn get(
                 anyhow::format_err!("could not parse target-spec-json from command output")
             })
     };
+    if let Some(spec) = target.and_then(|target| TargetSpec::load(target, config.current_dir())) {
+        return spec.and_then(|spec| spec.data_layout());
+    }
     let (sysroot, current_dir) = match config {
         QueryConfig::Cargo(sysroot, cargo_toml) => {
             let mut cmd = sysroot.tool(Tool::Cargo, cargo_toml.parent());
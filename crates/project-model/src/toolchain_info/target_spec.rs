@@ -0,0 +1,181 @@
+//! Reads custom target specification files, as accepted by `--target path/to/target.json`.
+//!
+//! Targets like these are commonly used with `-Zbuild-std` for embedded platforms. As the
+//! specification contains everything we need, we read the data layout and the cfgs from the file
+//! instead of asking `rustc`, which may not even be able to handle the target on a stable
+//! toolchain.
+
+use std::{fs, path::Path};
+
+use anyhow::Context;
+use serde_json::{Map, Value};
+use stdx::format_to;
+
+pub(crate) struct TargetSpec {
+    fields: Map<String, Value>,
+}
+
+impl TargetSpec {
+    /// Loads the target specification if `target` is a path to one. Relative paths are resolved
+    /// against `current_dir`, just like `rustc` would.
+    pub(crate) fn load(target: &str, current_dir: &Path) -> Option<anyhow::Result<TargetSpec>> {
+        if !target.ends_with(".json") {
+            return None;
+        }
+        let path = current_dir.join(target);
+        let load = || {
+            let text = fs::read_to_string(&path)
+                .with_context(|| format!("unable to read target specification {path:?}"))?;
+            let fields = serde_json::from_str(&text)
+                .with_context(|| format!("unable to parse target specification {path:?}"))?;
+            Ok(TargetSpec { fields })
+        };
+        Some(load())
+    }
+
+    pub(crate) fn data_layout(&self) -> anyhow::Result<String> {
+        self.str("data-layout")
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| anyhow::format_err!("target specification has no `data-layout`"))
+    }
+
+    /// Renders the cfgs of the target the way `rustc --print cfg` would.
+    ///
+    /// Target features implied by the listed ones are not known to us, so only the explicitly
+    /// enabled features are reported.
+    pub(crate) fn print_cfg(&self) -> String {
+        let mut res = String::new();
+        let pointer_width = self.int("target-pointer-width").unwrap_or(64);
+
+        format_to!(res, "panic=\"{}\"\n", self.str("panic-strategy").unwrap_or("unwind"));
+        if let Some(abi) = self.str("abi") {
+            format_to!(res, "target_abi=\"{abi}\"\n");
+        }
+        if let Some(arch) = self.str("arch") {
+            format_to!(res, "target_arch=\"{arch}\"\n");
+        }
+        format_to!(res, "target_endian=\"{}\"\n", self.str("target-endian").unwrap_or("little"));
+        format_to!(res, "target_env=\"{}\"\n", self.str("env").unwrap_or_default());
+        let families = self.fields.get("target-family").and_then(Value::as_array);
+        for family in families.into_iter().flatten().filter_map(Value::as_str) {
+            format_to!(res, "target_family=\"{family}\"\n");
+            if let "unix" | "windows" = family {
+                format_to!(res, "{family}\n");
+            }
+        }
+        let features = self.str("features").unwrap_or_default().split(',');
+        for feature in features.filter_map(|it| it.strip_prefix('+')) {
+            format_to!(res, "target_feature=\"{feature}\"\n");
+        }
+        let min_atomic_width = self.int("min-atomic-width").unwrap_or(8);
+        let max_atomic_width = self.int("max-atomic-width").unwrap_or(pointer_width);
+        for width in [8, 16, 32, 64, 128] {
+            if (min_atomic_width..=max_atomic_width).contains(&width) {
+                format_to!(res, "target_has_atomic=\"{width}\"\n");
+            }
+        }
+        if (min_atomic_width..=max_atomic_width).contains(&pointer_width) {
+            res.push_str("target_has_atomic=\"ptr\"\n");
+        }
+        format_to!(res, "target_os=\"{}\"\n", self.str("os").unwrap_or("none"));
+        format_to!(res, "target_pointer_width=\"{pointer_width}\"\n");
+        format_to!(res, "target_vendor=\"{}\"\n", self.str("vendor").unwrap_or("unknown"));
+        res
+    }
+
+    fn str(&self, field: &str) -> Option<&str> {
+        self.fields.get(field)?.as_str()
+    }
+
+    /// Some integer fields used to be strings in older specifications, accept both.
+    fn int(&self, field: &str) -> Option<u64> {
+        match self.fields.get(field)? {
+            Value::Number(it) => it.as_u64(),
+            Value::String(it) => it.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+
+    fn spec(json: &str) -> TargetSpec {
+        TargetSpec { fields: serde_json::from_str(json).unwrap() }
+    }
+
+    #[test]
+    fn bare_metal() {
+        let spec = spec(
+            r#"{
+                "abi": "eabihf",
+                "arch": "arm",
+                "data-layout": "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64",
+                "features": "+vfp4d16sp,-neon",
+                "llvm-target": "thumbv7em-none-eabihf",
+                "max-atomic-width": 32,
+                "panic-strategy": "abort",
+                "target-pointer-width": "32"
+            }"#,
+        );
+        assert_eq!(
+            spec.data_layout().unwrap(),
+            "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64"
+        );
+        expect![[r#"
+            panic="abort"
+            target_abi="eabihf"
+            target_arch="arm"
+            target_endian="little"
+            target_env=""
+            target_feature="vfp4d16sp"
+            target_has_atomic="8"
+            target_has_atomic="16"
+            target_has_atomic="32"
+            target_has_atomic="ptr"
+            target_os="none"
+            target_pointer_width="32"
+            target_vendor="unknown"
+        "#]]
+        .assert_eq(&spec.print_cfg());
+    }
+
+    #[test]
+    fn hosted() {
+        let spec = spec(
+            r#"{
+                "arch": "x86_64",
+                "env": "gnu",
+                "os": "linux",
+                "target-family": ["unix"],
+                "target-pointer-width": 64
+            }"#,
+        );
+        assert!(spec.data_layout().is_err());
+        expect![[r#"
+            panic="unwind"
+            target_arch="x86_64"
+            target_endian="little"
+            target_env="gnu"
+            target_family="unix"
+            unix
+            target_has_atomic="8"
+            target_has_atomic="16"
+            target_has_atomic="32"
+            target_has_atomic="64"
+            target_has_atomic="ptr"
+            target_os="linux"
+            target_pointer_width="64"
+            target_vendor="unknown"
+        "#]]
+        .assert_eq(&spec.print_cfg());
+    }
+
+    #[test]
+    fn builtin_target_is_not_a_spec() {
+        assert!(TargetSpec::load("thumbv7em-none-eabihf", Path::new("/")).is_none());
+    }
+}
@@ -0,0 +1,741 @@
+//! Resolves a workspace from `Cargo.lock` and vendored sources, for when `cargo metadata` can't.
+//!
+//! In offline mode, `cargo metadata` fails as soon as the registry index is unavailable, even if
+//! all dependencies are vendored through `.cargo/config.toml`'s source replacement. In that case,
+//! we take the `--no-deps` metadata of the workspace and add the locked packages found in the
+//! vendored directories ourselves.
+//!
+//! The result is best-effort: packages that aren't vendored are missing, target-specific
+//! dependencies are not filtered by platform and only the default features plus the ones
+//! requested by dependents are enabled.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
+};
+
+use anyhow::Context;
+use cargo_metadata::{DependencyKind, Metadata};
+use paths::{AbsPath, AbsPathBuf};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use toolchain::Tool;
+
+use crate::{utf8_stdout, CargoFeatures, CargoMetadataConfig, ManifestPath, Sysroot, TargetKind};
+
+const CRATES_IO: &str = "https://github.com/rust-lang/crates.io-index";
+const CRATES_IO_SPARSE: &str = "https://index.crates.io/";
+
+/// Extends the `--no-deps` metadata `meta` with the locked dependencies of the workspace that are
+/// available as vendored sources.
+pub(crate) fn resolve_vendored_dependencies(
+    meta: &Metadata,
+    cargo_toml: &ManifestPath,
+    config: &CargoMetadataConfig,
+    sysroot: &Sysroot,
+) -> anyhow::Result<Metadata> {
+    let lockfile = AbsPathBuf::assert(meta.workspace_root.join("Cargo.lock"));
+    let lockfile =
+        fs::read_to_string(&lockfile).with_context(|| format!("Failed to read {lockfile}"))?;
+    let directories = vendored_directories(cargo_toml, &config.extra_env, sysroot);
+    if directories.is_empty() {
+        anyhow::bail!("No vendored sources are configured for {cargo_toml}");
+    }
+    resolve(meta, &lockfile, &directories, &config.features)
+}
+
+/// The parts of `Cargo.lock` we need.
+#[derive(Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// The parts of the normalized manifest of a vendored package we need.
+#[derive(Deserialize)]
+struct Manifest {
+    package: ManifestPackage,
+    lib: Option<ManifestLib>,
+    #[serde(default)]
+    features: FxHashMap<String, Vec<String>>,
+    #[serde(flatten)]
+    dependencies: DependencyTables,
+    #[serde(default)]
+    target: BTreeMap<String, DependencyTables>,
+}
+
+#[derive(Deserialize)]
+struct ManifestPackage {
+    version: String,
+    edition: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ManifestLib {
+    name: Option<String>,
+    path: Option<String>,
+    #[serde(default, alias = "proc_macro")]
+    proc_macro: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct DependencyTables {
+    #[serde(default)]
+    dependencies: BTreeMap<String, ManifestDependency>,
+    #[serde(default, alias = "dev_dependencies")]
+    dev_dependencies: BTreeMap<String, ManifestDependency>,
+    #[serde(default, alias = "build_dependencies")]
+    build_dependencies: BTreeMap<String, ManifestDependency>,
+}
+
+/// `name = "version"` or `name = { version = "1", package = "other" }`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ManifestDependency {
+    #[allow(dead_code)]
+    Version(String),
+    Detailed(DetailedDependency),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct DetailedDependency {
+    package: Option<String>,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(alias = "default_features")]
+    default_features: Option<bool>,
+}
+
+fn resolve(
+    meta: &Metadata,
+    lockfile: &str,
+    directories: &[(String, AbsPathBuf)],
+    features: &CargoFeatures,
+) -> anyhow::Result<Metadata> {
+    let mut packages: Vec<Package> = Vec::new();
+    let mut locked_deps: Vec<Vec<String>> = Vec::new();
+    let lockfile: Lockfile = toml::from_str(lockfile).context("Failed to parse Cargo.lock")?;
+    for locked in lockfile.package {
+        let (name, version) = (&*locked.name, &*locked.version);
+        let package = match locked.source.as_deref() {
+            None => meta
+                .packages
+                .iter()
+                .find(|it| it.name == name && it.version.to_string() == version)
+                .map(|it| Package::member(it, features)),
+            Some(source) => {
+                let url = source_url(source);
+                directories
+                    .iter()
+                    .filter(|(source, _)| *source == url)
+                    .find_map(|(_, dir)| Package::vendored(dir, source, name, version))
+            }
+        };
+        if let Some(package) = package {
+            packages.push(package);
+            locked_deps.push(locked.dependencies);
+        }
+    }
+
+    // Resolve the locked dependencies, which are given as `name`, `name version` or
+    // `name version (source)`, whatever is needed to be unambiguous.
+    let resolved_deps: Vec<Vec<usize>> = locked_deps
+        .iter()
+        .map(|deps| {
+            deps.iter()
+                .filter_map(|dep| {
+                    let mut parts = dep.split(' ');
+                    let name = parts.next()?;
+                    let version = parts.next();
+                    packages.iter().position(|it| {
+                        it.name == name && version.is_none_or(|version| it.version == version)
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    enable_features(&mut packages, &resolved_deps);
+
+    let nodes: Vec<Value> = packages
+        .iter()
+        .zip(&resolved_deps)
+        .map(|(package, resolved)| {
+            let deps: Vec<Value> = resolved
+                .iter()
+                .map(|&dep| {
+                    let dep = &packages[dep];
+                    let declared: Vec<&Dependency> =
+                        package.deps.iter().filter(|it| it.package == dep.name).collect();
+                    let name = declared
+                        .iter()
+                        .find_map(|it| it.rename.clone())
+                        .unwrap_or_else(|| dep.lib_name.clone());
+                    let mut kinds: Vec<Option<&str>> = declared
+                        .iter()
+                        .map(|it| match it.kind {
+                            DependencyKind::Development => Some("dev"),
+                            DependencyKind::Build => Some("build"),
+                            _ => None,
+                        })
+                        .collect();
+                    kinds.sort();
+                    kinds.dedup();
+                    if kinds.is_empty() {
+                        kinds.push(None);
+                    }
+                    let kinds: Vec<Value> = kinds
+                        .into_iter()
+                        .map(|kind| json!({ "kind": kind, "target": null }))
+                        .collect();
+                    json!({ "name": name, "pkg": dep.id, "dep_kinds": kinds })
+                })
+                .collect();
+            let dependencies: Vec<&str> =
+                resolved.iter().map(|&dep| packages[dep].id.as_str()).collect();
+            let mut features: Vec<&String> = package.enabled_features.iter().collect();
+            features.sort();
+            json!({
+                "id": package.id,
+                "deps": deps,
+                "dependencies": dependencies,
+                "features": features,
+            })
+        })
+        .collect();
+
+    let mut meta = serde_json::to_value(meta)?;
+    let synthesized = packages.into_iter().filter_map(|package| package.json);
+    if let Some(Value::Array(packages)) = meta.get_mut("packages") {
+        packages.extend(synthesized);
+    }
+    meta["resolve"] = json!({ "nodes": nodes, "root": null });
+    serde_json::from_value(meta).context("Failed to synthesize metadata")
+}
+
+/// Propagates enabled features through the feature tables and to dependencies until nothing
+/// changes anymore.
+fn enable_features(packages: &mut [Package], resolved_deps: &[Vec<usize>]) {
+    let mut queue: VecDeque<usize> = (0..packages.len()).collect();
+    while let Some(idx) = queue.pop_front() {
+        let mut requested: Vec<(usize, String)> = Vec::new();
+        let mut enabled = FxHashSet::default();
+        let mut stack: Vec<String> = packages[idx].enabled_features.iter().cloned().collect();
+        while let Some(feature) = stack.pop() {
+            if !enabled.insert(feature.clone()) {
+                continue;
+            }
+            for value in packages[idx].features.get(&feature).into_iter().flatten() {
+                match value.split_once('/') {
+                    Some((dep, dep_feature)) => {
+                        let dep = dep.trim_end_matches('?');
+                        let dep = packages[idx]
+                            .deps
+                            .iter()
+                            .find(|it| {
+                                it.rename.as_deref().unwrap_or(&it.package).replace('-', "_")
+                                    == dep.replace('-', "_")
+                            })
+                            .map_or(dep, |it| &it.package);
+                        requested.extend(
+                            resolved_deps[idx]
+                                .iter()
+                                .filter(|&&it| packages[it].name == dep)
+                                .map(|&it| (it, dep_feature.to_owned())),
+                        );
+                    }
+                    None if value.starts_with("dep:") => (),
+                    None => stack.push(value.clone()),
+                }
+            }
+        }
+        packages[idx].enabled_features = enabled;
+
+        for &dep_idx in &resolved_deps[idx] {
+            for dep in packages[idx].deps.iter().filter(|it| it.package == packages[dep_idx].name) {
+                requested.extend(dep.features.iter().map(|it| (dep_idx, it.clone())));
+                if dep.default_features {
+                    requested.push((dep_idx, "default".to_owned()));
+                }
+            }
+        }
+        for (dep_idx, feature) in requested {
+            let dep = &mut packages[dep_idx];
+            if dep.features.contains_key(&feature) && dep.enabled_features.insert(feature) {
+                queue.push_back(dep_idx);
+            }
+        }
+    }
+}
+
+struct Package {
+    id: String,
+    name: String,
+    version: String,
+    lib_name: String,
+    deps: Vec<Dependency>,
+    features: FxHashMap<String, Vec<String>>,
+    enabled_features: FxHashSet<String>,
+    /// The metadata of the package, for the ones we synthesized.
+    json: Option<Value>,
+}
+
+struct Dependency {
+    package: String,
+    rename: Option<String>,
+    kind: DependencyKind,
+    features: Vec<String>,
+    default_features: bool,
+}
+
+impl Package {
+    fn member(package: &cargo_metadata::Package, features: &CargoFeatures) -> Package {
+        let enabled_features = match features {
+            CargoFeatures::All => package.features.keys().cloned().collect(),
+            CargoFeatures::Selected { features, no_default_features } => features
+                .iter()
+                .cloned()
+                .chain((!no_default_features).then(|| "default".to_owned()))
+                .filter(|it| package.features.contains_key(it))
+                .collect(),
+        };
+        Package {
+            id: package.id.repr.clone(),
+            name: package.name.clone(),
+            version: package.version.to_string(),
+            lib_name: package
+                .targets
+                .iter()
+                .find(|it| matches!(TargetKind::new(&it.kind), TargetKind::Lib { .. }))
+                .map_or_else(|| package.name.replace('-', "_"), |it| it.name.replace('-', "_")),
+            deps: package
+                .dependencies
+                .iter()
+                .map(|dep| Dependency {
+                    package: dep.name.clone(),
+                    rename: dep.rename.clone(),
+                    kind: dep.kind,
+                    features: dep.features.clone(),
+                    default_features: dep.uses_default_features,
+                })
+                .collect(),
+            features: package.features.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            enabled_features,
+            json: None,
+        }
+    }
+
+    /// Looks for the package in a directory created by `cargo vendor`, which places it in
+    /// `name-version` if multiple versions are vendored and in `name` otherwise.
+    fn vendored(dir: &AbsPath, source: &str, name: &str, version: &str) -> Option<Package> {
+        let (root, manifest) = [dir.join(format!("{name}-{version}")), dir.join(name)]
+            .into_iter()
+            .find_map(|root| {
+                let manifest: Manifest =
+                    toml::from_str(&fs::read_to_string(root.join("Cargo.toml")).ok()?).ok()?;
+                (manifest.package.version == version).then_some((root, manifest))
+            })?;
+        let edition = manifest.package.edition.as_deref().unwrap_or("2015");
+
+        let lib = manifest.lib.as_ref();
+        let lib_name = lib.and_then(|it| it.name.clone()).unwrap_or_else(|| name.replace('-', "_"));
+        let lib_path = root.join(lib.and_then(|it| it.path.as_deref()).unwrap_or("src/lib.rs"));
+        let lib_kind = if lib.is_some_and(|it| it.proc_macro) { "proc-macro" } else { "lib" };
+        let targets = if fs::metadata(&lib_path).is_ok() {
+            vec![json!({
+                "name": lib_name,
+                "kind": [lib_kind],
+                "crate_types": [lib_kind],
+                "src_path": lib_path.as_str(),
+                "edition": edition,
+            })]
+        } else {
+            vec![]
+        };
+
+        let deps = dependencies(&manifest);
+        let features = manifest.features;
+
+        let id = format!("{source}#{name}@{version}");
+        let json = json!({
+            "name": name,
+            "version": version,
+            "id": id,
+            "source": source,
+            "dependencies": [],
+            "targets": targets,
+            "features": features,
+            "manifest_path": root.join("Cargo.toml").as_str(),
+            "edition": edition,
+        });
+        Some(Package {
+            id,
+            name: name.to_owned(),
+            version: version.to_owned(),
+            lib_name,
+            deps,
+            features,
+            enabled_features: FxHashSet::default(),
+            json: Some(json),
+        })
+    }
+}
+
+/// The dependencies declared in a manifest, including the platform-specific ones.
+fn dependencies(manifest: &Manifest) -> Vec<Dependency> {
+    let tables = std::iter::once(&manifest.dependencies).chain(manifest.target.values());
+    tables
+        .flat_map(|tables| {
+            [
+                (DependencyKind::Normal, &tables.dependencies),
+                (DependencyKind::Development, &tables.dev_dependencies),
+                (DependencyKind::Build, &tables.build_dependencies),
+            ]
+        })
+        .flat_map(|(kind, deps)| deps.iter().map(move |(name, dep)| (kind, name, dep)))
+        .map(|(kind, name, dep)| {
+            let detailed = match dep {
+                ManifestDependency::Version(_) => None,
+                ManifestDependency::Detailed(it) => Some(it),
+            };
+            let package =
+                detailed.and_then(|it| it.package.clone()).unwrap_or_else(|| name.clone());
+            Dependency {
+                rename: (package != *name).then(|| name.replace('-', "_")),
+                package,
+                kind,
+                features: detailed.map(|it| it.features.clone()).unwrap_or_default(),
+                default_features: detailed.and_then(|it| it.default_features).unwrap_or(true),
+            }
+        })
+        .collect()
+}
+
+/// Maps the source urls that are replaced by vendored directories to those directories, by
+/// querying the `source` configuration of cargo.
+fn vendored_directories(
+    cargo_toml: &ManifestPath,
+    extra_env: &FxHashMap<String, String>,
+    sysroot: &Sysroot,
+) -> Vec<(String, AbsPathBuf)> {
+    let mut cargo_config = sysroot.tool(Tool::Cargo, cargo_toml.parent());
+    cargo_config.envs(extra_env);
+    cargo_config
+        .args(["-Z", "unstable-options", "config", "get", "source", "--show-origin"])
+        .env("RUSTC_BOOTSTRAP", "1");
+    tracing::debug!("Discovering vendored sources by {:?}", cargo_config);
+    match utf8_stdout(&mut cargo_config) {
+        Ok(stdout) => parse_source_config(cargo_toml.parent(), &stdout),
+        Err(e) => {
+            tracing::debug!("Failed to discover vendored sources: {e:?}");
+            vec![]
+        }
+    }
+}
+
+/// Parses the output of `cargo config get source --show-origin`, which looks like
+/// `source.vendored-sources.directory = "vendor" # /path/to/.cargo/config.toml`.
+fn parse_source_config(workspace_dir: &AbsPath, stdout: &str) -> Vec<(String, AbsPathBuf)> {
+    #[derive(Default)]
+    struct Source {
+        replace_with: Option<String>,
+        directory: Option<AbsPathBuf>,
+        url: Option<String>,
+    }
+    let mut sources: FxHashMap<String, Source> = FxHashMap::default();
+    for line in stdout.lines() {
+        // Every line is a TOML document on its own, with the origin given as a comment.
+        let Ok(config) = line.parse::<toml::Table>() else { continue };
+        let origin = line.rsplit_once(" # ").map(|(_, origin)| origin.trim());
+        let Some(config) = config.get("source").and_then(toml::Value::as_table) else { continue };
+        for (name, keys) in config {
+            let Some(keys) = keys.as_table() else { continue };
+            let source = sources.entry(name.clone()).or_default();
+            for (key, value) in keys {
+                let Some(value) = value.as_str() else { continue };
+                match &**key {
+                    "replace-with" => source.replace_with = Some(value.to_owned()),
+                    "directory" => {
+                        // Paths in config files are relative to the directory containing `.cargo/`.
+                        let base = origin
+                            .and_then(|origin| AbsPathBuf::try_from(origin).ok())
+                            .filter(|origin| {
+                                origin.parent().and_then(AbsPath::file_name) == Some(".cargo")
+                            })
+                            .and_then(|origin| Some(origin.parent()?.parent()?.to_path_buf()))
+                            .unwrap_or_else(|| workspace_dir.to_path_buf());
+                        source.directory = Some(base.join(value));
+                    }
+                    "registry" | "git" => source.url = Some(value.to_owned()),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    let mut res = Vec::new();
+    for (name, source) in &sources {
+        let Some(directory) =
+            source.replace_with.as_ref().and_then(|it| sources.get(it)?.directory.as_ref())
+        else {
+            continue;
+        };
+        if name == "crates-io" {
+            res.push((CRATES_IO.to_owned(), directory.clone()));
+            res.push((CRATES_IO_SPARSE.to_owned(), directory.clone()));
+        } else if let Some(url) = &source.url {
+            res.push((source_url(url).to_owned(), directory.clone()));
+        }
+    }
+    res
+}
+
+/// Strips the kind and the revision or query from a source id like
+/// `git+https://github.com/foo/bar?branch=main#abcdef`.
+fn source_url(source: &str) -> &str {
+    let url = ["registry+", "sparse+", "git+"]
+        .into_iter()
+        .find_map(|prefix| source.strip_prefix(prefix))
+        .unwrap_or(source);
+    url.split(['?', '#']).next().unwrap_or(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+    use itertools::Itertools;
+    use paths::Utf8PathBuf;
+    use stdx::format_to;
+
+    use super::*;
+
+    #[test]
+    fn source_config() {
+        let root = if cfg!(windows) { r"C:\ROOT" } else { "/ROOT" };
+        let workspace_dir = AbsPathBuf::assert(Utf8PathBuf::from(root));
+        let config = workspace_dir.join(".cargo/config.toml");
+        let stdout = format!(
+            r#"source.crates-io.replace-with = "vendored-sources" # {config}
+source."https://github.com/foo/bar".git = "https://github.com/foo/bar" # {config}
+source."https://github.com/foo/bar".replace-with = "vendored-sources" # {config}
+source.vendored-sources.directory = "vendor" # {config}"#
+        );
+        let mut sources = parse_source_config(&workspace_dir, &stdout);
+        sources.sort();
+        let vendor = workspace_dir.join("vendor");
+        assert_eq!(
+            sources,
+            [
+                ("https://github.com/foo/bar".to_owned(), vendor.clone()),
+                (CRATES_IO.to_owned(), vendor.clone()),
+                (CRATES_IO_SPARSE.to_owned(), vendor),
+            ]
+        );
+    }
+
+    #[test]
+    fn source_urls() {
+        assert_eq!(source_url("registry+https://github.com/rust-lang/crates.io-index"), CRATES_IO);
+        assert_eq!(source_url("sparse+https://index.crates.io/"), CRATES_IO_SPARSE);
+        assert_eq!(
+            source_url("git+https://github.com/foo/bar?branch=main#0123456789abcdef"),
+            "https://github.com/foo/bar"
+        );
+    }
+
+    #[test]
+    fn vendored_manifest() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[package]
+edition = "2018"
+name = "serde"
+version = "1.0.219"
+keywords = [
+    "serde",
+    "no_std",
+]
+
+[features]
+default = ["std"]
+derive = ["serde_derive"]
+std = []
+
+[lib]
+name = "serde"
+path = "src/lib.rs"
+
+[dependencies]
+either = { version = "1", default-features = false, features = ["use_std"] }
+renamed = { version = "0.1", package = "some-crate" }
+multi-line = { version = "1", features = [
+    "a",
+    "b",
+] }
+
+[dependencies.serde_derive]
+version = "1"
+optional = true
+
+[target."cfg(any())".build-dependencies.cc]
+version = "1"
+"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.package.version, "1.0.219");
+        assert_eq!(manifest.package.edition.as_deref(), Some("2018"));
+        assert_eq!(manifest.lib.as_ref().and_then(|it| it.path.as_deref()), Some("src/lib.rs"));
+        assert_eq!(manifest.features["derive"], ["serde_derive"]);
+
+        let deps = dependencies(&manifest)
+            .into_iter()
+            .map(|dep| {
+                format!(
+                    "{} {:?} {:?} {:?} default-features: {}\n",
+                    dep.package, dep.rename, dep.kind, dep.features, dep.default_features
+                )
+            })
+            .collect::<String>();
+        expect![[r#"
+            either None Normal ["use_std"] default-features: false
+            multi-line None Normal ["a", "b"] default-features: true
+            some-crate Some("renamed") Normal [] default-features: true
+            serde_derive None Normal [] default-features: true
+            cc None Build [] default-features: true
+        "#]]
+        .assert_eq(&deps);
+    }
+
+    #[test]
+    fn resolve_vendored() {
+        let root = AbsPathBuf::assert(Utf8PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_data/vendored"
+        )));
+        let meta = serde_json::from_value(json!({
+            "packages": [{
+                "name": "app",
+                "version": "0.1.0",
+                "id": "path+file:///app#0.1.0",
+                "source": null,
+                "dependencies": [
+                    {
+                        "name": "serde",
+                        "req": "^1",
+                        "kind": null,
+                        "optional": false,
+                        "uses_default_features": true,
+                        "features": ["derive"],
+                        "target": null,
+                        "rename": null,
+                    },
+                    {
+                        "name": "itoa",
+                        "req": "^0.4",
+                        "kind": null,
+                        "optional": false,
+                        "uses_default_features": false,
+                        "features": [],
+                        "target": null,
+                        "rename": "old_itoa",
+                    },
+                ],
+                "targets": [
+                    {
+                        "name": "app",
+                        "kind": ["lib"],
+                        "src_path": root.join("src/lib.rs").as_str(),
+                    },
+                ],
+                "features": {},
+                "manifest_path": root.join("Cargo.toml").as_str(),
+            }],
+            "workspace_members": ["path+file:///app#0.1.0"],
+            "resolve": null,
+            "target_directory": root.join("target").as_str(),
+            "version": 1,
+            "workspace_root": root.as_str(),
+        }))
+        .unwrap();
+        let lockfile = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "itoa 0.4.8",
+ "serde",
+]
+
+[[package]]
+name = "itoa"
+version = "0.4.8"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "b71991ff56294aa922b450139ee08b3bfc70982c6b2c7562771375cf73542dd4"
+
+[[package]]
+name = "itoa"
+version = "1.0.15"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "4a5f13b858c8d314ee3e8f639011f7ccefe71f97f96e50151fb991f267928e2c"
+
+[[package]]
+name = "serde"
+version = "1.0.219"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "5f0e2c6ed6606019b4e29e69dbaba95b11854410e5347d525002456dbbb786b6"
+dependencies = [
+ "itoa 1.0.15",
+ "serde_derive",
+]
+
+[[package]]
+name = "serde_derive"
+version = "1.0.219"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "5b0276cf7f2c73365f7157c8123c21cd9a50fbbd844757af28ca1f5925fc2a00"
+"#;
+        let directories = [(CRATES_IO.to_owned(), root.join("vendor"))];
+        let meta = resolve(&meta, lockfile, &directories, &CargoFeatures::default()).unwrap();
+
+        let mut res = String::new();
+        for node in meta.resolve.unwrap().nodes {
+            let package = meta.packages.iter().find(|it| it.id == node.id).unwrap();
+            let targets = package.targets.iter().map(|it| format!("{}: {:?}", it.name, it.kind));
+            format_to!(res, "{} {} [{}]\n", package.name, package.version, targets.format(", "));
+            format_to!(res, "    features: {:?}\n", node.features);
+            for dep in node.deps {
+                let kinds = dep.dep_kinds.iter().map(|it| it.kind.to_string());
+                format_to!(res, "    {} -> {} ({})\n", dep.name, dep.pkg, kinds.format(", "));
+            }
+        }
+        expect![[r#"
+            app 0.1.0 [app: ["lib"]]
+                features: []
+                old_itoa -> registry+https://github.com/rust-lang/crates.io-index#itoa@0.4.8 (normal)
+                serde -> registry+https://github.com/rust-lang/crates.io-index#serde@1.0.219 (normal)
+            itoa 0.4.8 [itoa: ["lib"]]
+                features: []
+            itoa 1.0.15 [itoa: ["lib"]]
+                features: ["std"]
+            serde 1.0.219 [serde: ["lib"]]
+                features: ["default", "derive", "serde_derive", "std"]
+                itoa -> registry+https://github.com/rust-lang/crates.io-index#itoa@1.0.15 (normal)
+                serde_derive -> registry+https://github.com/rust-lang/crates.io-index#serde_derive@1.0.219 (normal)
+            serde_derive 1.0.219 [serde_derive: ["proc-macro"]]
+                features: []
+        "#]].assert_eq(&res);
+    }
+}
@@ -6,7 +6,7 @@ use std::{collections::VecDeque, fmt, fs, iter, ops::Deref, sync, thread};
 
 use anyhow::Context;
 use base_db::{
-    CrateBuilderId, CrateDisplayName, CrateGraphBuilder, CrateName, CrateOrigin,
+    salsa::Durability, CrateBuilderId, CrateDisplayName, CrateGraphBuilder, CrateName, CrateOrigin,
     CrateWorkspaceData, DependencyBuilder, Env, LangCrateOrigin, ProcMacroPaths,
     TargetLayoutLoadResult,
 };
@@ -27,8 +27,8 @@ use crate::{
     sysroot::RustLibSrcWorkspace,
     toolchain_info::{rustc_cfg, target_data_layout, target_tuple, version, QueryConfig},
     CargoConfig, CargoWorkspace, CfgOverrides, InvocationStrategy, ManifestPath, Package,
-    ProjectJson, ProjectManifest, RustSourceWorkspaceConfig, Sysroot, TargetData, TargetKind,
-    WorkspaceBuildScripts,
+    ProjectJson, ProjectJsonData, ProjectManifest, RustSourceWorkspaceConfig, Sysroot, Target,
+    TargetData, TargetKind, WorkspaceBuildScripts,
 };
 use tracing::{debug, error, info};
 
@@ -86,14 +86,9 @@ pub enum ProjectWorkspaceKind {
     },
     /// Project workspace was specified using a `rust-project.json` file.
     Json(ProjectJson),
-    // FIXME: The primary limitation of this approach is that the set of detached files needs to be fixed at the beginning.
-    // That's not the end user experience we should strive for.
-    // Ideally, you should be able to just open a random detached file in existing cargo projects, and get the basic features working.
-    // That needs some changes on the salsa-level though.
-    // In particular, we should split the unified CrateGraph (which currently has maximal durability) into proper crate graph, and a set of ad hoc roots (with minimal durability).
-    // Then, we need to hide the graph behind the queries such that most queries look only at the proper crate graph, and fall back to ad hoc roots only if there's no results.
-    // After this, we should be able to tweak the logic in reload.rs to add newly opened files, which don't belong to any existing crates, to the set of the detached files.
-    // //
+    // Note that the set of detached files is fixed when the workspace is loaded. Files opened later
+    // on that belong to no crate are instead added as ad hoc crates, see
+    // `ProjectWorkspace::add_ad_hoc_crate`.
     /// Project with a set of disjoint files, not belonging to any particular workspace.
     /// Backed by basic sysroot crates for basic completion and highlighting.
     DetachedFile {
@@ -219,10 +214,17 @@ impl ProjectWorkspace {
             extra_includes,
             sysroot,
             sysroot_src,
+            sysroot_project,
             target,
+            offline,
             ..
         } = config;
-        let mut sysroot = match (sysroot, sysroot_src) {
+        let sysroot_project = sysroot_project.as_ref().and_then(load_sysroot_project);
+        let sysroot_src = match &sysroot_project {
+            Some(project) => Some(project.project_root().to_path_buf()),
+            None => sysroot_src.clone(),
+        };
+        let mut sysroot = match (sysroot, &sysroot_src) {
             (Some(RustLibSource::Discover), None) => {
                 Sysroot::discover(cargo_toml.parent(), extra_env)
             }
@@ -299,6 +301,7 @@ impl ProjectWorkspace {
                             targets: targets.clone(),
                             extra_args: extra_args.clone(),
                             extra_env: extra_env.clone(),
+                            offline: *offline,
                         },
                         &sysroot,
                         false,
@@ -337,6 +340,7 @@ impl ProjectWorkspace {
                         targets: targets.clone(),
                         extra_args: extra_args.clone(),
                         extra_env: extra_env.clone(),
+                        offline: *offline,
                     },
                     &sysroot,
                     false,
@@ -344,9 +348,7 @@ impl ProjectWorkspace {
                 )
             });
             let loaded_sysroot = s.spawn(|| {
-                sysroot.load_workspace(&RustSourceWorkspaceConfig::CargoMetadata(
-                    sysroot_metadata_config(extra_env, &targets),
-                ))
+                sysroot.load_workspace(&sysroot_source_config(sysroot_project, extra_env, &targets))
             });
             let cargo_config_extra_env =
                 s.spawn(|| cargo_config_env(cargo_toml, extra_env, &sysroot));
@@ -383,11 +385,17 @@ impl ProjectWorkspace {
         if let Some(loaded_sysroot) = loaded_sysroot {
             sysroot.set_workspace(loaded_sysroot);
         }
+        // Start out with the outputs of the previous session until the build scripts have run.
+        let build_scripts = if config.build_scripts_cache {
+            WorkspaceBuildScripts::load_cache(config, &cargo)
+        } else {
+            WorkspaceBuildScripts::default()
+        };
 
         Ok(ProjectWorkspace {
             kind: ProjectWorkspaceKind::Cargo {
                 cargo,
-                build_scripts: WorkspaceBuildScripts::default(),
+                build_scripts,
                 rustc,
                 error: error.map(Arc::new),
             },
@@ -412,7 +420,7 @@ impl ProjectWorkspace {
 
         tracing::info!(workspace = %project_json.manifest_or_root(), src_root = ?sysroot.rust_lib_src_root(), root = ?sysroot.root(), "Using sysroot");
         progress("Querying project metadata".to_owned());
-        let sysroot_project = project_json.sysroot_project.take();
+        let sysroot_project = project_json.sysroot_project.take().map(|it| *it);
         let query_config = QueryConfig::Rustc(&sysroot, project_json.path().as_ref());
         let targets = target_tuple::get(query_config, config.target.as_deref(), &config.extra_env)
             .unwrap_or_default();
@@ -435,13 +443,11 @@ impl ProjectWorkspace {
                 )
             });
             let loaded_sysroot = s.spawn(|| {
-                if let Some(sysroot_project) = sysroot_project {
-                    sysroot.load_workspace(&RustSourceWorkspaceConfig::Json(*sysroot_project))
-                } else {
-                    sysroot.load_workspace(&RustSourceWorkspaceConfig::CargoMetadata(
-                        sysroot_metadata_config(&config.extra_env, &targets),
-                    ))
-                }
+                sysroot.load_workspace(&sysroot_source_config(
+                    sysroot_project,
+                    &config.extra_env,
+                    &targets,
+                ))
             });
 
             thread::Result::Ok((
@@ -478,20 +484,38 @@ impl ProjectWorkspace {
         config: &CargoConfig,
     ) -> anyhow::Result<ProjectWorkspace> {
         let dir = detached_file.parent();
-        let mut sysroot = match &config.sysroot {
-            Some(RustLibSource::Path(path)) => Sysroot::discover_rust_lib_src_dir(path.clone()),
-            Some(RustLibSource::Discover) => Sysroot::discover(dir, &config.extra_env),
-            None => Sysroot::empty(),
+        let sysroot_project = config.sysroot_project.as_ref().and_then(load_sysroot_project);
+        let mut sysroot = match (&config.sysroot, &sysroot_project) {
+            (Some(RustLibSource::Path(path)), None) => {
+                Sysroot::discover_rust_lib_src_dir(path.clone())
+            }
+            (Some(RustLibSource::Path(path)), Some(project)) => {
+                Sysroot::new(Some(path.clone()), Some(project.project_root().to_path_buf()))
+            }
+            (Some(RustLibSource::Discover), None) => Sysroot::discover(dir, &config.extra_env),
+            (Some(RustLibSource::Discover), Some(project)) => Sysroot::discover_with_src_override(
+                dir,
+                &config.extra_env,
+                project.project_root().to_path_buf(),
+            ),
+            (None, _) => Sysroot::empty(),
         };
 
         let query_config = QueryConfig::Cargo(&sysroot, detached_file);
         let toolchain = version::get(query_config, &config.extra_env).ok().flatten();
         let targets = target_tuple::get(query_config, config.target.as_deref(), &config.extra_env)
             .unwrap_or_default();
-        let rustc_cfg = rustc_cfg::get(query_config, None, &config.extra_env);
-        let data_layout = target_data_layout::get(query_config, None, &config.extra_env);
-        let loaded_sysroot = sysroot.load_workspace(&RustSourceWorkspaceConfig::CargoMetadata(
-            sysroot_metadata_config(&config.extra_env, &targets),
+        let rustc_cfg =
+            rustc_cfg::get(query_config, targets.first().map(Deref::deref), &config.extra_env);
+        let data_layout = target_data_layout::get(
+            query_config,
+            targets.first().map(Deref::deref),
+            &config.extra_env,
+        );
+        let loaded_sysroot = sysroot.load_workspace(&sysroot_source_config(
+            sysroot_project,
+            &config.extra_env,
+            &targets,
         ));
         if let Some(loaded_sysroot) = loaded_sysroot {
             sysroot.set_workspace(loaded_sysroot);
@@ -505,6 +529,7 @@ impl ProjectWorkspace {
                 targets,
                 extra_args: config.extra_args.clone(),
                 extra_env: config.extra_env.clone(),
+                offline: config.offline,
             },
             &sysroot,
             false,
@@ -911,6 +936,109 @@ impl ProjectWorkspace {
         (crate_graph, proc_macros)
     }
 
+    /// Adds an ad hoc crate for `file`, a file that is opened in the editor but belongs to no
+    /// crate, to `crate_graph`, which has to contain the crates of this workspace already.
+    ///
+    /// The ad hoc crate depends on the sysroot and, if `package_deps` is set and the file lies
+    /// within a member package of this cargo workspace, on that package's library and
+    /// dependencies like an example target would. These dependencies are looked up in
+    /// `crate_graph`, only the ad hoc crate itself is added.
+    pub fn add_ad_hoc_crate(
+        &self,
+        crate_graph: &mut CrateGraphBuilder,
+        file: &ManifestPath,
+        package_deps: bool,
+        load: FileLoader<'_>,
+    ) -> Option<CrateBuilderId> {
+        let _p = tracing::info_span!("ProjectWorkspace::add_ad_hoc_crate").entered();
+
+        let file_id = load(file)?;
+        let display_name = file.file_stem().map(CrateDisplayName::from_canonical_name);
+        let crate_ws_data = Arc::new(CrateWorkspaceData {
+            toolchain: self.toolchain.clone(),
+            data_layout: self.target_layout.clone(),
+        });
+        let package_crate = match &self.kind {
+            ProjectWorkspaceKind::Cargo { cargo, .. } if package_deps => {
+                ad_hoc_package_crate(crate_graph, cargo, file, load)
+            }
+            _ => None,
+        };
+
+        let ad_hoc_crate = match package_crate {
+            Some((package_crate, dependencies)) => {
+                let package_crate = &crate_graph[package_crate];
+                let (edition, cfg_options, env, origin) = (
+                    package_crate.basic.edition,
+                    package_crate.cfg_options.clone(),
+                    package_crate.env.clone(),
+                    package_crate.basic.origin.clone(),
+                );
+                let ad_hoc_crate = crate_graph.add_crate_root(
+                    file_id,
+                    edition,
+                    display_name,
+                    None,
+                    cfg_options,
+                    None,
+                    env,
+                    origin,
+                    false,
+                    None,
+                    crate_ws_data,
+                );
+                for dep in dependencies {
+                    add_dep_inner(crate_graph, ad_hoc_crate, dep);
+                }
+                ad_hoc_crate
+            }
+            None => {
+                let mut cfg_options = CfgOptions::from_iter(self.rustc_cfg.clone());
+                if self.set_test {
+                    cfg_options.insert_atom(sym::test.clone());
+                }
+                cfg_options.insert_atom(sym::rust_analyzer.clone());
+                self.cfg_overrides.apply(&mut cfg_options, "");
+                let ad_hoc_crate = crate_graph.add_crate_root(
+                    file_id,
+                    Edition::CURRENT,
+                    display_name.clone(),
+                    None,
+                    Arc::new(cfg_options),
+                    None,
+                    Env::default(),
+                    CrateOrigin::Local {
+                        repo: None,
+                        name: display_name.map(|n| n.canonical_name().to_owned()),
+                    },
+                    false,
+                    None,
+                    crate_ws_data,
+                );
+                // Like detached files, ad hoc crates depend on the public sysroot crates.
+                for lang in [
+                    LangCrateOrigin::Core,
+                    LangCrateOrigin::Alloc,
+                    LangCrateOrigin::Std,
+                    LangCrateOrigin::Test,
+                ] {
+                    let origin = CrateOrigin::Lang(lang);
+                    let Some(to) =
+                        crate_graph.iter().find(|&krate| crate_graph[krate].basic.origin == origin)
+                    else {
+                        continue;
+                    };
+                    let prelude = !matches!(lang, LangCrateOrigin::Test | LangCrateOrigin::Alloc);
+                    let name = CrateName::normalize_dashes(&lang.to_string());
+                    add_dep_with_prelude(crate_graph, ad_hoc_crate, name, to, prelude, true);
+                }
+                ad_hoc_crate
+            }
+        };
+        crate_graph.set_durability(ad_hoc_crate, Durability::LOW);
+        Some(ad_hoc_crate)
+    }
+
     pub fn eq_ignore_build_data(&self, other: &Self) -> bool {
         let Self { kind, sysroot, rustc_cfg, toolchain, target_layout, cfg_overrides, .. } = self;
         let Self {
@@ -1300,6 +1428,54 @@ fn cargo_to_crate_graph(
     res
 }
 
+/// Finds the crate of the member package containing `file` whose dependencies an ad hoc crate for
+/// `file` should have, which are the ones of an example target of that package.
+///
+/// Returns the crate and the dependencies.
+fn ad_hoc_package_crate(
+    crate_graph: &CrateGraphBuilder,
+    cargo: &CargoWorkspace,
+    file: &ManifestPath,
+    load: FileLoader<'_>,
+) -> Option<(CrateBuilderId, Vec<DependencyBuilder>)> {
+    let pkg = cargo
+        .packages()
+        .filter(|&pkg| cargo[pkg].is_member && file.starts_with(cargo[pkg].manifest.parent()))
+        .max_by_key(|&pkg| cargo[pkg].manifest.parent().as_str().len())?;
+    let crate_of_target = |load: FileLoader<'_>, tgt: Target| {
+        let file_id = load(&cargo[tgt].root)?;
+        crate_graph.iter().find(|&krate| crate_graph[krate].basic.root_file_id == file_id)
+    };
+    let lib_target = |pkg: Package| {
+        cargo[pkg]
+            .targets
+            .iter()
+            .copied()
+            .find(|&tgt| matches!(cargo[tgt].kind, TargetKind::Lib { .. }))
+    };
+
+    // Examples and tests already depend on what we want, the library and the dev-dependencies.
+    let targets = || cargo[pkg].targets.iter().copied();
+    let mut preferred = targets()
+        .filter(|&tgt| matches!(cargo[tgt].kind, TargetKind::Example | TargetKind::Test))
+        .chain(targets().filter(|&tgt| matches!(cargo[tgt].kind, TargetKind::Lib { .. })))
+        .chain(targets().filter(|&tgt| cargo[tgt].kind == TargetKind::Bin));
+    let (tgt, krate) = preferred.find_map(|tgt| Some((tgt, crate_of_target(load, tgt)?)))?;
+    let mut dependencies = crate_graph[krate].basic.dependencies.clone();
+    if !matches!(cargo[tgt].kind, TargetKind::Example | TargetKind::Test) {
+        if matches!(cargo[tgt].kind, TargetKind::Lib { .. }) {
+            let name = CrateName::normalize_dashes(&cargo[tgt].name);
+            dependencies.push(DependencyBuilder::new(name, krate));
+        }
+        for dep in cargo[pkg].dependencies.iter().filter(|dep| dep.kind == DepKind::Dev) {
+            if let Some(to) = lib_target(dep.pkg).and_then(|tgt| crate_of_target(load, tgt)) {
+                dependencies.push(DependencyBuilder::new(CrateName::new(&dep.name).unwrap(), to));
+            }
+        }
+    }
+    Some((krate, dependencies))
+}
+
 fn detached_file_to_crate_graph(
     rustc_cfg: Vec<CfgAtom>,
     load: FileLoader<'_>,
@@ -1777,6 +1953,37 @@ fn add_dep_inner(graph: &mut CrateGraphBuilder, from: CrateBuilderId, dep: Depen
     }
 }
 
+pub(crate) fn sysroot_source_config(
+    sysroot_project: Option<ProjectJson>,
+    extra_env: &FxHashMap<String, String>,
+    targets: &[String],
+) -> RustSourceWorkspaceConfig {
+    match sysroot_project {
+        Some(project) => RustSourceWorkspaceConfig::Json(project),
+        None => {
+            RustSourceWorkspaceConfig::CargoMetadata(sysroot_metadata_config(extra_env, targets))
+        }
+    }
+}
+
+/// Loads a `rust-project.json`-style file describing the sysroot crates, like a patched `core` and
+/// `alloc` used with `-Zbuild-std`. Paths in it are relative to the file.
+pub(crate) fn load_sysroot_project(path: &ManifestPath) -> Option<ProjectJson> {
+    let data = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read sysroot project {path}"))
+        .and_then(|text| {
+            serde_json::from_str::<ProjectJsonData>(&text)
+                .with_context(|| format!("Failed to deserialize sysroot project {path}"))
+        });
+    match data {
+        Ok(data) => Some(ProjectJson::new(Some(path.clone()), path.parent(), data)),
+        Err(e) => {
+            tracing::error!(%e, "falling back to the default sysroot crates");
+            None
+        }
+    }
+}
+
 fn sysroot_metadata_config(
     extra_env: &FxHashMap<String, String>,
     targets: &[String],
@@ -1786,5 +1993,6 @@ fn sysroot_metadata_config(
         targets: targets.to_vec(),
         extra_args: Default::default(),
         extra_env: extra_env.clone(),
+        offline: false,
     }
 }
@@ -87,6 +87,9 @@ fn actual_main() -> anyhow::Result<ExitCode> {
         }
         flags::RustAnalyzerCmd::Scip(cmd) => cmd.run()?,
         flags::RustAnalyzerCmd::RunTests(cmd) => cmd.run()?,
+        flags::RustAnalyzerCmd::LayoutDump(cmd) => cmd.run()?,
+        flags::RustAnalyzerCmd::LayoutDiff(cmd) => cmd.run()?,
+        flags::RustAnalyzerCmd::DebugAdapter(cmd) => cmd.run()?,
         flags::RustAnalyzerCmd::RustcTests(cmd) => cmd.run()?,
     }
     Ok(ExitCode::SUCCESS)
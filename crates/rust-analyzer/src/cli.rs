@@ -3,9 +3,11 @@
 #![allow(clippy::print_stdout, clippy::print_stderr)]
 
 mod analysis_stats;
+mod debug_adapter;
 mod diagnostics;
 pub mod flags;
 mod highlight;
+mod layouts;
 mod lsif;
 mod parse;
 mod run_tests;
@@ -43,6 +45,23 @@ impl Verbosity {
     }
 }
 
+/// All modules of the local crates.
+fn all_modules(db: &dyn HirDatabase) -> Vec<Module> {
+    let mut worklist: Vec<_> = hir::Crate::all(db)
+        .into_iter()
+        .filter(|x| x.origin(db).is_local())
+        .map(|krate| krate.root_module())
+        .collect();
+    let mut modules = Vec::new();
+
+    while let Some(module) = worklist.pop() {
+        modules.push(module);
+        worklist.extend(module.children(db));
+    }
+
+    modules
+}
+
 fn read_stdin() -> anyhow::Result<String> {
     let mut buff = String::new();
     std::io::stdin().read_to_string(&mut buff)?;
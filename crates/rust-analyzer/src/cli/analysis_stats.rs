@@ -1071,6 +1071,7 @@ impl flags::AnalysisStats {
                     adjustment_hints_hide_outside_unsafe: false,
                     closure_return_type_hints: ide::ClosureReturnTypeHints::Always,
                     closure_capture_hints: true,
+                    const_value_hints: true,
                     binding_mode_hints: true,
                     implicit_drop_hints: true,
                     lifetime_elision_hints: ide::LifetimeElisionHints::Always,
@@ -0,0 +1,708 @@
+//! A Debug Adapter Protocol server, debugging functions with the mir interpreter.
+//!
+//! The server speaks DAP over stdio. A session is started with a `launch` request, whose
+//! arguments name the workspace and the function to run:
+//!
+//! ```json
+//! { "workspace": "/path/to/project", "function": "tests::my_test", "stopOnEntry": true }
+//! ```
+//!
+//! The function is then run inside the interpreter, which pauses at breakpoints and supports
+//! stepping over statements and into and out of calls. Since nothing is compiled, there is a
+//! single thread, and locals are shown by rendering their value with their type.
+
+use std::{
+    io::{self, BufRead, Write},
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
+
+use crossbeam_channel::{Receiver, TryRecvError};
+use hir::{
+    mir::{DebugState, MirDebugger},
+    Function,
+};
+use ide::{FileId, LineCol};
+use ide_db::{LineIndexDatabase, RootDatabase};
+use load_cargo::{load_workspace_at, LoadCargoConfig, ProcMacroServerChoice};
+use paths::AbsPathBuf;
+use project_model::{CargoConfig, RustLibSource};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use syntax::TextRange;
+use vfs::{Vfs, VfsPath};
+
+use crate::cli::{all_modules, flags, full_name_of_item};
+
+/// DAP clients see a single thread with this id.
+const THREAD_ID: u64 = 1;
+
+impl flags::DebugAdapter {
+    pub fn run(self) -> anyhow::Result<()> {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        std::thread::Builder::new().name("DebugAdapterReader".to_owned()).spawn(move || {
+            let mut stdin = io::stdin().lock();
+            while let Ok(Some(request)) = read_message(&mut stdin) {
+                if sender.send(request).is_err() {
+                    break;
+                }
+            }
+        })?;
+        Session::new(receiver, Box::new(io::stdout()), Box::new(load_cargo_workspace)).run()
+    }
+}
+
+/// Loads the workspace in the given directory, for the `launch` request.
+type LoadWorkspace = Box<dyn Fn(&Path) -> anyhow::Result<(RootDatabase, Vfs)> + Send>;
+
+fn load_cargo_workspace(root: &Path) -> anyhow::Result<(RootDatabase, Vfs)> {
+    let cargo_config = CargoConfig {
+        sysroot: Some(RustLibSource::Discover),
+        all_targets: true,
+        set_test: true,
+        ..Default::default()
+    };
+    let load_cargo_config = LoadCargoConfig {
+        load_out_dirs_from_check: true,
+        with_proc_macro_server: ProcMacroServerChoice::Sysroot,
+        prefill_caches: false,
+    };
+    let (db, vfs, _proc_macro) =
+        load_workspace_at(root, &cargo_config, &load_cargo_config, &|_| {})?;
+    Ok((db, vfs))
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    seq: u64,
+    command: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchArguments {
+    /// Directory with Cargo.toml.
+    workspace: PathBuf,
+    /// Path of the function to run, relative to its crate, e.g. `tests::my_test`.
+    function: String,
+    #[serde(default)]
+    stop_on_entry: bool,
+}
+
+/// Reads a message framed by a `Content-Length` header.
+fn read_message<T: DeserializeOwned>(input: &mut impl BufRead) -> anyhow::Result<Option<T>> {
+    let mut content_length = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(": ") {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = Some(value.parse::<usize>()?);
+            }
+        }
+    }
+    let Some(content_length) = content_length else {
+        anyhow::bail!("missing Content-Length header");
+    };
+    let mut buf = vec![0; content_length];
+    input.read_exact(&mut buf)?;
+    Ok(Some(serde_json::from_slice(&buf)?))
+}
+
+/// Writes responses and events to the client, usually stdout.
+struct Output {
+    seq: u64,
+    writer: Box<dyn Write + Send>,
+}
+
+impl Output {
+    fn send(&mut self, mut message: Value) {
+        self.seq += 1;
+        message["seq"] = self.seq.into();
+        let message = message.to_string();
+        let writer = &mut self.writer;
+        let res = write!(writer, "Content-Length: {}\r\n\r\n{message}", message.len())
+            .and_then(|()| writer.flush());
+        if let Err(e) = res {
+            tracing::error!("failed to write to the debug adapter client: {e}");
+        }
+    }
+
+    fn respond(&mut self, request: &Request, body: Value) {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "command": request.command,
+            "success": true,
+            "body": body,
+        }));
+    }
+
+    fn respond_err(&mut self, request: &Request, message: impl std::fmt::Display) {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "command": request.command,
+            "success": false,
+            "message": message.to_string(),
+        }));
+    }
+
+    fn event(&mut self, event: &str, body: Value) {
+        self.send(json!({ "type": "event", "event": event, "body": body }));
+    }
+}
+
+struct Workspace {
+    db: RootDatabase,
+    vfs: Vfs,
+    function: Function,
+}
+
+/// Zero based source lines of a file.
+type Location = (FileId, u32);
+
+/// Decides where the interpreter pauses.
+enum Mode {
+    Run,
+    Pause(&'static str),
+    StepIn,
+    StepOver { depth: usize },
+    StepOut { depth: usize },
+}
+
+struct Session {
+    receiver: Receiver<Request>,
+    output: Output,
+    load_workspace: LoadWorkspace,
+    lines_start_at1: bool,
+    /// Breakpoints received before the launch, to be resolved once the workspace is loaded.
+    pending_breakpoints: Vec<(String, Vec<u32>)>,
+    breakpoints: FxHashMap<FileId, FxHashSet<u32>>,
+    mode: Mode,
+    /// The location of the last step, used to hit breakpoints once per line.
+    last_step: Option<(usize, Location)>,
+    /// The location we last paused at.
+    last_stop: Option<(usize, Location)>,
+}
+
+impl Session {
+    fn new(
+        receiver: Receiver<Request>,
+        writer: Box<dyn Write + Send>,
+        load_workspace: LoadWorkspace,
+    ) -> Session {
+        Session {
+            receiver,
+            output: Output { seq: 0, writer },
+            load_workspace,
+            lines_start_at1: true,
+            pending_breakpoints: Vec::new(),
+            breakpoints: FxHashMap::default(),
+            mode: Mode::Run,
+            last_step: None,
+            last_stop: None,
+        }
+    }
+
+    fn run(mut self) -> anyhow::Result<()> {
+        let mut workspace = None;
+        let mut configured = false;
+        while let Ok(request) = self.receiver.recv() {
+            match request.command.as_str() {
+                "initialize" => {
+                    self.lines_start_at1 =
+                        request.arguments["linesStartAt1"].as_bool().unwrap_or(true);
+                    self.output.respond(
+                        &request,
+                        json!({
+                            "supportsConfigurationDoneRequest": true,
+                            "supportsTerminateRequest": true,
+                        }),
+                    );
+                    self.output.event("initialized", json!({}));
+                }
+                "launch" => match self.launch(&request) {
+                    Ok(it) => {
+                        self.output.respond(&request, json!({}));
+                        if configured {
+                            self.debug(&it);
+                        }
+                        workspace = Some(it);
+                    }
+                    Err(e) => self.output.respond_err(&request, e),
+                },
+                "setBreakpoints" => self.set_breakpoints(&request, workspace.as_ref()),
+                "configurationDone" => {
+                    self.output.respond(&request, json!({}));
+                    configured = true;
+                    if let Some(workspace) = &workspace {
+                        self.debug(workspace);
+                    }
+                }
+                "threads" => self.respond_threads(&request),
+                "disconnect" | "terminate" => {
+                    self.output.respond(&request, json!({}));
+                    break;
+                }
+                _ => self.output.respond_err(&request, "the debuggee is not running"),
+            }
+        }
+        Ok(())
+    }
+
+    fn launch(&mut self, request: &Request) -> anyhow::Result<Workspace> {
+        let args = LaunchArguments::deserialize(&request.arguments)?;
+        if args.stop_on_entry {
+            self.mode = Mode::Pause("entry");
+        }
+        let (db, vfs) = (self.load_workspace)(&args.workspace)?;
+        let function = all_modules(&db)
+            .into_iter()
+            .flat_map(|module| module.declarations(&db))
+            .filter_map(|it| match it {
+                hir::ModuleDef::Function(it) => Some(it),
+                _ => None,
+            })
+            .find(|it| full_name_of_item(&db, it.module(&db), it.name(&db)) == args.function)
+            .ok_or_else(|| anyhow::format_err!("function `{}` not found", args.function))?;
+        for (path, lines) in std::mem::take(&mut self.pending_breakpoints) {
+            if let Some(file_id) = file_id_of_path(&vfs, &path) {
+                self.breakpoints.insert(file_id, lines.into_iter().collect());
+            }
+        }
+        Ok(Workspace { db, vfs, function })
+    }
+
+    fn set_breakpoints(&mut self, request: &Request, workspace: Option<&Workspace>) {
+        let args = &request.arguments;
+        let Some(path) = args["source"]["path"].as_str() else {
+            self.output.respond_err(request, "missing source path");
+            return;
+        };
+        let first_line = self.first_line();
+        let lines: Vec<u32> = args["breakpoints"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|it| it["line"].as_u64())
+            .filter_map(|it| u32::try_from(it).ok()?.checked_sub(first_line))
+            .collect();
+        let verified = match workspace {
+            Some(workspace) => match file_id_of_path(&workspace.vfs, path) {
+                Some(file_id) => {
+                    self.breakpoints.insert(file_id, lines.iter().copied().collect());
+                    true
+                }
+                None => false,
+            },
+            None => {
+                self.pending_breakpoints.push((path.into(), lines.clone()));
+                true
+            }
+        };
+        let breakpoints: Vec<_> = lines
+            .iter()
+            .map(|line| json!({ "verified": verified, "line": line + first_line }))
+            .collect();
+        self.output.respond(request, json!({ "breakpoints": breakpoints }));
+    }
+
+    fn respond_threads(&mut self, request: &Request) {
+        self.output.respond(request, json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }));
+    }
+
+    fn first_line(&self) -> u32 {
+        u32::from(self.lines_start_at1)
+    }
+
+    fn debug(&mut self, workspace: &Workspace) {
+        let db = &workspace.db;
+        let span_formatter = |file_id, range: TextRange| {
+            let LineCol { line, col } = db.line_index(file_id).line_col(range.start());
+            format!("{}:{}:{}", workspace.vfs.file_path(file_id), line + 1, col + 1)
+        };
+        let mut debugger = Debugger { session: self, workspace };
+        let result = workspace.function.debug(db, span_formatter, &mut debugger);
+        let (output, exit_code) = match result {
+            Ok(output) => {
+                let exit_code = if output.starts_with("pass") { 0 } else { 1 };
+                (output, exit_code)
+            }
+            Err(e) => (format!("{e:?}"), 1),
+        };
+        self.output.event("output", json!({ "category": "stdout", "output": output + "\n" }));
+        self.output.event("exited", json!({ "exitCode": exit_code }));
+        self.output.event("terminated", json!({}));
+    }
+}
+
+/// Drives the interpreter for a session.
+struct Debugger<'a> {
+    session: &'a mut Session,
+    workspace: &'a Workspace,
+}
+
+impl MirDebugger for Debugger<'_> {
+    fn on_step(&mut self, state: &DebugState<'_, '_>) -> ControlFlow<()> {
+        loop {
+            match self.session.receiver.try_recv() {
+                Ok(request) => self.handle(request, state)?,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return ControlFlow::Break(()),
+            }
+        }
+        let session = &mut *self.session;
+        if matches!(session.mode, Mode::Run) && session.breakpoints.is_empty() {
+            return ControlFlow::Continue(());
+        }
+        let Some(location) = self.location(state) else {
+            return ControlFlow::Continue(());
+        };
+        let session = &mut *self.session;
+        let depth = state.depth();
+        let step = (depth, location);
+        let last_stop = session.last_stop.map(|it| it.1);
+        let reason = match session.mode {
+            Mode::Pause(reason) => Some(reason),
+            Mode::StepIn if session.last_stop != Some(step) => Some("step"),
+            Mode::StepOver { depth: d }
+                if depth < d || (depth == d && last_stop != Some(location)) =>
+            {
+                Some("step")
+            }
+            Mode::StepOut { depth: d } if depth < d => Some("step"),
+            _ => None,
+        };
+        let hit_breakpoint = session.last_step != Some(step)
+            && session.breakpoints.get(&location.0).is_some_and(|it| it.contains(&location.1));
+        session.last_step = Some(step);
+        let Some(reason) = reason.or(hit_breakpoint.then_some("breakpoint")) else {
+            return ControlFlow::Continue(());
+        };
+        session.last_stop = Some(step);
+        session.output.event(
+            "stopped",
+            json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        );
+        session.mode = Mode::Pause("pause");
+        while matches!(self.session.mode, Mode::Pause(_)) {
+            let Ok(request) = self.session.receiver.recv() else {
+                return ControlFlow::Break(());
+            };
+            self.handle(request, state)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl Debugger<'_> {
+    fn handle(&mut self, request: Request, state: &DebugState<'_, '_>) -> ControlFlow<()> {
+        let session = &mut *self.session;
+        let depth = state.depth();
+        match request.command.as_str() {
+            "continue" => {
+                session.mode = Mode::Run;
+                session.output.respond(&request, json!({ "allThreadsContinued": true }));
+            }
+            "next" => {
+                session.mode = Mode::StepOver { depth };
+                session.output.respond(&request, json!({}));
+            }
+            "stepIn" => {
+                session.mode = Mode::StepIn;
+                session.output.respond(&request, json!({}));
+            }
+            "stepOut" => {
+                session.mode = Mode::StepOut { depth };
+                session.output.respond(&request, json!({}));
+            }
+            "pause" => {
+                if !matches!(session.mode, Mode::Pause(_)) {
+                    session.mode = Mode::Pause("pause");
+                }
+                session.output.respond(&request, json!({}));
+            }
+            "threads" => session.respond_threads(&request),
+            "setBreakpoints" => session.set_breakpoints(&request, Some(self.workspace)),
+            "stackTrace" => {
+                let frames: Vec<_> = state
+                    .frames()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(id, frame)| {
+                        let mut result = json!({
+                            "id": id,
+                            "name": frame.name,
+                            "line": 0,
+                            "column": 0,
+                        });
+                        if let Some(range) = frame.range {
+                            let file_id = range.file_id.file_id();
+                            let line_col =
+                                self.workspace.db.line_index(file_id).line_col(range.range.start());
+                            let first_line = self.session.first_line();
+                            result["line"] = (line_col.line + first_line).into();
+                            result["column"] = (line_col.col + first_line).into();
+                            let path = self.workspace.vfs.file_path(file_id);
+                            result["source"] = json!({
+                                "name": path.name_and_extension().map(|(name, ext)| match ext {
+                                    Some(ext) => format!("{name}.{ext}"),
+                                    None => name.to_owned(),
+                                }),
+                                "path": path.to_string(),
+                            });
+                        }
+                        result
+                    })
+                    .collect();
+                let total = frames.len();
+                self.session
+                    .output
+                    .respond(&request, json!({ "stackFrames": frames, "totalFrames": total }));
+            }
+            "scopes" => {
+                let frame = request.arguments["frameId"].as_u64().unwrap_or(0);
+                session.output.respond(
+                    &request,
+                    json!({ "scopes": [{
+                        "name": "Locals",
+                        "presentationHint": "locals",
+                        "variablesReference": frame + 1,
+                        "expensive": false,
+                    }] }),
+                );
+            }
+            "variables" => {
+                let reference = request.arguments["variablesReference"].as_u64().unwrap_or(0);
+                let variables: Vec<_> = match reference.checked_sub(1) {
+                    Some(frame) => state
+                        .locals(frame as usize)
+                        .into_iter()
+                        .map(|local| {
+                            json!({
+                                "name": local.name,
+                                "value": local.value,
+                                "type": local.ty,
+                                "variablesReference": 0,
+                            })
+                        })
+                        .collect(),
+                    None => Vec::new(),
+                };
+                session.output.respond(&request, json!({ "variables": variables }));
+            }
+            "disconnect" | "terminate" => {
+                session.output.respond(&request, json!({}));
+                return ControlFlow::Break(());
+            }
+            _ => session.output.respond_err(&request, format!("unsupported: {}", request.command)),
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn location(&self, state: &DebugState<'_, '_>) -> Option<Location> {
+        let range = state.range()?;
+        let file_id = range.file_id.file_id();
+        let line = self.workspace.db.line_index(file_id).line_col(range.range.start()).line;
+        Some((file_id, line))
+    }
+}
+
+fn file_id_of_path(vfs: &Vfs, path: &str) -> Option<FileId> {
+    let path = AbsPathBuf::try_from(path).ok()?;
+    vfs.file_id(&VfsPath::from(path)).map(|(file_id, _)| file_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use crossbeam_channel::{Receiver, Sender};
+    use expect_test::{expect, Expect};
+    use ide::AnalysisHost;
+    use test_fixture::ChangeFixture;
+
+    use super::*;
+
+    /// Sends every flushed message to the test.
+    struct ChannelWriter {
+        buf: Vec<u8>,
+        sender: Sender<Vec<u8>>,
+    }
+
+    impl Write for ChannelWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.buf.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            _ = self.sender.send(std::mem::take(&mut self.buf));
+            Ok(())
+        }
+    }
+
+    /// A client talking to a session that loads `ra_fixture` as its workspace, with the first
+    /// file at `/ROOT/main.rs`.
+    struct Client {
+        seq: u64,
+        requests: Sender<Request>,
+        messages: Receiver<Vec<u8>>,
+        transcript: String,
+        session: thread::JoinHandle<anyhow::Result<()>>,
+    }
+
+    impl Client {
+        fn new(#[rust_analyzer::rust_fixture] ra_fixture: &str) -> Client {
+            let fixture = ra_fixture.to_owned();
+            let load_workspace = move |_: &Path| {
+                let mut host = AnalysisHost::default();
+                host.raw_database_mut().apply_change(ChangeFixture::parse(&fixture).change);
+                let mut vfs = Vfs::default();
+                vfs.set_file_contents(VfsPath::from(root().join("main.rs")), Some(Vec::new()));
+                Ok((host.raw_database().clone(), vfs))
+            };
+            let (requests, receiver) = crossbeam_channel::unbounded();
+            let (sender, messages) = crossbeam_channel::unbounded();
+            let writer = ChannelWriter { buf: Vec::new(), sender };
+            let session = thread::spawn(move || {
+                Session::new(receiver, Box::new(writer), Box::new(load_workspace)).run()
+            });
+            Client { seq: 0, requests, messages, transcript: String::new(), session }
+        }
+
+        /// Sends a request and waits for its response.
+        fn request(&mut self, command: &str, arguments: Value) {
+            self.seq += 1;
+            let request = json!({ "seq": self.seq, "command": command, "arguments": arguments });
+            let request = request.to_string();
+            let framed = format!("Content-Length: {}\r\n\r\n{request}", request.len());
+            let request = read_message(&mut framed.as_bytes()).unwrap().unwrap();
+            self.requests.send(request).unwrap();
+            let seq = self.seq;
+            self.receive_until(|it| it["type"] == "response" && it["request_seq"] == seq);
+        }
+
+        /// Waits for the given event.
+        fn event(&mut self, event: &str) {
+            self.receive_until(|it| it["type"] == "event" && it["event"] == event);
+        }
+
+        fn receive_until(&mut self, f: impl Fn(&Value) -> bool) {
+            loop {
+                let Ok(message) = self.messages.recv_timeout(Duration::from_secs(10)) else {
+                    panic!("timed out, received:\n{}", self.transcript);
+                };
+                let message: Value = read_message(&mut &*message).unwrap().unwrap();
+                let root = root().to_string().replace('\\', "\\\\");
+                let line = message.to_string().replace(&root, "$ROOT");
+                self.transcript.push_str(&line);
+                self.transcript.push('\n');
+                if f(&message) {
+                    break;
+                }
+            }
+        }
+
+        fn finish(self, expect: Expect) {
+            drop(self.requests);
+            self.session.join().unwrap().unwrap();
+            expect.assert_eq(&self.transcript);
+        }
+    }
+
+    fn root() -> AbsPathBuf {
+        let root = if cfg!(windows) { r"C:\ROOT" } else { "/ROOT" };
+        AbsPathBuf::assert_utf8(root.into())
+    }
+
+    #[test]
+    fn requests_before_launch() {
+        let mut client = Client::new("");
+        client.request("initialize", json!({ "linesStartAt1": true }));
+        client.request(
+            "setBreakpoints",
+            json!({ "source": { "path": "/main.rs" }, "breakpoints": [{ "line": 3 }] }),
+        );
+        client.request("threads", json!({}));
+        client.request("next", json!({}));
+        client.request("launch", json!({ "workspace": "/" }));
+        client.request("disconnect", json!({}));
+        client.finish(expect![[r#"
+            {"type":"response","request_seq":1,"command":"initialize","success":true,"body":{"supportsConfigurationDoneRequest":true,"supportsTerminateRequest":true},"seq":1}
+            {"type":"event","event":"initialized","body":{},"seq":2}
+            {"type":"response","request_seq":2,"command":"setBreakpoints","success":true,"body":{"breakpoints":[{"verified":true,"line":3}]},"seq":3}
+            {"type":"response","request_seq":3,"command":"threads","success":true,"body":{"threads":[{"id":1,"name":"main"}]},"seq":4}
+            {"type":"response","request_seq":4,"command":"next","success":false,"message":"the debuggee is not running","seq":5}
+            {"type":"response","request_seq":5,"command":"launch","success":false,"message":"missing field `function`","seq":6}
+            {"type":"response","request_seq":6,"command":"disconnect","success":true,"body":{},"seq":7}
+        "#]]);
+    }
+
+    #[test]
+    fn step_through_function() {
+        let mut client = Client::new(
+            r#"
+//- /main.rs crate:main
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+fn run() {
+    let x = add(1, 2);
+    let y = x * 2;
+}
+"#,
+        );
+        client.request("initialize", json!({ "linesStartAt1": true }));
+        client.request("launch", json!({ "workspace": "/", "function": "run" }));
+        let main_rs = root().join("main.rs");
+        client.request(
+            "setBreakpoints",
+            json!({ "source": { "path": main_rs.as_str() }, "breakpoints": [{ "line": 6 }] }),
+        );
+        client.request("configurationDone", json!({}));
+        client.event("stopped");
+        client.request("stepIn", json!({}));
+        client.event("stopped");
+        client.request("stackTrace", json!({ "threadId": THREAD_ID }));
+        client.request("scopes", json!({ "frameId": 0 }));
+        client.request("variables", json!({ "variablesReference": 1 }));
+        client.request("stepOut", json!({}));
+        client.event("stopped");
+        client.request("continue", json!({}));
+        client.event("terminated");
+        client.request("disconnect", json!({}));
+        client.finish(expect![[r#"
+            {"type":"response","request_seq":1,"command":"initialize","success":true,"body":{"supportsConfigurationDoneRequest":true,"supportsTerminateRequest":true},"seq":1}
+            {"type":"event","event":"initialized","body":{},"seq":2}
+            {"type":"response","request_seq":2,"command":"launch","success":true,"body":{},"seq":3}
+            {"type":"response","request_seq":3,"command":"setBreakpoints","success":true,"body":{"breakpoints":[{"verified":true,"line":6}]},"seq":4}
+            {"type":"response","request_seq":4,"command":"configurationDone","success":true,"body":{},"seq":5}
+            {"type":"event","event":"stopped","body":{"reason":"breakpoint","threadId":1,"allThreadsStopped":true},"seq":6}
+            {"type":"response","request_seq":5,"command":"stepIn","success":true,"body":{},"seq":7}
+            {"type":"event","event":"stopped","body":{"reason":"step","threadId":1,"allThreadsStopped":true},"seq":8}
+            {"type":"response","request_seq":6,"command":"stackTrace","success":true,"body":{"stackFrames":[{"id":0,"name":"add","line":2,"column":5,"source":{"name":"main.rs","path":"$ROOT/main.rs"}},{"id":1,"name":"run","line":6,"column":13,"source":{"name":"main.rs","path":"$ROOT/main.rs"}}],"totalFrames":2},"seq":9}
+            {"type":"response","request_seq":7,"command":"scopes","success":true,"body":{"scopes":[{"name":"Locals","presentationHint":"locals","variablesReference":1,"expensive":false}]},"seq":10}
+            {"type":"response","request_seq":8,"command":"variables","success":true,"body":{"variables":[{"name":"a","value":"1","type":"i32","variablesReference":0},{"name":"b","value":"2","type":"i32","variablesReference":0}]},"seq":11}
+            {"type":"response","request_seq":9,"command":"stepOut","success":true,"body":{},"seq":12}
+            {"type":"event","event":"stopped","body":{"reason":"step","threadId":1,"allThreadsStopped":true},"seq":13}
+            {"type":"response","request_seq":10,"command":"continue","success":true,"body":{"allThreadsContinued":true},"seq":14}
+            {"type":"event","event":"output","body":{"category":"stdout","output":"pass\n"},"seq":15}
+            {"type":"event","event":"exited","body":{"exitCode":0},"seq":16}
+            {"type":"event","event":"terminated","body":{},"seq":17}
+            {"type":"response","request_seq":11,"command":"disconnect","success":true,"body":{},"seq":18}
+        "#]]);
+    }
+}
@@ -105,8 +105,38 @@ xflags::xflags! {
         cmd run-tests {
             /// Directory with Cargo.toml.
             required path: PathBuf
+
+            /// Fail tests which run into undefined behavior, like out of bounds accesses.
+            optional --check-ub
+        }
+
+        /// Dump the size, alignment and niches of all non-generic types in the workspace as JSON
+        cmd layout-dump {
+            /// Directory with Cargo.toml.
+            required path: PathBuf
+
+            /// Write the dump to this file, instead of stdout.
+            optional --output path: PathBuf
+
+            /// Don't run build scripts or load `OUT_DIR` values by running `cargo check` before analysis.
+            optional --disable-build-scripts
+            /// Don't use expand proc macros.
+            optional --disable-proc-macros
+            /// Run the proc-macro-srv binary at the specified path.
+            optional --proc-macro-srv path: PathBuf
+        }
+
+        /// Compare two dumps of `layout-dump`, failing if any type grew
+        cmd layout-diff {
+            /// The dump to compare against, e.g. from the base branch.
+            required old: PathBuf
+            /// The dump with the changes.
+            required new: PathBuf
         }
 
+        /// Run a Debug Adapter Protocol server on stdio, debugging functions with mir interpreter
+        cmd debug-adapter {}
+
         /// Run unit tests of the project using mir interpreter
         cmd rustc-tests {
             /// Directory with Cargo.toml.
@@ -196,6 +226,9 @@ pub enum RustAnalyzerCmd {
     Highlight(Highlight),
     AnalysisStats(AnalysisStats),
     RunTests(RunTests),
+    LayoutDump(LayoutDump),
+    LayoutDiff(LayoutDiff),
+    DebugAdapter(DebugAdapter),
     RustcTests(RustcTests),
     Diagnostics(Diagnostics),
     UnresolvedReferences(UnresolvedReferences),
@@ -252,8 +285,29 @@ pub struct AnalysisStats {
 #[derive(Debug)]
 pub struct RunTests {
     pub path: PathBuf,
+
+    pub check_ub: bool,
+}
+
+#[derive(Debug)]
+pub struct LayoutDump {
+    pub path: PathBuf,
+
+    pub output: Option<PathBuf>,
+    pub disable_build_scripts: bool,
+    pub disable_proc_macros: bool,
+    pub proc_macro_srv: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct LayoutDiff {
+    pub old: PathBuf,
+    pub new: PathBuf,
 }
 
+#[derive(Debug)]
+pub struct DebugAdapter;
+
 #[derive(Debug)]
 pub struct RustcTests {
     pub rustc_repo: PathBuf,
@@ -0,0 +1,309 @@
+//! Dumps the memory layout of all non-generic types of a workspace, and compares such dumps to
+//! catch types that unexpectedly grew.
+
+use std::{collections::BTreeMap, fmt::Write, fs, path::Path};
+
+use hir::{Crate, GenericDef, ModuleDef};
+use ide::RootDatabase;
+use load_cargo::{load_workspace, LoadCargoConfig, ProcMacroServerChoice};
+use project_model::{ProjectManifest, ProjectWorkspace, ProjectWorkspaceKind, TargetKind};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{all_modules, flags, full_name_of_item};
+
+/// The layout of a type, as it is stored in a dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct TypeLayout {
+    size: u64,
+    align: u64,
+    /// The number of invalid values the type has, which enums around it can use for their tag.
+    niches: Option<u128>,
+}
+
+impl flags::LayoutDump {
+    pub fn run(self) -> anyhow::Result<()> {
+        let root =
+            vfs::AbsPathBuf::assert_utf8(std::env::current_dir()?.join(&self.path)).normalize();
+        let config = crate::config::Config::new(
+            root.clone(),
+            lsp_types::ClientCapabilities::default(),
+            vec![],
+            None,
+        );
+        let cargo_config = config.cargo(None);
+        let with_proc_macro_server = if let Some(p) = &self.proc_macro_srv {
+            let path = vfs::AbsPathBuf::assert_utf8(std::env::current_dir()?.join(p));
+            ProcMacroServerChoice::Explicit(path)
+        } else if self.disable_proc_macros {
+            ProcMacroServerChoice::None
+        } else {
+            ProcMacroServerChoice::Sysroot
+        };
+        let load_cargo_config = LoadCargoConfig {
+            load_out_dirs_from_check: !self.disable_build_scripts,
+            with_proc_macro_server,
+            prefill_caches: false,
+        };
+        let manifest = ProjectManifest::discover_single(&root)?;
+        let mut workspace = ProjectWorkspace::load(manifest, &cargo_config, &|_| {})?;
+        if load_cargo_config.load_out_dirs_from_check {
+            let build_scripts = workspace.run_build_scripts(&cargo_config, &|_| {})?;
+            workspace.set_build_scripts(build_scripts);
+        }
+        let (db, vfs, _proc_macro) =
+            load_workspace(workspace.clone(), &cargo_config.extra_env, &load_cargo_config)?;
+
+        let target_kind = |krate: Crate| {
+            let ProjectWorkspaceKind::Cargo { cargo, .. } = &workspace.kind else { return None };
+            let root = vfs.file_path(krate.root_file(&db));
+            let target = cargo.target_by_root(root.as_path()?)?;
+            Some(cargo[target].kind)
+        };
+        let layouts = collect_layouts(&db, &target_kind);
+        let json = serde_json::to_string_pretty(&layouts)?;
+        match &self.output {
+            Some(output) => fs::write(output, json + "\n")?,
+            None => println!("{json}"),
+        }
+        Ok(())
+    }
+}
+
+impl flags::LayoutDiff {
+    pub fn run(self) -> anyhow::Result<()> {
+        let old = read_dump(&self.old)?;
+        let new = read_dump(&self.new)?;
+        let (report, grown) = diff_layouts(&old, &new);
+        print!("{report}");
+        if grown > 0 {
+            anyhow::bail!("{grown} types grew");
+        }
+        Ok(())
+    }
+}
+
+fn read_dump(path: &Path) -> anyhow::Result<BTreeMap<String, TypeLayout>> {
+    let text = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Computes the layouts of all structs, enums and unions without type or const parameters
+/// declared in the modules of local crates, by their path.
+///
+/// Workspaces usually have more than one crate, so paths start with the crate. The library and
+/// the binaries of a package usually share their name, so the crates of other targets than
+/// libraries are followed by their kind, e.g. `foo[bin]::Config`.
+fn collect_layouts(
+    db: &RootDatabase,
+    target_kind: &dyn Fn(Crate) -> Option<TargetKind>,
+) -> BTreeMap<String, TypeLayout> {
+    let mut layouts = BTreeMap::new();
+    for module in all_modules(db) {
+        let name = module.krate().display_name(db).map(|it| it.to_string()).unwrap_or_default();
+        let krate = match target_kind(module.krate()) {
+            None | Some(TargetKind::Lib { .. }) => name,
+            Some(TargetKind::Bin) => format!("{name}[bin]"),
+            Some(TargetKind::Example) => format!("{name}[example]"),
+            Some(TargetKind::Test) => format!("{name}[test]"),
+            Some(TargetKind::Bench) => format!("{name}[bench]"),
+            Some(TargetKind::BuildScript) => format!("{name}[build-script]"),
+            Some(TargetKind::Other) => format!("{name}[other]"),
+        };
+        for def in module.declarations(db) {
+            let ModuleDef::Adt(adt) = def else { continue };
+            if !GenericDef::from(adt).type_or_const_params(db).is_empty() {
+                continue;
+            }
+            let Ok(layout) = adt.layout(db) else { continue };
+            layouts.insert(
+                format!("{krate}::{}", full_name_of_item(db, module, adt.name(db))),
+                TypeLayout { size: layout.size(), align: layout.align(), niches: layout.niches() },
+            );
+        }
+    }
+    layouts
+}
+
+/// Describes the differences between two dumps, and returns the number of types whose size grew.
+fn diff_layouts(
+    old: &BTreeMap<String, TypeLayout>,
+    new: &BTreeMap<String, TypeLayout>,
+) -> (String, usize) {
+    let mut report = String::new();
+    let mut grown = 0;
+    for (name, new_layout) in new {
+        let Some(old_layout) = old.get(name) else {
+            _ = writeln!(report, "added {name}: size {}", new_layout.size);
+            continue;
+        };
+        if old_layout == new_layout {
+            continue;
+        }
+        let mut changes = Vec::new();
+        if old_layout.size != new_layout.size {
+            if new_layout.size > old_layout.size {
+                grown += 1;
+            }
+            let delta = new_layout.size as i128 - old_layout.size as i128;
+            changes.push(format!("size {} -> {} ({delta:+})", old_layout.size, new_layout.size));
+        }
+        if old_layout.align != new_layout.align {
+            changes.push(format!("align {} -> {}", old_layout.align, new_layout.align));
+        }
+        if old_layout.niches != new_layout.niches {
+            let niches = |it: Option<u128>| it.unwrap_or(0);
+            changes.push(format!(
+                "niches {} -> {}",
+                niches(old_layout.niches),
+                niches(new_layout.niches)
+            ));
+        }
+        _ = writeln!(report, "changed {name}: {}", changes.join(", "));
+    }
+    for name in old.keys().filter(|name| !new.contains_key(*name)) {
+        _ = writeln!(report, "removed {name}");
+    }
+    if report.is_empty() {
+        report.push_str("no layout changes\n");
+    }
+    (report, grown)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+    use ide::AnalysisHost;
+    use test_fixture::ChangeFixture;
+
+    use super::*;
+
+    fn check_dump(#[rust_analyzer::rust_fixture] ra_fixture: &str, expect: Expect) {
+        let mut host = AnalysisHost::default();
+        host.raw_database_mut().apply_change(ChangeFixture::parse(ra_fixture).change);
+        let db = host.raw_database();
+        // The crate called `app` stands for a binary.
+        let target_kind = |krate: Crate| {
+            let name = krate.display_name(db)?;
+            (name.to_string() == "app").then_some(TargetKind::Bin)
+        };
+        let layouts = collect_layouts(db, &target_kind);
+        expect.assert_eq(&serde_json::to_string_pretty(&layouts).unwrap());
+    }
+
+    fn check_diff(old: &str, new: &str, expect: Expect) {
+        let old = serde_json::from_str(old).unwrap();
+        let new = serde_json::from_str(new).unwrap();
+        let (report, grown) = diff_layouts(&old, &new);
+        expect.assert_eq(&format!("{report}grown: {grown}\n"));
+    }
+
+    #[test]
+    fn dump_non_generic_types() {
+        check_dump(
+            r#"
+//- /lib.rs crate:foo
+pub struct Event {
+    pub id: u32,
+    pub kind: Kind,
+}
+
+pub enum Kind {
+    Start,
+    Stop(bool),
+}
+
+pub struct Borrowed<'a>(&'a u8);
+
+pub struct Generic<T>(T);
+
+mod inner {
+    pub union Bits {
+        a: u64,
+        b: f32,
+    }
+}
+"#,
+            expect![[r#"
+                {
+                  "foo::Borrowed": {
+                    "size": 8,
+                    "align": 8,
+                    "niches": 1
+                  },
+                  "foo::Event": {
+                    "size": 8,
+                    "align": 4,
+                    "niches": 253
+                  },
+                  "foo::Kind": {
+                    "size": 1,
+                    "align": 1,
+                    "niches": 253
+                  },
+                  "foo::inner::Bits": {
+                    "size": 8,
+                    "align": 8,
+                    "niches": null
+                  }
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn dump_binary_types() {
+        check_dump(
+            r#"
+//- /lib.rs crate:foo
+pub struct Event(u32);
+//- /main.rs crate:app deps:foo
+struct Config(foo::Event, u8);
+"#,
+            expect![[r#"
+                {
+                  "app[bin]::Config": {
+                    "size": 8,
+                    "align": 4,
+                    "niches": null
+                  },
+                  "foo::Event": {
+                    "size": 4,
+                    "align": 4,
+                    "niches": null
+                  }
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn diff_dumps() {
+        check_diff(
+            r#"{
+                "foo::Event": { "size": 64, "align": 8, "niches": null },
+                "foo::Kind": { "size": 1, "align": 1, "niches": 253 },
+                "foo::Old": { "size": 4, "align": 4, "niches": null },
+                "foo::Same": { "size": 4, "align": 4, "niches": null }
+            }"#,
+            r#"{
+                "foo::Event": { "size": 72, "align": 8, "niches": null },
+                "foo::Kind": { "size": 1, "align": 1, "niches": 252 },
+                "foo::New": { "size": 2, "align": 2, "niches": null },
+                "foo::Same": { "size": 4, "align": 4, "niches": null }
+            }"#,
+            expect![[r#"
+                changed foo::Event: size 64 -> 72 (+8)
+                changed foo::Kind: niches 253 -> 252
+                added foo::New: size 2
+                removed foo::Old
+                grown: 1
+            "#]],
+        );
+        check_diff(
+            r#"{ "foo::Event": { "size": 64, "align": 8, "niches": null } }"#,
+            r#"{ "foo::Event": { "size": 56, "align": 4, "niches": null } }"#,
+            expect![[r#"
+                changed foo::Event: size 64 -> 56 (-8), align 8 -> 4
+                grown: 0
+            "#]],
+        );
+    }
+}
@@ -1,7 +1,5 @@
 //! Run all tests in a project, similar to `cargo test`, but using the mir interpreter.
 
-use hir::{Crate, Module};
-use hir_ty::db::HirDatabase;
 use ide_db::{base_db::SourceDatabase, LineIndexDatabase};
 use profile::StopWatch;
 use project_model::{CargoConfig, RustLibSource};
@@ -9,7 +7,7 @@ use syntax::TextRange;
 
 use load_cargo::{load_workspace_at, LoadCargoConfig, ProcMacroServerChoice};
 
-use crate::cli::{flags, full_name_of_item, Result};
+use crate::cli::{all_modules, flags, full_name_of_item, Result};
 
 impl flags::RunTests {
     pub fn run(self) -> Result<()> {
@@ -60,7 +58,7 @@ impl flags::RunTests {
                 continue;
             }
             let mut sw_one = StopWatch::start();
-            let result = test.eval(db, span_formatter);
+            let result = test.eval(db, span_formatter, self.check_ub);
             match &result {
                 Ok(result) if result.trim() == "pass" => pass_count += 1,
                 _ => fail_count += 1,
@@ -73,19 +71,3 @@ impl flags::RunTests {
         Ok(())
     }
 }
-
-fn all_modules(db: &dyn HirDatabase) -> Vec<Module> {
-    let mut worklist: Vec<_> = Crate::all(db)
-        .into_iter()
-        .filter(|x| x.origin(db).is_local())
-        .map(|krate| krate.root_module())
-        .collect();
-    let mut modules = Vec::new();
-
-    while let Some(module) = worklist.pop() {
-        modules.push(module);
-        worklist.extend(module.children(db));
-    }
-
-    modules
-}
@@ -0,0 +1,69 @@
+//! Persists how often completions were accepted, see [`CompletionUsageStats`].
+//!
+//! The counts are stored as a JSON object mapping completion contexts to the labels accepted in
+//! them and their counts.
+
+use std::{collections::BTreeMap, fs, io};
+
+use ide::CompletionUsageStats;
+use paths::AbsPath;
+
+pub(crate) fn load(path: &AbsPath) -> CompletionUsageStats {
+    let mut stats = CompletionUsageStats::default();
+    let text = match fs::read_to_string(path) {
+        Ok(it) => it,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return stats,
+        Err(e) => {
+            tracing::error!("failed to read completion usage from {path}: {e}");
+            return stats;
+        }
+    };
+    match serde_json::from_str::<BTreeMap<String, BTreeMap<String, u32>>>(&text) {
+        Ok(counts) => {
+            for (context, labels) in &counts {
+                for (label, &count) in labels {
+                    stats.record(context, label, count);
+                }
+            }
+        }
+        Err(e) => tracing::error!("failed to parse completion usage from {path}: {e}"),
+    }
+    stats
+}
+
+pub(crate) fn save(path: &AbsPath, stats: &CompletionUsageStats) -> io::Result<()> {
+    let mut counts = BTreeMap::<&str, BTreeMap<&str, u32>>::new();
+    for (context, label, count) in stats.iter() {
+        counts.entry(context).or_default().insert(label, count);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&counts)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use paths::AbsPathBuf;
+
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let dir = AbsPathBuf::assert_utf8(std::env::temp_dir())
+            .join(format!("ra-completion-usage-{}", std::process::id()));
+        let path = dir.join("completion-usage.json");
+
+        let mut stats = CompletionUsageStats::default();
+        stats.record(".vec::Vec", "push", 2);
+        stats.record(".vec::Vec", "len", 1);
+        stats.record("expr", "foo", 1);
+        save(&path, &stats).unwrap();
+        let loaded = load(&path);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded, stats);
+        assert_eq!(loaded.count(".vec::Vec", "push"), 2);
+        assert_eq!(loaded.count("expr", "push"), 0);
+    }
+}
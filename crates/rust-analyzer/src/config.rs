@@ -9,10 +9,10 @@ use cfg::{CfgAtom, CfgDiff};
 use hir::Symbol;
 use ide::{
     AssistConfig, CallHierarchyConfig, CallableSnippets, CompletionConfig,
-    CompletionFieldsToResolve, DiagnosticsConfig, ExprFillDefaultMode, GenericParameterHints,
-    HighlightConfig, HighlightRelatedConfig, HoverConfig, HoverDocFormat, InlayFieldsToResolve,
-    InlayHintsConfig, JoinLinesConfig, MemoryLayoutHoverConfig, MemoryLayoutHoverRenderKind,
-    Snippet, SnippetScope, SourceRootId,
+    CompletionFieldsToResolve, DeriveHelperArg, DiagnosticsConfig, ExprFillDefaultMode,
+    GenericParameterHints, HighlightConfig, HighlightRelatedConfig, HoverConfig, HoverDocFormat,
+    InlayFieldsToResolve, InlayHintsConfig, JoinLinesConfig, MemoryLayoutHoverConfig,
+    MemoryLayoutHoverRenderKind, Snippet, SnippetScope, SourceRootId,
 };
 use ide_db::{
     imports::insert_use::{ImportGranularity, InsertUseConfig, PrefixKind},
@@ -21,7 +21,7 @@ use ide_db::{
 use itertools::{Either, Itertools};
 use paths::{Utf8Path, Utf8PathBuf};
 use project_model::{
-    CargoConfig, CargoFeatures, ProjectJson, ProjectJsonData, ProjectJsonFromCommand,
+    CargoConfig, CargoFeatures, ManifestPath, ProjectJson, ProjectJsonData, ProjectJsonFromCommand,
     ProjectManifest, RustLibSource,
 };
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -84,7 +84,17 @@ config_data! {
 
         /// Custom completion snippets.
         completion_snippets_custom: FxIndexMap<String, SnippetDef> = Config::completion_snippets_default(),
-
+        /// Whether to rank completions by how often they were accepted before in the same
+        /// context, like the methods called on a type. Accepted completions are counted in
+        /// `.rust-analyzer/completion-usage.json` in the workspace root.
+        completion_usageRanking_enable: bool = false,
+
+        /// Whether to analyze opened files that don't belong to any crate of the loaded
+        /// workspaces as standalone crates, without reloading the workspaces.
+        files_adHocCrates_enable: bool = false,
+        /// Whether ad hoc crates of files within a package of a loaded Cargo workspace get
+        /// access to that package's library and dependencies, like an example target would.
+        files_adHocCrates_packageDependencies: bool = true,
 
         /// These paths (file/directories) will be ignored by rust-analyzer. They are
         /// relative to the workspace root, and globs are not supported. You may
@@ -109,6 +119,9 @@ config_data! {
         hover_actions_debug_enable: bool           = true,
         /// Whether to show HoverActions in Rust files.
         hover_actions_enable: bool          = true,
+        /// Whether to show `Explain type` action on expressions whose type could not be inferred
+        /// or doesn't match. Only applies when `#rust-analyzer.hover.actions.enable#` is set.
+        hover_actions_explainType_enable: bool     = false,
         /// Whether to show `Go to Type Definition` action. Only applies when
         /// `#rust-analyzer.hover.actions.enable#` is set.
         hover_actions_gotoTypeDef_enable: bool     = true,
@@ -173,6 +186,9 @@ config_data! {
         inlayHints_closureReturnTypeHints_enable: ClosureReturnTypeHintsDef  = ClosureReturnTypeHintsDef::Never,
         /// Closure notation in type and chaining inlay hints.
         inlayHints_closureStyle: ClosureStyle                                = ClosureStyle::ImplFn,
+        /// Whether to show the evaluated values of const items, array lengths, `size_of` calls
+        /// and associated consts. Evaluations that take too long are skipped.
+        inlayHints_constValueHints_enable: bool                              = false,
         /// Whether to show enum variant discriminant hints.
         inlayHints_discriminantHints_enable: DiscriminantHintsDef            = DiscriminantHintsDef::Never,
         /// Whether to show inlay hints for type adjustments.
@@ -217,7 +233,13 @@ config_data! {
         /// Whether to hide inlay type hints for constructors.
         inlayHints_typeHints_hideNamedConstructor: bool            = false,
 
-        /// Enables the experimental support for interpreting tests.
+        /// Whether the interpreter should check for undefined behavior, like out of bounds
+        /// accesses, use after free and invalid values, when interpreting functions and tests.
+        /// This makes the interpretation slower.
+        interpret_checkUndefinedBehavior: bool = false,
+        /// Enables the experimental support for interpreting tests. This adds an "Interpret" code
+        /// lens to tests and makes the test explorer run the selected tests with the interpreter
+        /// in-process, instead of running `cargo test`.
         interpret_tests: bool = false,
 
         /// Join lines merges consecutive declaration and initialization of an assignment.
@@ -486,6 +508,13 @@ config_data! {
         completion_autoself_enable: bool        = true,
         /// Whether to add parenthesis and argument snippets when completing function.
         completion_callable_snippets: CallableCompletionDef  = CallableCompletionDef::FillArguments,
+        /// Known arguments of derive helper attributes, completed inside a helper attribute when
+        /// the derive declaring it is applied to the item.
+        ///
+        /// Maps helper attribute names to their keys. Keys mapped to `null` are flags like
+        /// `#[serde(default)]`, keys mapped to a list take a string value like
+        /// `#[serde(rename_all = "camelCase")]` and the list holds the values to complete.
+        completion_deriveHelpers_arguments: FxIndexMap<String, FxIndexMap<String, Option<Vec<String>>>> = Config::completion_derive_helpers_default(),
         /// A list of full paths to traits whose methods to exclude from completion.
         ///
         /// Methods from these traits won't be completed, even if the trait is in scope. However, they will still be suggested on expressions whose type is `dyn Trait`, `impl Trait` or `T where T: Trait`.
@@ -506,6 +535,9 @@ config_data! {
         completion_termSearch_enable: bool = false,
         /// Term search fuel in "units of work" for autocompletion (Defaults to 1000).
         completion_termSearch_fuel: usize = 1000,
+        /// Whether to insert the default implementation from the trait instead of an empty body
+        /// when completing a missing trait method in an `impl` block.
+        completion_traitImplDefaultBody_enable: bool = false,
 
         /// List of rust-analyzer diagnostics to disable.
         diagnostics_disabled: FxHashSet<String> = FxHashSet::default(),
@@ -556,6 +588,10 @@ config_data! {
         /// Automatically refresh project info via `cargo metadata` on
         /// `Cargo.toml` or `.cargo/config.toml` changes.
         cargo_autoreload: bool           = true,
+        /// Persist the outputs of build scripts and proc-macro builds in the target directory, and
+        /// use the outputs of the previous session on startup while the build scripts are rerun
+        /// in the background.
+        cargo_buildScripts_cache_enable: bool = false,
         /// Run build scripts (`build.rs`) for more precise code analysis.
         cargo_buildScripts_enable: bool  = true,
         /// Specifies the invocation strategy to use when running the build scripts command.
@@ -609,6 +645,11 @@ config_data! {
         cargo_features: CargoFeaturesDef      = CargoFeaturesDef::Selected(vec![]),
         /// Whether to pass `--no-default-features` to cargo.
         cargo_noDefaultFeatures: bool    = false,
+        /// Whether to run cargo without accessing the network, by passing `--offline` to
+        /// `cargo metadata` and the build script invocation. If `cargo metadata` fails,
+        /// dependencies are resolved from `Cargo.lock` and the sources vendored through the
+        /// source replacement in `.cargo/config.toml`.
+        cargo_offline: bool              = false,
         /// Relative path to the sysroot, or "discover" to try to automatically find it via
         /// "rustc --print sysroot".
         ///
@@ -616,12 +657,20 @@ config_data! {
         ///
         /// This option does not take effect until rust-analyzer is restarted.
         cargo_sysroot: Option<String>    = Some("discover".to_owned()),
+        /// Relative path to a file in the `rust-project.json` format describing the sysroot
+        /// crates, for example a patched `core` and `alloc` built with `-Zbuild-std`. Paths in
+        /// it are relative to the file, and each crate may list its own `cfg`s and `target`.
+        /// Takes precedence over `#rust-analyzer.cargo.sysrootSrc#`.
+        ///
+        /// This option does not take effect until rust-analyzer is restarted.
+        cargo_sysrootProject: Option<String>    = None,
         /// Relative path to the sysroot library sources. If left unset, this will default to
         /// `{cargo.sysroot}/lib/rustlib/src/rust/library`.
         ///
         /// This option does not take effect until rust-analyzer is restarted.
         cargo_sysrootSrc: Option<String>    = None,
-        /// Compilation target override (target tuple).
+        /// Compilation target override (target tuple), or path to a custom target specification
+        /// JSON file. The data layout and cfgs of a custom target are read from the file.
         // FIXME(@poliorcetics): move to multiple targets here too, but this will need more work
         // than `checkOnSave_target`
         cargo_target: Option<String>     = None,
@@ -822,6 +871,9 @@ pub struct Config {
 
     ratoml_file: FxHashMap<SourceRootId, (RatomlFile, ConfigErrors)>,
 
+    /// Snippets defined in the `.rust-analyzer/snippets.toml` file of a source root.
+    snippet_files: FxHashMap<SourceRootId, (Vec<Snippet>, ConfigErrors)>,
+
     /// Clone of the value that is stored inside a `GlobalState`.
     source_root_parent_map: Arc<FxHashMap<SourceRootId, SourceRootId>>,
 
@@ -847,6 +899,7 @@ impl fmt::Debug for Config {
             .field("client_config", &self.client_config)
             .field("user_config", &self.user_config)
             .field("ratoml_file", &self.ratoml_file)
+            .field("snippet_files", &self.snippet_files)
             .field("source_root_parent_map", &self.source_root_parent_map)
             .field("validation_errors", &self.validation_errors)
             .field("detached_files", &self.detached_files)
@@ -948,25 +1001,11 @@ impl Config {
                     if def.prefix.is_empty() && def.postfix.is_empty() {
                         continue;
                     }
-                    let scope = match def.scope {
-                        SnippetScopeDef::Expr => SnippetScope::Expr,
-                        SnippetScopeDef::Type => SnippetScope::Type,
-                        SnippetScopeDef::Item => SnippetScope::Item,
-                    };
-                    match Snippet::new(
-                        &def.prefix,
-                        &def.postfix,
-                        &def.body,
-                        def.description.as_ref().unwrap_or(name),
-                        &def.requires,
-                        scope,
-                    ) {
-                        Some(snippet) => config.snippets.push(snippet),
-                        None => json_errors.push((
+                    match def.to_snippet(name) {
+                        Ok(snippet) => config.snippets.push(snippet),
+                        Err(e) => json_errors.push((
                             name.to_owned(),
-                            <serde_json::Error as serde::de::Error>::custom(format!(
-                                "snippet {name} is invalid or triggers are missing",
-                            )),
+                            <serde_json::Error as serde::de::Error>::custom(e),
                         )),
                     }
                 }
@@ -1081,6 +1120,39 @@ impl Config {
             }
         }
 
+        if let Some(change) = change.snippets_file_change {
+            for (source_root_id, text) in change {
+                let Some(text) = text else {
+                    config.snippet_files.remove(&source_root_id);
+                    continue;
+                };
+                tracing::info!("updating snippets file: {:#}", text);
+                match toml::from_str::<FxIndexMap<String, SnippetDef>>(&text) {
+                    Ok(defs) => {
+                        let mut snippets = Vec::with_capacity(defs.len());
+                        let mut errors = vec![];
+                        for (name, def) in defs.iter() {
+                            match def.to_snippet(name) {
+                                Ok(snippet) => snippets.push(snippet),
+                                Err(e) => errors.push(Arc::new(ConfigErrorInner::Toml {
+                                    config_key: name.to_owned(),
+                                    error: <toml::de::Error as serde::de::Error>::custom(e),
+                                })),
+                            }
+                        }
+                        config
+                            .snippet_files
+                            .insert(source_root_id, (snippets, ConfigErrors(errors)));
+                    }
+                    Err(e) => {
+                        config.validation_errors.0.push(
+                            ConfigErrorInner::ParseError { reason: e.message().to_owned() }.into(),
+                        );
+                    }
+                }
+            }
+        }
+
         if let Some(source_root_map) = change.source_map_change {
             config.source_root_parent_map = source_root_map;
         }
@@ -1108,6 +1180,7 @@ impl Config {
                 .iter()
                 .chain(config.user_config.as_ref().into_iter().flat_map(|it| it.1 .0.iter()))
                 .chain(config.ratoml_file.values().flat_map(|it| it.1 .0.iter()))
+                .chain(config.snippet_files.values().flat_map(|it| it.1 .0.iter()))
                 .chain(config.validation_errors.0.iter())
                 .cloned()
                 .collect(),
@@ -1137,6 +1210,7 @@ pub struct ConfigChange {
     client_config_change: Option<serde_json::Value>,
     ratoml_file_change:
         Option<FxHashMap<SourceRootId, (RatomlFileKind, VfsPath, Option<Arc<str>>)>>,
+    snippets_file_change: Option<FxHashMap<SourceRootId, Option<Arc<str>>>>,
     source_map_change: Option<Arc<FxHashMap<SourceRootId, SourceRootId>>>,
 }
 
@@ -1168,6 +1242,12 @@ impl ConfigChange {
             .insert(source_root, (RatomlFileKind::Workspace, vfs_path, content))
     }
 
+    /// Records the new contents of the `.rust-analyzer/snippets.toml` file of `source_root`,
+    /// `None` if it was removed.
+    pub fn change_snippets_file(&mut self, source_root: SourceRootId, content: Option<Arc<str>>) {
+        self.snippets_file_change.get_or_insert_with(Default::default).insert(source_root, content);
+    }
+
     pub fn change_client_config(&mut self, change: serde_json::Value) {
         self.client_config_change = Some(change);
     }
@@ -1282,6 +1362,7 @@ pub struct HoverActionsConfig {
     pub debug: bool,
     pub update_test: bool,
     pub goto_type_def: bool,
+    pub explain_type: bool,
 }
 
 impl HoverActionsConfig {
@@ -1292,10 +1373,15 @@ impl HoverActionsConfig {
         debug: false,
         update_test: false,
         goto_type_def: false,
+        explain_type: false,
     };
 
     pub fn any(&self) -> bool {
-        self.implementations || self.references || self.runnable() || self.goto_type_def
+        self.implementations
+            || self.references
+            || self.runnable()
+            || self.goto_type_def
+            || self.explain_type
     }
 
     pub fn none(&self) -> bool {
@@ -1357,7 +1443,9 @@ pub struct ClientCommandsConfig {
     pub debug_single: bool,
     pub show_reference: bool,
     pub goto_location: bool,
+    pub explain_type: bool,
     pub trigger_parameter_hints: bool,
+    pub completion_accepted: bool,
     pub rename: bool,
 }
 
@@ -1425,6 +1513,7 @@ impl Config {
             detached_files: Default::default(),
             validation_errors: Default::default(),
             ratoml_file: Default::default(),
+            snippet_files: Default::default(),
         }
     }
 
@@ -1492,6 +1581,20 @@ impl Config {
         CallHierarchyConfig { exclude_tests: self.references_excludeTests().to_owned() }
     }
 
+    /// The client's snippets, followed by the ones of the snippet files of `source_root` and the
+    /// source roots containing it.
+    fn snippets(&self, source_root: Option<SourceRootId>) -> Vec<Snippet> {
+        let mut snippets = self.snippets.clone();
+        let mut source_root = source_root;
+        while let Some(sr) = source_root {
+            if let Some((file_snippets, _)) = self.snippet_files.get(&sr) {
+                snippets.extend(file_snippets.iter().cloned());
+            }
+            source_root = self.source_root_parent_map.get(&sr).copied();
+        }
+        snippets
+    }
+
     pub fn completion(&self, source_root: Option<SourceRootId>) -> CompletionConfig<'_> {
         let client_capability_fields = self.completion_resolve_support_properties();
         CompletionConfig {
@@ -1505,6 +1608,9 @@ impl Config {
             full_function_signatures: self
                 .completion_fullFunctionSignatures_enable(source_root)
                 .to_owned(),
+            trait_impl_default_body: self
+                .completion_traitImplDefaultBody_enable(source_root)
+                .to_owned(),
             callable: match self.completion_callable_snippets(source_root) {
                 CallableCompletionDef::FillArguments => Some(CallableSnippets::FillArguments),
                 CallableCompletionDef::AddParentheses => Some(CallableSnippets::AddParentheses),
@@ -1516,7 +1622,7 @@ impl Config {
             prefer_no_std: self.imports_preferNoStd(source_root).to_owned(),
             prefer_prelude: self.imports_preferPrelude(source_root).to_owned(),
             prefer_absolute: self.imports_prefixExternPrelude(source_root).to_owned(),
-            snippets: self.snippets.clone().to_vec(),
+            snippets: self.snippets(source_root),
             limit: self.completion_limit(source_root).to_owned(),
             enable_term_search: self.completion_termSearch_enable(source_root).to_owned(),
             term_search_fuel: self.completion_termSearch_fuel(source_root).to_owned() as u64,
@@ -1546,9 +1652,27 @@ impl Config {
                 })
                 .collect(),
             exclude_traits: self.completion_excludeTraits(source_root),
+            usage_stats: None,
+            derive_helper_args: self
+                .completion_deriveHelpers_arguments(source_root)
+                .iter()
+                .flat_map(|(helper, args)| {
+                    args.iter().map(|(key, values)| DeriveHelperArg {
+                        helper: helper.clone(),
+                        key: key.clone(),
+                        values: values.clone(),
+                    })
+                })
+                .collect(),
         }
     }
 
+    /// The file accepted completions are counted in, if usage based ranking is enabled.
+    pub fn completion_usage_stats_path(&self) -> Option<AbsPathBuf> {
+        self.completion_usageRanking_enable()
+            .then(|| self.root_path.join(".rust-analyzer").join("completion-usage.json"))
+    }
+
     pub fn completion_hide_deprecated(&self) -> bool {
         *self.completion_hideDeprecated(None)
     }
@@ -1605,6 +1729,7 @@ impl Config {
                 && self.hover_actions_run_enable().to_owned()
                 && self.hover_actions_updateTest_enable().to_owned(),
             goto_type_def: enable && self.hover_actions_gotoTypeDef_enable().to_owned(),
+            explain_type: enable && self.hover_actions_explainType_enable().to_owned(),
         }
     }
 
@@ -1688,6 +1813,7 @@ impl Config {
                 ClosureStyle::Hide => hir::ClosureStyle::Hide,
             },
             closure_capture_hints: self.inlayHints_closureCaptureHints_enable().to_owned(),
+            const_value_hints: self.inlayHints_constValueHints_enable().to_owned(),
             adjustment_hints: match self.inlayHints_expressionAdjustmentHints_enable() {
                 AdjustmentHintsDef::Always => ide::AdjustmentHints::Always,
                 AdjustmentHintsDef::Never => match self.inlayHints_reborrowHints_enable() {
@@ -1928,6 +2054,14 @@ impl Config {
         }
     }
 
+    pub fn ad_hoc_crates(&self) -> bool {
+        *self.files_adHocCrates_enable()
+    }
+
+    pub fn ad_hoc_crates_package_dependencies(&self) -> bool {
+        *self.files_adHocCrates_packageDependencies()
+    }
+
     pub fn excluded(&self) -> impl Iterator<Item = AbsPathBuf> + use<'_> {
         self.files_exclude().iter().map(|it| self.root_path.join(it))
     }
@@ -1963,6 +2097,10 @@ impl Config {
         });
         let sysroot_src =
             self.cargo_sysrootSrc(source_root).as_ref().map(|sysroot| self.root_path.join(sysroot));
+        let sysroot_project = self
+            .cargo_sysrootProject(source_root)
+            .as_ref()
+            .and_then(|project| ManifestPath::try_from(self.root_path.join(project)).ok());
         let extra_includes = self
             .vfs_extraIncludes(source_root)
             .iter()
@@ -1983,6 +2121,7 @@ impl Config {
             target: self.cargo_target(source_root).clone(),
             sysroot,
             sysroot_src,
+            sysroot_project,
             rustc_source,
             extra_includes,
             cfg_overrides: project_model::CfgOverrides {
@@ -2023,8 +2162,11 @@ impl Config {
                 InvocationStrategy::PerWorkspace => project_model::InvocationStrategy::PerWorkspace,
             },
             run_build_script_command: self.cargo_buildScripts_overrideCommand(source_root).clone(),
+            build_scripts_cache: *self.cargo_buildScripts_cache_enable(source_root)
+                && self.run_build_scripts(source_root),
             extra_args: self.cargo_extraArgs(source_root).clone(),
             extra_env: self.cargo_extraEnv(source_root).clone(),
+            offline: *self.cargo_offline(source_root),
             target_dir: self.target_dir_from_config(source_root),
             set_test: *self.cfg_setTest(source_root),
         }
@@ -2081,6 +2223,51 @@ impl Config {
         .unwrap()
     }
 
+    pub(crate) fn completion_derive_helpers_default(
+    ) -> FxIndexMap<String, FxIndexMap<String, Option<Vec<String>>>> {
+        serde_json::from_str(
+            r#"{
+            "serde": {
+                "alias": [],
+                "borrow": null,
+                "bound": [],
+                "content": [],
+                "crate": [],
+                "default": null,
+                "deny_unknown_fields": null,
+                "deserialize_with": [],
+                "flatten": null,
+                "from": [],
+                "into": [],
+                "other": null,
+                "remote": [],
+                "rename": [],
+                "rename_all": [
+                    "lowercase",
+                    "UPPERCASE",
+                    "PascalCase",
+                    "camelCase",
+                    "snake_case",
+                    "SCREAMING_SNAKE_CASE",
+                    "kebab-case",
+                    "SCREAMING-KEBAB-CASE"
+                ],
+                "serialize_with": [],
+                "skip": null,
+                "skip_deserializing": null,
+                "skip_serializing": null,
+                "skip_serializing_if": [],
+                "tag": [],
+                "transparent": null,
+                "try_from": [],
+                "untagged": null,
+                "with": []
+            }
+        }"#,
+        )
+        .unwrap()
+    }
+
     pub fn rustfmt(&self, source_root_id: Option<SourceRootId>) -> RustfmtConfig {
         match &self.rustfmt_overrideCommand(source_root_id) {
             Some(args) if !args.is_empty() => {
@@ -2243,6 +2430,14 @@ impl Config {
         }
     }
 
+    pub fn interpret_tests_in_test_explorer(&self) -> bool {
+        *self.interpret_tests()
+    }
+
+    pub fn interpret_check_ub(&self) -> bool {
+        *self.interpret_checkUndefinedBehavior()
+    }
+
     pub fn workspace_symbol(&self, source_root: Option<SourceRootId>) -> WorkspaceSymbolConfig {
         WorkspaceSymbolConfig {
             search_scope: match self.workspace_symbol_search_scope(source_root) {
@@ -2269,7 +2464,9 @@ impl Config {
             debug_single: get("rust-analyzer.debugSingle"),
             show_reference: get("rust-analyzer.showReferences"),
             goto_location: get("rust-analyzer.gotoLocation"),
+            explain_type: get("rust-analyzer.explainType"),
             trigger_parameter_hints: get("rust-analyzer.triggerParameterHints"),
+            completion_accepted: get("rust-analyzer.completionAccepted"),
             rename: get("rust-analyzer.rename"),
         }
     }
@@ -2403,6 +2600,7 @@ enum SnippetScopeDef {
     Expr,
     Item,
     Type,
+    Pattern,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -2428,6 +2626,62 @@ pub(crate) struct SnippetDef {
     description: Option<String>,
 
     scope: SnippetScopeDef,
+
+    /// Type paths constraining the placeholders of the body, by placeholder number.
+    #[serde(skip_serializing_if = "FxIndexMap::is_empty")]
+    placeholders: FxIndexMap<String, String>,
+
+    /// The trait path an enclosing impl has to implement for the snippet to apply.
+    #[serde(rename = "impl")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    impl_trait: Option<String>,
+
+    /// The trait path the receiver of a postfix snippet has to implement for it to apply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receiver_impl: Option<String>,
+
+    /// Whether the postfix body is a structural search and replace template.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    structural: bool,
+}
+
+impl SnippetDef {
+    fn to_snippet(&self, name: &str) -> Result<Snippet, String> {
+        let scope = match self.scope {
+            SnippetScopeDef::Expr => SnippetScope::Expr,
+            SnippetScopeDef::Type => SnippetScope::Type,
+            SnippetScopeDef::Item => SnippetScope::Item,
+            SnippetScopeDef::Pattern => SnippetScope::Pat,
+        };
+        let mut snippet = Snippet::new(
+            &self.prefix,
+            &self.postfix,
+            &self.body,
+            self.description.as_deref().unwrap_or(name),
+            &self.requires,
+            scope,
+        )
+        .ok_or_else(|| format!("snippet {name} is invalid or triggers are missing"))?;
+        for (index, ty) in &self.placeholders {
+            let index = index.parse().map_err(|_| {
+                format!("snippet {name} constrains an invalid placeholder `{index}`")
+            })?;
+            snippet.constrain_placeholder(index, ty);
+        }
+        if let Some(trait_) = &self.impl_trait {
+            snippet.require_impl_of(trait_);
+        }
+        if let Some(trait_) = &self.receiver_impl {
+            snippet.require_receiver_impl(trait_);
+        }
+        if self.structural {
+            if self.postfix.is_empty() {
+                return Err(format!("structural snippet {name} has no postfix triggers"));
+            }
+            snippet.make_structural();
+        }
+        Ok(snippet)
+    }
 }
 
 mod single_or_array {
@@ -3215,6 +3469,9 @@ fn field_props(field: &str, ty: &str, doc: &[&str], default: &str) -> serde_json
         "FxIndexMap<String, SnippetDef>" => set! {
             "type": "object",
         },
+        "FxIndexMap<String, FxIndexMap<String, Option<Vec<String>>>>" => set! {
+            "type": "object",
+        },
         "FxHashMap<String, String>" => set! {
             "type": "object",
         },
@@ -3847,4 +4104,53 @@ mod tests {
             matches!(config.flycheck(None), FlycheckConfig::CargoCommand { options, .. } if options.target_dir == Some(Utf8PathBuf::from("other_folder")))
         );
     }
+
+    #[test]
+    fn snippets_file_applies_to_nested_source_roots() {
+        let mut config =
+            Config::new(AbsPathBuf::assert(project_root()), Default::default(), vec![], None);
+
+        let mut change = ConfigChange::default();
+        change.change_snippets_file(
+            SourceRootId(0),
+            Some(
+                r#"
+["fmt write"]
+prefix = "fmtw"
+body = 'write!(${1:f}, "$0")'
+placeholders = { 1 = "core::fmt::Formatter" }
+impl = "core::fmt::Display"
+scope = "expr"
+
+["bad placeholder"]
+prefix = "bad"
+body = "$1"
+placeholders = { one = "u32" }
+"#
+                .into(),
+            ),
+        );
+        change.change_source_root_parent_map(Arc::new(FxHashMap::from_iter([(
+            SourceRootId(1),
+            SourceRootId(0),
+        )])));
+
+        let e;
+        (config, e, _) = config.apply_change(change);
+        expect_test::expect![[r#"
+            invalid config value:
+            bad placeholder: snippet bad placeholder constrains an invalid placeholder `one`
+        "#]]
+        .assert_eq(&e.to_string());
+
+        let snippets = config.completion(Some(SourceRootId(1))).snippets;
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(&*snippets[0].prefix_triggers, [Box::from("fmtw")]);
+        assert!(config.completion(Some(SourceRootId(2))).snippets.is_empty());
+
+        let mut change = ConfigChange::default();
+        change.change_snippets_file(SourceRootId(0), None);
+        (config, _, _) = config.apply_change(change);
+        assert!(config.completion(Some(SourceRootId(1))).snippets.is_empty());
+    }
 }
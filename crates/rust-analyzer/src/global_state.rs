@@ -3,11 +3,14 @@
 //!
 //! Each tick provides an immutable snapshot of the state as `WorldSnapshot`.
 
-use std::{ops::Not as _, time::Instant};
+use std::{
+    ops::Not as _,
+    time::{Duration, Instant},
+};
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use hir::ChangeWithProcMacros;
-use ide::{Analysis, AnalysisHost, Cancellable, FileId, SourceRootId};
+use ide::{Analysis, AnalysisHost, Cancellable, CompletionUsageStats, FileId, SourceRootId};
 use ide_db::base_db::{Crate, ProcMacroPaths, SourceDatabase};
 use itertools::Itertools;
 use load_cargo::SourceRootConfig;
@@ -25,6 +28,7 @@ use triomphe::Arc;
 use vfs::{AbsPathBuf, AnchoredPathBuf, ChangeKind, Vfs, VfsPath};
 
 use crate::{
+    completion_usage,
     config::{Config, ConfigChange, ConfigErrors, RatomlFileKind},
     diagnostics::{CheckFixes, DiagnosticCollection},
     discover,
@@ -38,7 +42,7 @@ use crate::{
     reload,
     target_spec::{CargoTargetSpec, ProjectJsonTargetSpec, TargetSpec},
     task_pool::{TaskPool, TaskQueue},
-    test_runner::{CargoTestHandle, CargoTestMessage},
+    test_runner::{TestRunMessage, TestRunSession},
 };
 
 pub(crate) struct FetchWorkspaceRequest {
@@ -65,6 +69,9 @@ pub(crate) struct Handle<H, C> {
 pub(crate) type ReqHandler = fn(&mut GlobalState, lsp_server::Response);
 type ReqQueue = lsp_server::ReqQueue<(String, Instant), ReqHandler>;
 
+/// How often the completion usage stats are saved while completions are being accepted.
+const COMPLETION_USAGE_SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
 /// `GlobalState` is the primary mutable state of the language server
 ///
 /// The most interesting components are `vfs`, which stores a consistent
@@ -89,6 +96,12 @@ pub(crate) struct GlobalState {
     /// A mapping that maps a local source root's `SourceRootId` to it parent's `SourceRootId`, if it has one.
     pub(crate) local_roots_parent_map: Arc<FxHashMap<SourceRootId, SourceRootId>>,
     pub(crate) semantic_tokens_cache: Arc<Mutex<FxHashMap<Url, SemanticTokens>>>,
+    /// How often completions were accepted, loaded from and saved to
+    /// [`Config::completion_usage_stats_path`].
+    pub(crate) completion_usage: Arc<CompletionUsageStats>,
+    /// Whether `completion_usage` changed since it was last saved.
+    pub(crate) completion_usage_dirty: bool,
+    completion_usage_saved: Instant,
 
     // status
     pub(crate) shutdown_requested: bool,
@@ -105,9 +118,11 @@ pub(crate) struct GlobalState {
     pub(crate) last_flycheck_error: Option<String>,
 
     // Test explorer
-    pub(crate) test_run_session: Option<Vec<CargoTestHandle>>,
-    pub(crate) test_run_sender: Sender<CargoTestMessage>,
-    pub(crate) test_run_receiver: Receiver<CargoTestMessage>,
+    pub(crate) test_run_session: Option<TestRunSession>,
+    /// The id of the latest test run session, messages of other sessions are ignored.
+    pub(crate) test_run_id: u32,
+    pub(crate) test_run_sender: Sender<TestRunMessage>,
+    pub(crate) test_run_receiver: Receiver<TestRunMessage>,
     pub(crate) test_run_remaining_jobs: usize,
 
     // Project loading
@@ -154,6 +169,13 @@ pub(crate) struct GlobalState {
     pub(crate) workspaces: Arc<Vec<ProjectWorkspace>>,
     pub(crate) crate_graph_file_dependencies: FxHashSet<vfs::VfsPath>,
     pub(crate) detached_files: FxHashSet<ManifestPath>,
+    /// The frontmatter manifests of the detached files, as of the last time their save
+    /// triggered a workspace fetch. Used to only re-run `cargo metadata` for cargo scripts when
+    /// their manifest changes.
+    pub(crate) detached_file_manifests: FxHashMap<ManifestPath, Option<String>>,
+    /// Opened files that belong to no crate of the loaded workspaces, added to the crate graph as
+    /// ad hoc crates until they are closed or become a module of another crate.
+    pub(crate) ad_hoc_files: FxHashSet<ManifestPath>,
 
     // op queues
     pub(crate) fetch_workspaces_queue: OpQueue<FetchWorkspaceRequest, FetchWorkspaceResponse>,
@@ -181,6 +203,7 @@ pub(crate) struct GlobalStateSnapshot {
     pub(crate) check_fixes: CheckFixes,
     mem_docs: MemDocs,
     pub(crate) semantic_tokens_cache: Arc<Mutex<FxHashMap<Url, SemanticTokens>>>,
+    pub(crate) completion_usage: Arc<CompletionUsageStats>,
     vfs: Arc<RwLock<(vfs::Vfs, IntMap<FileId, LineEndings>)>>,
     pub(crate) workspaces: Arc<Vec<ProjectWorkspace>>,
     // used to signal semantic highlighting to fall back to syntax based highlighting until
@@ -237,6 +260,14 @@ impl GlobalState {
             diagnostics: Default::default(),
             mem_docs: MemDocs::default(),
             semantic_tokens_cache: Arc::new(Default::default()),
+            completion_usage: Arc::new(
+                config
+                    .completion_usage_stats_path()
+                    .map(|path| completion_usage::load(&path))
+                    .unwrap_or_default(),
+            ),
+            completion_usage_dirty: false,
+            completion_usage_saved: Instant::now(),
             shutdown_requested: false,
             last_reported_status: lsp_ext::ServerStatusParams {
                 health: lsp_ext::Health::Ok,
@@ -257,6 +288,7 @@ impl GlobalState {
             last_flycheck_error: None,
 
             test_run_session: None,
+            test_run_id: 0,
             test_run_sender,
             test_run_receiver,
             test_run_remaining_jobs: 0,
@@ -275,6 +307,8 @@ impl GlobalState {
             workspaces: Arc::from(Vec::new()),
             crate_graph_file_dependencies: FxHashSet::default(),
             detached_files: FxHashSet::default(),
+            detached_file_manifests: FxHashMap::default(),
+            ad_hoc_files: FxHashSet::default(),
             fetch_workspaces_queue: OpQueue::default(),
             fetch_build_data_queue: OpQueue::default(),
             fetch_proc_macros_queue: OpQueue::default(),
@@ -298,6 +332,7 @@ impl GlobalState {
         // mapping is not ready until `AnalysisHost::apply_changes` has been called.
         let mut modified_ratoml_files: FxHashMap<FileId, (ChangeKind, vfs::VfsPath)> =
             FxHashMap::default();
+        let mut modified_snippet_files: FxHashMap<FileId, ChangeKind> = FxHashMap::default();
 
         let (change, modified_rust_files, workspace_structure_change) = {
             let mut change = ChangeWithProcMacros::new();
@@ -322,6 +357,9 @@ impl GlobalState {
                     // Remember ids to use them after `apply_changes`
                     modified_ratoml_files.insert(file.file_id, (file.kind(), vfs_path.clone()));
                 }
+                if is_snippets_file(vfs_path) {
+                    modified_snippet_files.insert(file.file_id, file.kind());
+                }
 
                 if let Some(path) = vfs_path.as_path() {
                     has_structure_changes |= file.is_created_or_deleted();
@@ -393,6 +431,7 @@ impl GlobalState {
         let _p = span!(Level::INFO, "GlobalState::process_changes/apply_change").entered();
         self.analysis_host.apply_change(change);
         if !modified_ratoml_files.is_empty()
+            || !modified_snippet_files.is_empty()
             || !self.config.same_source_root_parent_map(&self.local_roots_parent_map)
         {
             let config_change = {
@@ -474,6 +513,15 @@ impl GlobalState {
                         tracing::info!(%vfs_path, "Ignoring library rust-analyzer.toml");
                     }
                 }
+                for (file_id, change_kind) in modified_snippet_files {
+                    let source_root_id = db.file_source_root(file_id).source_root_id(db);
+                    if db.source_root(source_root_id).source_root(db).is_library {
+                        continue;
+                    }
+                    let text =
+                        (change_kind != ChangeKind::Delete).then(|| db.file_text(file_id).text(db));
+                    change.change_snippets_file(source_root_id, text);
+                }
                 change.change_source_root_parent_map(self.local_roots_parent_map.clone());
                 change
             };
@@ -495,6 +543,12 @@ impl GlobalState {
         // Or maybe instead of replacing that check, kick off a semantic one if the syntactic one
         // didn't find anything (to make up for the lack of precision).
         {
+            if !self.ad_hoc_files.is_empty() && !modified_rust_files.is_empty() {
+                _ = self
+                    .deferred_task_queue
+                    .sender
+                    .send(crate::main_loop::QueuedTask::CheckAdHocFiles);
+            }
             if !matches!(&workspace_structure_change, Some((.., true))) {
                 _ = self
                     .deferred_task_queue
@@ -527,12 +581,40 @@ impl GlobalState {
             check_fixes: Arc::clone(&self.diagnostics.check_fixes),
             mem_docs: self.mem_docs.clone(),
             semantic_tokens_cache: Arc::clone(&self.semantic_tokens_cache),
+            completion_usage: Arc::clone(&self.completion_usage),
             proc_macros_loaded: !self.config.expand_proc_macros()
                 || self.fetch_proc_macros_queue.last_op_result().copied().unwrap_or(false),
             flycheck: self.flycheck.clone(),
         }
     }
 
+    /// Saves the completion usage stats to `path` if they changed since they were last saved.
+    /// Unless `force` is set, they are saved on the task pool and at most once per
+    /// [`COMPLETION_USAGE_SAVE_INTERVAL`].
+    pub(crate) fn save_completion_usage(&mut self, path: Option<AbsPathBuf>, force: bool) {
+        let Some(path) = path else { return };
+        if !self.completion_usage_dirty
+            || !force && self.completion_usage_saved.elapsed() < COMPLETION_USAGE_SAVE_INTERVAL
+        {
+            return;
+        }
+        self.completion_usage_dirty = false;
+        self.completion_usage_saved = Instant::now();
+        let stats = Arc::clone(&self.completion_usage);
+        let save = move || {
+            if let Err(e) = completion_usage::save(&path, &stats) {
+                tracing::error!("failed to save completion usage to {path}: {e}");
+            }
+        };
+        if force {
+            save();
+        } else {
+            self.task_pool
+                .handle
+                .spawn_with_sender(stdx::thread::ThreadIntent::Worker, move |_| save());
+        }
+    }
+
     pub(crate) fn send_request<R: lsp_types::request::Request>(
         &mut self,
         params: R::Params,
@@ -792,3 +874,11 @@ pub(crate) fn vfs_path_to_file_id(
         vfs::FileExcluded::No => Ok(Some(file_id)),
     }
 }
+
+/// Whether `path` is a `.rust-analyzer/snippets.toml` file defining completion snippets.
+fn is_snippets_file(path: &VfsPath) -> bool {
+    path.name_and_extension() == Some(("snippets", Some("toml")))
+        && path
+            .parent()
+            .is_some_and(|dir| dir.name_and_extension() == Some((".rust-analyzer", None)))
+}
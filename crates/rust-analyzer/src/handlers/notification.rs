@@ -19,7 +19,7 @@ use crate::{
     flycheck::Target,
     global_state::{FetchWorkspaceRequest, GlobalState},
     lsp::{from_proto, utils::apply_document_changes},
-    lsp_ext::{self, RunFlycheckParams},
+    lsp_ext::{self, CompletionAcceptedParams, RunFlycheckParams},
     mem_docs::DocumentData,
     reload,
     target_spec::TargetSpec,
@@ -86,7 +86,7 @@ pub(crate) fn handle_did_open_text_document(
 
         let contents = params.text_document.text.into_bytes();
         state.vfs.write().0.set_file_contents(path, Some(contents));
-        if state.config.discover_workspace_config().is_some() {
+        if state.config.discover_workspace_config().is_some() || state.config.ad_hoc_crates() {
             tracing::debug!("queuing task");
             let _ = state
                 .deferred_task_queue
@@ -146,6 +146,9 @@ pub(crate) fn handle_did_close_text_document(
 
         if let Some(path) = path.as_path() {
             state.loader.handle.invalidate(path.to_path_buf());
+            if state.ad_hoc_files.remove(path) {
+                state.recreate_crate_graph(format!("ad hoc file closed {path}"));
+            }
         }
     }
     Ok(())
@@ -185,14 +188,23 @@ pub(crate) fn handle_did_save_text_document(
                         force_crate_graph_reload: false,
                     },
                 );
-            } else if state.detached_files.contains(path) {
-                state.fetch_workspaces_queue.request_op(
-                    format!("detached file saved {path}"),
-                    FetchWorkspaceRequest {
-                        path: Some(path.to_owned()),
-                        force_crate_graph_reload: false,
-                    },
-                );
+            } else if let Some(file) = state.detached_files.get(path) {
+                // Only the embedded manifest of cargo scripts affects the workspace
+                let manifest = snap
+                    .analysis
+                    .parse(file_id)?
+                    .frontmatter_token()
+                    .map(|it| it.text().to_owned());
+                if state.detached_file_manifests.get(path) != Some(&manifest) {
+                    state.detached_file_manifests.insert(file.clone(), manifest);
+                    state.fetch_workspaces_queue.request_op(
+                        format!("detached file manifest changed {path}"),
+                        FetchWorkspaceRequest {
+                            path: Some(path.to_owned()),
+                            force_crate_graph_reload: false,
+                        },
+                    );
+                }
             }
         }
 
@@ -471,3 +483,17 @@ pub(crate) fn handle_abort_run_test(state: &mut GlobalState, _: ()) -> anyhow::R
     }
     Ok(())
 }
+
+pub(crate) fn handle_completion_accepted(
+    state: &mut GlobalState,
+    params: CompletionAcceptedParams,
+) -> anyhow::Result<()> {
+    let _p = tracing::info_span!("handle_completion_accepted").entered();
+    if state.config.completion_usage_stats_path().is_none() {
+        return Ok(());
+    }
+    // Saved by the main loop, see `GlobalState::save_completion_usage`.
+    Arc::make_mut(&mut state.completion_usage).record(&params.context, &params.label, 1);
+    state.completion_usage_dirty = true;
+    Ok(())
+}
@@ -52,7 +52,7 @@ use crate::{
         FetchDependencyListResult, PositionOrRange, ViewCrateGraphParams, WorkspaceSymbolParams,
     },
     target_spec::{CargoTargetSpec, TargetSpec},
-    test_runner::{CargoTestHandle, TestTarget},
+    test_runner::{CargoTestHandle, InterpretTestHandle, TestRunSession, TestTarget},
     try_default,
 };
 
@@ -174,7 +174,31 @@ pub(crate) fn handle_interpret_function(
 ) -> anyhow::Result<String> {
     let _p = tracing::info_span!("handle_interpret_function").entered();
     let position = try_default!(from_proto::file_position(&snap, params)?);
-    let res = snap.analysis.interpret_function(position)?;
+    let res = snap.analysis.interpret_function(position, snap.config.interpret_check_ub())?;
+    Ok(res)
+}
+
+pub(crate) fn handle_evaluate_expression(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::EvaluateExpressionParams,
+) -> anyhow::Result<String> {
+    let _p = tracing::info_span!("handle_evaluate_expression").entered();
+    let file_id = try_default!(from_proto::file_id(&snap, &params.text_document.uri)?);
+    let line_index = snap.file_line_index(file_id)?;
+    let range = from_proto::text_range(&line_index, params.range)?;
+    let res = snap.analysis.evaluate_expression(FileRange { file_id, range })?;
+    Ok(res)
+}
+
+pub(crate) fn handle_explain_type(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::ExplainTypeParams,
+) -> anyhow::Result<Option<String>> {
+    let _p = tracing::info_span!("handle_explain_type").entered();
+    let file_id = try_default!(from_proto::file_id(&snap, &params.text_document.uri)?);
+    let line_index = snap.file_line_index(file_id)?;
+    let range = from_proto::text_range(&line_index, params.range)?;
+    let res = snap.analysis.explain_type(FileRange { file_id, range })?;
     Ok(res)
 }
 
@@ -217,6 +241,20 @@ pub(crate) fn handle_run_test(
     if let Some(_session) = state.test_run_session.take() {
         state.send_notification::<lsp_ext::EndRunTest>(());
     }
+    state.test_run_id = state.test_run_id.wrapping_add(1);
+    if state.config.interpret_tests_in_test_explorer() {
+        let handle = InterpretTestHandle::spawn(
+            state.analysis_host.analysis(),
+            params.include,
+            params.exclude.unwrap_or_default(),
+            state.config.interpret_check_ub(),
+            state.test_run_id,
+            state.test_run_sender.clone(),
+        )?;
+        state.test_run_remaining_jobs = 1;
+        state.test_run_session = Some(TestRunSession::Interpreter { _handle: handle });
+        return Ok(());
+    }
     // We detect the lowest common ancestor of all included tests, and
     // run it. We ignore excluded tests for now, the client will handle
     // it for us.
@@ -261,6 +299,7 @@ pub(crate) fn handle_run_test(
                 state.config.cargo_test_options(None),
                 cargo.workspace_root(),
                 test_target,
+                state.test_run_id,
                 state.test_run_sender.clone(),
             )?;
             handles.push(handle);
@@ -268,7 +307,7 @@ pub(crate) fn handle_run_test(
     }
     // Each process send finished signal twice, once for stdout and once for stderr
     state.test_run_remaining_jobs = 2 * handles.len();
-    state.test_run_session = Some(handles);
+    state.test_run_session = Some(TestRunSession::Cargo { _handles: handles });
     Ok(())
 }
 
@@ -1083,7 +1122,11 @@ pub(crate) fn handle_completion(
         context.and_then(|ctx| ctx.trigger_character).and_then(|s| s.chars().next());
 
     let source_root = snap.analysis.source_root_id(position.file_id)?;
-    let completion_config = &snap.config.completion(Some(source_root));
+    let mut completion_config = snap.config.completion(Some(source_root));
+    if snap.config.completion_usage_stats_path().is_some() {
+        completion_config.usage_stats = Some(&snap.completion_usage);
+    }
+    let completion_config = &completion_config;
     // FIXME: We should fix up the position when retrying the cancelled request instead
     position.offset = position.offset.min(line_index.index.len());
     let items = match snap.analysis.completions(
@@ -1109,6 +1152,35 @@ pub(crate) fn handle_completion(
     Ok(Some(completion_list.into()))
 }
 
+pub(crate) fn handle_inline_completion(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::InlineCompletionParams,
+) -> anyhow::Result<Option<lsp_types::InlineCompletionResponse>> {
+    let _p = tracing::info_span!("handle_inline_completion").entered();
+    let mut position =
+        try_default!(from_proto::file_position(&snap, params.text_document_position)?);
+    let line_index = snap.file_line_index(position.file_id)?;
+    let source_root = snap.analysis.source_root_id(position.file_id)?;
+    let completion_config = snap.config.completion(Some(source_root));
+    position.offset = position.offset.min(line_index.index.len());
+    let items = snap
+        .analysis
+        .statement_completions(&completion_config, position)?
+        .into_iter()
+        .map(|it| lsp_types::InlineCompletionItem {
+            insert_text: it.text,
+            filter_text: None,
+            range: Some(to_proto::range(&line_index, it.range)),
+            command: None,
+            insert_text_format: None,
+        })
+        .collect::<Vec<_>>();
+    if items.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(lsp_types::InlineCompletionResponse::Array(items)))
+}
+
 pub(crate) fn handle_completion_resolve(
     snap: GlobalStateSnapshot,
     mut original_completion: CompletionItem,
@@ -1139,6 +1211,9 @@ pub(crate) fn handle_completion_resolve(
 
     let mut forced_resolve_completions_config = snap.config.completion(Some(source_root));
     forced_resolve_completions_config.fields_to_resolve = CompletionFieldsToResolve::empty();
+    if snap.config.completion_usage_stats_path().is_some() {
+        forced_resolve_completions_config.usage_stats = Some(&snap.completion_usage);
+    }
 
     let position = FilePosition { file_id, offset };
     let Some(completions) = snap.analysis.completions(
@@ -2193,6 +2268,23 @@ fn runnable_action_links(
     Some(group)
 }
 
+fn explain_type_command_link(
+    snap: &GlobalStateSnapshot,
+    frange: &FileRange,
+) -> Option<lsp_ext::CommandLinkGroup> {
+    if !snap.config.hover_actions().explain_type || !snap.config.client_commands().explain_type {
+        return None;
+    }
+    let uri = to_proto::url(snap, frange.file_id);
+    let line_index = snap.file_line_index(frange.file_id).ok()?;
+    let range = to_proto::range(&line_index, frange.range);
+    let command = to_proto::command::explain_type(&uri, range);
+    Some(lsp_ext::CommandLinkGroup {
+        commands: vec![to_command_link(command, "Explain this type".into())],
+        ..Default::default()
+    })
+}
+
 fn goto_type_action_links(
     snap: &GlobalStateSnapshot,
     nav_targets: &[HoverGotoTypeData],
@@ -2227,6 +2319,7 @@ fn prepare_hover_actions(
             HoverAction::Reference(position) => show_ref_command_link(snap, position),
             HoverAction::Runnable(r) => runnable_action_links(snap, r.clone()),
             HoverAction::GoToType(targets) => goto_type_action_links(snap, targets),
+            HoverAction::ExplainType(frange) => explain_type_command_link(snap, frange),
         })
         .collect()
 }
@@ -166,6 +166,7 @@ fn integrated_completion_benchmark() {
             enable_term_search: true,
             term_search_fuel: 200,
             full_function_signatures: false,
+            trait_impl_default_body: false,
             callable: Some(CallableSnippets::FillArguments),
             snippet_cap: SnippetCap::new(true),
             insert_use: InsertUseConfig {
@@ -184,6 +185,8 @@ fn integrated_completion_benchmark() {
             fields_to_resolve: CompletionFieldsToResolve::empty(),
             exclude_flyimport: vec![],
             exclude_traits: &[],
+            derive_helper_args: Vec::new(),
+            usage_stats: None,
             enable_auto_await: true,
             enable_auto_iter: true,
         };
@@ -218,6 +221,7 @@ fn integrated_completion_benchmark() {
             enable_term_search: true,
             term_search_fuel: 200,
             full_function_signatures: false,
+            trait_impl_default_body: false,
             callable: Some(CallableSnippets::FillArguments),
             snippet_cap: SnippetCap::new(true),
             insert_use: InsertUseConfig {
@@ -236,6 +240,8 @@ fn integrated_completion_benchmark() {
             fields_to_resolve: CompletionFieldsToResolve::empty(),
             exclude_flyimport: vec![],
             exclude_traits: &[],
+            derive_helper_args: Vec::new(),
+            usage_stats: None,
             enable_auto_await: true,
             enable_auto_iter: true,
         };
@@ -268,6 +274,7 @@ fn integrated_completion_benchmark() {
             enable_term_search: true,
             term_search_fuel: 200,
             full_function_signatures: false,
+            trait_impl_default_body: false,
             callable: Some(CallableSnippets::FillArguments),
             snippet_cap: SnippetCap::new(true),
             insert_use: InsertUseConfig {
@@ -286,6 +293,8 @@ fn integrated_completion_benchmark() {
             fields_to_resolve: CompletionFieldsToResolve::empty(),
             exclude_flyimport: vec![],
             exclude_traits: &[],
+            derive_helper_args: Vec::new(),
+            usage_stats: None,
             enable_auto_await: true,
             enable_auto_iter: true,
         };
@@ -21,6 +21,7 @@ pub const MINIMUM_SUPPORTED_TOOLCHAIN_VERSION: semver::Version = semver::Version
 pub mod cli;
 
 mod command;
+mod completion_usage;
 mod diagnostics;
 mod discover;
 mod flycheck;
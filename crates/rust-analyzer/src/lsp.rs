@@ -121,6 +121,8 @@ pub(crate) fn completion_item_hash(item: &CompletionItem, is_ref_completion: boo
         hasher.update(detail);
     }
 
+    // NB: the usage count isn't hashed either, accepting other completions changes it between
+    // the completion and the resolve request.
     hash_completion_relevance(&mut hasher, &item.relevance);
 
     hasher.update([u8::from(item.ref_match.is_some())]);
@@ -172,7 +172,7 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
                 work_done_progress_options: WorkDoneProgressOptions { work_done_progress: None },
             },
         )),
-        inline_completion_provider: None,
+        inline_completion_provider: config.caps().inline_completion().then_some(OneOf::Left(true)),
     }
 }
 
@@ -406,6 +406,10 @@ impl ClientCapabilities {
         .unwrap_or_default()
     }
 
+    pub fn inline_completion(&self) -> bool {
+        (|| -> _ { self.0.text_document.as_ref()?.inline_completion.as_ref() })().is_some()
+    }
+
     pub fn text_document_diagnostic(&self) -> bool {
         (|| -> _ { self.0.text_document.as_ref()?.diagnostic.as_ref() })().is_some()
     }
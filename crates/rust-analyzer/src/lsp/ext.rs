@@ -146,6 +146,36 @@ impl Request for InterpretFunction {
     const METHOD: &'static str = "rust-analyzer/interpretFunction";
 }
 
+pub enum EvaluateExpression {}
+
+impl Request for EvaluateExpression {
+    type Params = EvaluateExpressionParams;
+    type Result = String;
+    const METHOD: &'static str = "experimental/evaluateExpression";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EvaluateExpressionParams {
+    pub text_document: TextDocumentIdentifier,
+    pub range: Range,
+}
+
+pub enum ExplainType {}
+
+impl Request for ExplainType {
+    type Params = ExplainTypeParams;
+    type Result = Option<String>;
+    const METHOD: &'static str = "rust-analyzer/explainType";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainTypeParams {
+    pub text_document: TextDocumentIdentifier,
+    pub range: Range,
+}
+
 pub enum ViewFileText {}
 
 impl Request for ViewFileText {
@@ -293,6 +323,20 @@ impl Notification for ChangeTestState {
     const METHOD: &'static str = "experimental/changeTestState";
 }
 
+pub enum CompletionAccepted {}
+
+impl Notification for CompletionAccepted {
+    type Params = CompletionAcceptedParams;
+    const METHOD: &'static str = "rust-analyzer/completionAccepted";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionAcceptedParams {
+    pub context: String,
+    pub label: String,
+}
+
 pub enum ExpandMacro {}
 
 impl Request for ExpandMacro {
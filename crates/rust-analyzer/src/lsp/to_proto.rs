@@ -342,10 +342,20 @@ fn completion_item(
     } else {
         item.deprecated.then(|| vec![lsp_types::CompletionItemTag::DEPRECATED])
     };
-    let command = if item.trigger_call_info && config.client_commands().trigger_parameter_hints {
+    let trigger_parameter_hints =
+        item.trigger_call_info && config.client_commands().trigger_parameter_hints;
+    let usage_context =
+        item.usage_context.as_ref().filter(|_| config.client_commands().completion_accepted);
+    let command = if trigger_parameter_hints || usage_context.is_some() {
         if fields_to_resolve.resolve_command {
             something_to_resolve |= true;
             None
+        } else if let Some(context) = usage_context {
+            Some(command::completion_accepted(
+                context,
+                &item.label.primary,
+                trigger_parameter_hints,
+            ))
         } else {
             Some(command::trigger_parameter_hints())
         }
@@ -1900,6 +1910,35 @@ pub(crate) mod command {
         })
     }
 
+    pub(crate) fn explain_type(
+        uri: &lsp_types::Url,
+        range: lsp_types::Range,
+    ) -> lsp_types::Command {
+        lsp_types::Command {
+            title: "Explain type".into(),
+            command: "rust-analyzer.explainType".into(),
+            arguments: Some(vec![to_value(uri).unwrap(), to_value(range).unwrap()]),
+        }
+    }
+
+    /// Reports the accepted completion to the server, and triggers parameter hints afterwards if
+    /// requested, as completion items can only have a single command.
+    pub(crate) fn completion_accepted(
+        context: &str,
+        label: &str,
+        trigger_parameter_hints: bool,
+    ) -> lsp_types::Command {
+        lsp_types::Command {
+            title: "completionAccepted".into(),
+            command: "rust-analyzer.completionAccepted".into(),
+            arguments: Some(vec![
+                to_value(context).unwrap(),
+                to_value(label).unwrap(),
+                to_value(trigger_parameter_hints).unwrap(),
+            ]),
+        }
+    }
+
     pub(crate) fn trigger_parameter_hints() -> lsp_types::Command {
         lsp_types::Command {
             title: "triggerParameterHints".into(),
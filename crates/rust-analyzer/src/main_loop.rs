@@ -13,6 +13,7 @@ use crossbeam_channel::{select, Receiver};
 use ide_db::base_db::{SourceDatabase, VfsPath};
 use lsp_server::{Connection, Notification, Request};
 use lsp_types::{notification::Notification as _, TextDocumentIdentifier};
+use project_model::ManifestPath;
 use stdx::thread::ThreadIntent;
 use tracing::{error, span, Level};
 use vfs::{loader::LoadingProgress, AbsPathBuf, FileId};
@@ -37,7 +38,7 @@ use crate::{
     },
     lsp_ext,
     reload::{BuildDataProgress, ProcMacroProgress, ProjectWorkspaceProgress},
-    test_runner::{CargoTestMessage, TestState},
+    test_runner::{CargoTestMessage, TestRunMessage, TestState},
 };
 
 pub fn main_loop(config: Config, connection: Connection) -> anyhow::Result<()> {
@@ -71,7 +72,7 @@ enum Event {
     QueuedTask(QueuedTask),
     Vfs(vfs::loader::Message),
     Flycheck(FlycheckMessage),
-    TestResult(CargoTestMessage),
+    TestResult(TestRunMessage),
     DiscoverProject(DiscoverProjectMessage),
 }
 
@@ -93,6 +94,7 @@ impl fmt::Display for Event {
 pub(crate) enum QueuedTask {
     CheckIfIndexed(lsp_types::Url),
     CheckProcMacroSources(Vec<FileId>),
+    CheckAdHocFiles,
 }
 
 #[derive(Debug)]
@@ -112,6 +114,8 @@ pub(crate) enum Task {
     FetchWorkspace(ProjectWorkspaceProgress),
     FetchBuildData(BuildDataProgress),
     LoadProcMacros(ProcMacroProgress),
+    AddAdHocFile(AbsPathBuf),
+    RemoveAdHocFiles(Vec<AbsPathBuf>),
     // FIXME: Remove this in favor of a more general QueuedTask, see `handle_did_save_text_document`
     BuildDepsHaveChanged,
 }
@@ -515,6 +519,8 @@ impl GlobalState {
             self.prime_caches(cause);
         }
 
+        self.save_completion_usage(self.config.completion_usage_stats_path(), false);
+
         self.update_status_or_notify();
 
         let loop_duration = loop_start.elapsed();
@@ -819,6 +825,21 @@ impl GlobalState {
                 }
             }
             Task::BuildDepsHaveChanged => self.build_deps_changed = true,
+            Task::AddAdHocFile(path) => {
+                let Ok(file) = ManifestPath::try_from(path) else { return };
+                if self.ad_hoc_files.insert(file.clone()) {
+                    self.recreate_crate_graph(format!("ad hoc file opened {file}"));
+                }
+            }
+            Task::RemoveAdHocFiles(files) => {
+                let mut removed = false;
+                for file in &files {
+                    removed |= self.ad_hoc_files.remove(file.as_path());
+                }
+                if removed {
+                    self.recreate_crate_graph("ad hoc files became modules of crates".to_owned());
+                }
+            }
             Task::DiscoverTest(tests) => {
                 self.send_notification::<lsp_ext::DiscoveredTests>(tests);
             }
@@ -899,11 +920,13 @@ impl GlobalState {
                     };
                     if let Ok(crates) = &snap.analysis.crates_for(id) {
                         if crates.is_empty() {
+                            let path = from_proto::abs_path(&uri).expect("Unable to get AbsPath");
                             if snap.config.discover_workspace_config().is_some() {
-                                let path =
-                                    from_proto::abs_path(&uri).expect("Unable to get AbsPath");
                                 let arg = DiscoverProjectParam::Path(path);
                                 sender.send(Task::DiscoverLinkedProjects(arg)).unwrap();
+                            } else if snap.config.ad_hoc_crates() && path.extension() == Some("rs")
+                            {
+                                sender.send(Task::AddAdHocFile(path)).unwrap();
                             }
                         } else {
                             tracing::debug!(?uri, "is indexed");
@@ -929,6 +952,36 @@ impl GlobalState {
                     }
                 });
             }
+            QueuedTask::CheckAdHocFiles => {
+                let snap = self.snapshot();
+                let files: Vec<AbsPathBuf> =
+                    self.ad_hoc_files.iter().map(|file| file.to_path_buf()).collect();
+
+                self.task_pool.handle.spawn_with_sender(ThreadIntent::Worker, move |sender| {
+                    let _p = tracing::info_span!("GlobalState::check_ad_hoc_files").entered();
+                    // An ad hoc file that is now part of the module tree of another crate, for
+                    // example after a `mod` declaration was added, would be analyzed twice.
+                    let files: Vec<_> = files
+                        .into_iter()
+                        .filter(|file| {
+                            let vfs_path = VfsPath::from(file.clone());
+                            let Ok(Some(file_id)) = snap.vfs_path_to_file_id(&vfs_path) else {
+                                return false;
+                            };
+                            snap.analysis.crates_for(file_id).is_ok_and(|crates| {
+                                crates.into_iter().any(|krate| {
+                                    snap.analysis
+                                        .crate_root(krate)
+                                        .is_ok_and(|root| root != file_id)
+                                })
+                            })
+                        })
+                        .collect();
+                    if !files.is_empty() {
+                        sender.send(Task::RemoveAdHocFiles(files)).unwrap();
+                    }
+                });
+            }
         }
     }
 
@@ -961,21 +1014,20 @@ impl GlobalState {
         }
     }
 
-    fn handle_cargo_test_msg(&mut self, message: CargoTestMessage) {
+    fn handle_cargo_test_msg(&mut self, TestRunMessage { session, message }: TestRunMessage) {
+        // Replaced and aborted runs may still report, e.g. that they are finished.
+        if self.test_run_session.is_none() || session != self.test_run_id {
+            return;
+        }
         match message {
             CargoTestMessage::Test { name, state } => {
-                let state = match state {
-                    TestState::Started => lsp_ext::TestState::Started,
-                    TestState::Ignored => lsp_ext::TestState::Skipped,
-                    TestState::Ok => lsp_ext::TestState::Passed,
-                    TestState::Failed { stdout } => lsp_ext::TestState::Failed { message: stdout },
-                };
                 let Some(test_id) = hack_recover_crate_name::lookup_name(name) else {
                     return;
                 };
-                self.send_notification::<lsp_ext::ChangeTestState>(
-                    lsp_ext::ChangeTestStateParams { test_id, state },
-                );
+                self.send_test_state(test_id, state);
+            }
+            CargoTestMessage::Interpreted { test_id, state } => {
+                self.send_test_state(test_id, state);
             }
             CargoTestMessage::Suite => (),
             CargoTestMessage::Finished => {
@@ -991,6 +1043,19 @@ impl GlobalState {
         }
     }
 
+    fn send_test_state(&mut self, test_id: String, state: TestState) {
+        let state = match state {
+            TestState::Started => lsp_ext::TestState::Started,
+            TestState::Ignored => lsp_ext::TestState::Skipped,
+            TestState::Ok => lsp_ext::TestState::Passed,
+            TestState::Failed { stdout } => lsp_ext::TestState::Failed { message: stdout },
+        };
+        self.send_notification::<lsp_ext::ChangeTestState>(lsp_ext::ChangeTestStateParams {
+            test_id,
+            state,
+        });
+    }
+
     fn handle_flycheck_msg(&mut self, message: FlycheckMessage) {
         match message {
             FlycheckMessage::AddDiagnostic { id, workspace_root, diagnostic, package_id } => {
@@ -1077,6 +1142,7 @@ impl GlobalState {
         let mut dispatcher = RequestDispatcher { req: Some(req), global_state: self };
         dispatcher.on_sync_mut::<lsp_types::request::Shutdown>(|s, ()| {
             s.shutdown_requested = true;
+            s.save_completion_usage(s.config.completion_usage_stats_path(), true);
             Ok(())
         });
 
@@ -1125,6 +1191,7 @@ impl GlobalState {
             // threads in the threadpool.
             // FIXME: Retrying can make the result of this stale?
             .on_latency_sensitive::<RETRY, lsp_request::Completion>(handlers::handle_completion)
+            .on_latency_sensitive::<NO_RETRY, lsp_request::InlineCompletionRequest>(handlers::handle_inline_completion)
             // FIXME: Retrying can make the result of this stale
             .on_latency_sensitive::<RETRY, lsp_request::ResolveCompletionItem>(handlers::handle_completion_resolve)
             .on_latency_sensitive::<RETRY, lsp_request::SemanticTokensFullRequest>(handlers::handle_semantic_tokens_full)
@@ -1172,6 +1239,8 @@ impl GlobalState {
             .on::<NO_RETRY, lsp_ext::ViewHir>(handlers::handle_view_hir)
             .on::<NO_RETRY, lsp_ext::ViewMir>(handlers::handle_view_mir)
             .on::<NO_RETRY, lsp_ext::InterpretFunction>(handlers::handle_interpret_function)
+            .on::<NO_RETRY, lsp_ext::EvaluateExpression>(handlers::handle_evaluate_expression)
+            .on::<NO_RETRY, lsp_ext::ExplainType>(handlers::handle_explain_type)
             .on::<NO_RETRY, lsp_ext::ExpandMacro>(handlers::handle_expand_macro)
             .on::<NO_RETRY, lsp_ext::ParentModule>(handlers::handle_parent_module)
             .on::<NO_RETRY, lsp_ext::Runnables>(handlers::handle_runnables)
@@ -1214,6 +1283,7 @@ impl GlobalState {
             .on_sync_mut::<lsp_ext::ClearFlycheck>(handlers::handle_clear_flycheck)
             .on_sync_mut::<lsp_ext::RunFlycheck>(handlers::handle_run_flycheck)
             .on_sync_mut::<lsp_ext::AbortRunTest>(handlers::handle_abort_run_test)
+            .on_sync_mut::<lsp_ext::CompletionAccepted>(handlers::handle_completion_accepted)
             .finish();
     }
 }
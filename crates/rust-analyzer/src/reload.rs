@@ -30,6 +30,7 @@ use triomphe::Arc;
 use vfs::{AbsPath, AbsPathBuf, ChangeKind};
 
 use crate::{
+    completion_usage,
     config::{Config, FilesWatcher, LinkedProject},
     flycheck::{FlycheckConfig, FlycheckHandle},
     global_state::{
@@ -105,6 +106,14 @@ impl GlobalState {
             self.reload_flycheck();
         }
 
+        let usage_stats_path = self.config.completion_usage_stats_path();
+        if usage_stats_path != old_config.completion_usage_stats_path() {
+            self.save_completion_usage(old_config.completion_usage_stats_path(), true);
+            self.completion_usage = Arc::new(
+                usage_stats_path.map(|path| completion_usage::load(&path)).unwrap_or_default(),
+            );
+        }
+
         if self.analysis_host.raw_database().expand_proc_attr_macros()
             != self.config.expand_proc_attr_macros()
         {
@@ -276,6 +285,13 @@ impl GlobalState {
             let is_quiescent = !(self.discover_workspace_queue.op_in_progress()
                 || self.vfs_progress_config_version < self.vfs_config_version
                 || !self.vfs_done);
+            // A changed cargo script manifest only affects the workspace of that script, so the
+            // other workspaces can be reused as long as they all loaded fine.
+            let reusable_workspaces = path
+                .as_deref()
+                .filter(|path| self.detached_files.contains(*path))
+                .filter(|_| !self.workspaces.is_empty() && self.fetch_workspace_error().is_ok())
+                .map(|_| self.workspaces.clone());
 
             move |sender| {
                 let progress = {
@@ -289,6 +305,29 @@ impl GlobalState {
 
                 sender.send(Task::FetchWorkspace(ProjectWorkspaceProgress::Begin)).unwrap();
 
+                if let (Some(workspaces), Some(path)) = (reusable_workspaces, &path) {
+                    let workspaces = workspaces
+                        .iter()
+                        .map(|ws| match &ws.kind {
+                            ProjectWorkspaceKind::DetachedFile { file, .. } if **file == *path => {
+                                debug!(%path, "reloading cargo script");
+                                project_model::ProjectWorkspace::load_detached_file(
+                                    file,
+                                    &cargo_config,
+                                )
+                            }
+                            _ => Ok(ws.clone()),
+                        })
+                        .collect();
+                    sender
+                        .send(Task::FetchWorkspace(ProjectWorkspaceProgress::End(
+                            workspaces,
+                            force_crate_graph_reload,
+                        )))
+                        .unwrap();
+                    return;
+                }
+
                 if let (Some(_command), Some(path)) = (&discover_command, &path) {
                     let build = linked_projects.iter().find_map(|project| match project {
                         LinkedProject::InlineJsonProject(it) => it.crate_by_buildfile(path),
@@ -555,7 +594,8 @@ impl GlobalState {
                                 [
                                     (base.clone(), "**/*.rs"),
                                     (base.clone(), "**/Cargo.{lock,toml}"),
-                                    (base, "**/rust-analyzer.toml"),
+                                    (base.clone(), "**/rust-analyzer.toml"),
+                                    (base, "**/.rust-analyzer/snippets.toml"),
                                 ]
                             })
                         })
@@ -580,6 +620,7 @@ impl GlobalState {
                                     format!("{base}/**/*.rs"),
                                     format!("{base}/**/Cargo.{{toml,lock}}"),
                                     format!("{base}/**/rust-analyzer.toml"),
+                                    format!("{base}/**/.rust-analyzer/snippets.toml"),
                                 ]
                             })
                         })
@@ -696,7 +737,26 @@ impl GlobalState {
         info!("did switch workspaces");
     }
 
-    fn recreate_crate_graph(&mut self, cause: String) {
+    /// Records the manifests of newly loaded cargo scripts, so that saving them only refetches
+    /// the workspace once their manifest actually changed.
+    fn seed_detached_file_manifests(&mut self) {
+        let detached_files = &self.detached_files;
+        self.detached_file_manifests.retain(|file, _| detached_files.contains(file));
+        for file in detached_files {
+            if self.detached_file_manifests.contains_key(file) {
+                continue;
+            }
+            // `cargo metadata` read the script from disk as well.
+            let Ok(text) = std::fs::read_to_string(file) else { continue };
+            let manifest = syntax::SourceFile::parse(&text, syntax::Edition::CURRENT)
+                .tree()
+                .frontmatter_token()
+                .map(|it| it.text().to_owned());
+            self.detached_file_manifests.insert(file.clone(), manifest);
+        }
+    }
+
+    pub(crate) fn recreate_crate_graph(&mut self, cause: String) {
         info!(?cause, "Building Crate Graph");
         self.report_progress(
             "Building CrateGraph",
@@ -717,11 +777,12 @@ impl GlobalState {
                 _ => None,
             })
             .collect();
+        self.seed_detached_file_manifests();
 
         let (crate_graph, proc_macro_paths) = {
             // Create crate graph from all the workspaces
             let vfs = &self.vfs.read().0;
-            let load = |path: &AbsPath| {
+            let mut load = |path: &AbsPath| {
                 let vfs_path = vfs::VfsPath::from(path.to_path_buf());
                 self.crate_graph_file_dependencies.insert(vfs_path.clone());
                 vfs.file_id(&vfs_path).and_then(|(file_id, excluded)| {
@@ -729,7 +790,28 @@ impl GlobalState {
                 })
             };
 
-            ws_to_crate_graph(&self.workspaces, self.config.extra_env(None), load)
+            let (mut crate_graph, proc_macro_paths) =
+                ws_to_crate_graph(&self.workspaces, self.config.extra_env(None), &mut load);
+            self.ad_hoc_files.retain(|file| {
+                // The file may have been picked up by a workspace in the meantime.
+                let file_id = vfs.file_id(&vfs::VfsPath::from(file.to_path_buf()));
+                !crate_graph.iter().any(|krate| {
+                    Some(crate_graph[krate].basic.root_file_id) == file_id.map(|(it, _)| it)
+                })
+            });
+            for file in &self.ad_hoc_files {
+                // Prefer the workspace containing the file, its packages may provide dependencies.
+                let ws = self
+                    .workspaces
+                    .iter()
+                    .filter(|ws| !matches!(ws.kind, ProjectWorkspaceKind::DetachedFile { .. }))
+                    .find(|ws| file.starts_with(ws.workspace_root()))
+                    .or_else(|| self.workspaces.first());
+                let Some(ws) = ws else { continue };
+                let package_deps = self.config.ad_hoc_crates_package_dependencies();
+                ws.add_ad_hoc_crate(&mut crate_graph, file, package_deps, &mut load);
+            }
+            (crate_graph, proc_macro_paths)
         };
         let mut change = ChangeWithProcMacros::new();
         let initial_build = self.analysis_host.raw_database().all_crates().is_empty();
@@ -771,6 +853,9 @@ impl GlobalState {
 
         self.process_changes();
         self.reload_flycheck();
+        if !self.ad_hoc_files.is_empty() {
+            _ = self.deferred_task_queue.sender.send(crate::main_loop::QueuedTask::CheckAdHocFiles);
+        }
     }
 
     pub(super) fn fetch_workspace_error(&self) -> Result<(), String> {
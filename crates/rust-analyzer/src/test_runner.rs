@@ -1,14 +1,21 @@
 //! This module provides the functionality needed to run `cargo test` in a background
-//! thread and report the result of each test in a channel.
+//! thread and report the result of each test in a channel. Tests can also be run in-process with
+//! the mir interpreter instead, see [`InterpretTestHandle`].
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 use crossbeam_channel::Sender;
+use ide::{Analysis, InterpretedTestResult};
 use paths::AbsPath;
 use serde::Deserialize as _;
 use serde_derive::Deserialize;
 use toolchain::Tool;
 
 use crate::{
-    command::{CommandHandle, ParseFromLine},
+    command::{CargoParser, CommandHandle},
     flycheck::CargoOptions,
 };
 
@@ -33,6 +40,12 @@ pub(crate) enum CargoTestMessage {
         #[serde(flatten)]
         state: TestState,
     },
+    /// A test run by the mir interpreter, identified by its test explorer id.
+    #[serde(skip)]
+    Interpreted {
+        test_id: String,
+        state: TestState,
+    },
     Suite,
     Finished,
     Custom {
@@ -40,25 +53,41 @@ pub(crate) enum CargoTestMessage {
     },
 }
 
-impl ParseFromLine for CargoTestMessage {
-    fn from_line(line: &str, _: &mut String) -> Option<Self> {
+/// A message of the test run `session`. Runs that were replaced or aborted may still send
+/// messages, which must not be mistaken for the ones of the current run.
+#[derive(Debug)]
+pub(crate) struct TestRunMessage {
+    pub(crate) session: u32,
+    pub(crate) message: CargoTestMessage,
+}
+
+struct CargoTestParser {
+    session: u32,
+}
+
+impl CargoParser<TestRunMessage> for CargoTestParser {
+    fn from_line(&self, line: &str, _: &mut String) -> Option<TestRunMessage> {
         let mut deserializer = serde_json::Deserializer::from_str(line);
         deserializer.disable_recursion_limit();
-        if let Ok(message) = CargoTestMessage::deserialize(&mut deserializer) {
-            return Some(message);
-        }
-
-        Some(CargoTestMessage::Custom { text: line.to_owned() })
+        let message = CargoTestMessage::deserialize(&mut deserializer)
+            .unwrap_or_else(|_| CargoTestMessage::Custom { text: line.to_owned() });
+        Some(TestRunMessage { session: self.session, message })
     }
 
-    fn from_eof() -> Option<Self> {
-        Some(CargoTestMessage::Finished)
+    fn from_eof(&self) -> Option<TestRunMessage> {
+        Some(TestRunMessage { session: self.session, message: CargoTestMessage::Finished })
     }
 }
 
+#[derive(Debug)]
+pub(crate) enum TestRunSession {
+    Cargo { _handles: Vec<CargoTestHandle> },
+    Interpreter { _handle: InterpretTestHandle },
+}
+
 #[derive(Debug)]
 pub(crate) struct CargoTestHandle {
-    _handle: CommandHandle<CargoTestMessage>,
+    _handle: CommandHandle<TestRunMessage>,
 }
 
 // Example of a cargo test command:
@@ -78,7 +107,8 @@ impl CargoTestHandle {
         options: CargoOptions,
         root: &AbsPath,
         test_target: TestTarget,
-        sender: Sender<CargoTestMessage>,
+        session: u32,
+        sender: Sender<TestRunMessage>,
     ) -> std::io::Result<Self> {
         let mut cmd = toolchain::command(Tool::Cargo.path(), root);
         cmd.env("RUSTC_BOOTSTRAP", "1");
@@ -110,6 +140,85 @@ impl CargoTestHandle {
             cmd.arg(extra_arg);
         }
 
-        Ok(Self { _handle: CommandHandle::spawn(cmd, sender)? })
+        Ok(Self { _handle: CommandHandle::spawn(cmd, CargoTestParser { session }, sender)? })
+    }
+}
+
+/// Runs tests with the mir interpreter on a worker thread, stopping when dropped.
+///
+/// The worker holds a snapshot of the database, so the run ends as soon as the database is
+/// changed; otherwise it would block the main loop until all tests are finished.
+#[derive(Debug)]
+pub(crate) struct InterpretTestHandle {
+    stop: Arc<AtomicBool>,
+    _thread: stdx::thread::JoinHandle,
+}
+
+impl InterpretTestHandle {
+    pub(crate) fn spawn(
+        analysis: Analysis,
+        include: Option<Vec<String>>,
+        exclude: Vec<String>,
+        check_ub: bool,
+        session: u32,
+        sender: Sender<TestRunMessage>,
+    ) -> std::io::Result<Self> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = stdx::thread::Builder::new(stdx::thread::ThreadIntent::Worker)
+            .name("InterpretTests".to_owned())
+            .stack_size(8 * 1024 * 1024)
+            .allow_leak(true)
+            .spawn({
+                let stop = stop.clone();
+                move || {
+                    let send = |message| {
+                        // An aborted run must not report to the session that replaced it.
+                        if !stop.load(Ordering::Relaxed) {
+                            _ = sender.send(TestRunMessage { session, message });
+                        }
+                    };
+                    interpret_tests(analysis, include, exclude, check_ub, &stop, send)
+                }
+            })?;
+        Ok(Self { stop, _thread: thread })
+    }
+}
+
+impl Drop for InterpretTestHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn interpret_tests(
+    analysis: Analysis,
+    include: Option<Vec<String>>,
+    exclude: Vec<String>,
+    check_ub: bool,
+    stop: &AtomicBool,
+    send: impl Fn(CargoTestMessage),
+) {
+    let Ok(tests) = analysis.tests_to_interpret(include.as_deref(), &exclude) else {
+        send(CargoTestMessage::Finished);
+        return;
+    };
+    for test_id in tests {
+        send(CargoTestMessage::Interpreted { test_id: test_id.clone(), state: TestState::Started });
+        let state = match analysis.interpret_test(&test_id, stop, check_ub) {
+            Ok(Some(InterpretedTestResult::Passed)) => TestState::Ok,
+            Ok(Some(InterpretedTestResult::Ignored)) => TestState::Ignored,
+            Ok(Some(InterpretedTestResult::Failed { message })) => {
+                TestState::Failed { stdout: message }
+            }
+            Ok(None) => continue,
+            Err(_) => {
+                send(CargoTestMessage::Custom {
+                    text: "Test run cancelled because the workspace changed\n".to_owned(),
+                });
+                break;
+            }
+        };
+        send(CargoTestMessage::Interpreted { test_id, state });
     }
+    send(CargoTestMessage::Finished);
 }
@@ -30,7 +30,9 @@ use lsp_types::{
     PartialResultParams, Position, Range, RenameFilesParams, TextDocumentItem,
     TextDocumentPositionParams, WorkDoneProgressParams,
 };
-use rust_analyzer::lsp::ext::{OnEnter, Runnables, RunnablesParams};
+use rust_analyzer::lsp::ext::{
+    AnalyzerStatus, AnalyzerStatusParams, OnEnter, Runnables, RunnablesParams,
+};
 use serde_json::json;
 use stdx::format_to_acc;
 
@@ -1500,3 +1502,72 @@ version = "0.0.0"
 
     server.request::<WorkspaceSymbolRequest>(Default::default(), json!([]));
 }
+
+#[test]
+fn ad_hoc_file_becomes_module() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+pub fn foo() {}
+
+//- /src/scratch.rs
+pub fn scratch() {}
+"#,
+    )
+    .with_config(json!({
+        "cargo": { "sysroot": null },
+        "files": { "adHocCrates": { "enable": true } },
+    }))
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let wait_for_crates_of_scratch = |expected: &[&str]| {
+        let mut crates = vec![];
+        for _ in 0..100 {
+            let status = server.send_request::<AnalyzerStatus>(AnalyzerStatusParams {
+                text_document: Some(server.doc_id("src/scratch.rs")),
+            });
+            crates = status
+                .as_str()
+                .unwrap()
+                .lines()
+                .filter_map(|line| line.strip_prefix("Crate: "))
+                .filter_map(|krate| krate.split_once('(').map(|(name, _)| name.to_owned()))
+                .collect::<Vec<_>>();
+            if crates == expected {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        panic!("expected `src/scratch.rs` to belong to {expected:?}, got {crates:?}");
+    };
+
+    server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: server.doc_id("src/scratch.rs").uri,
+            language_id: "rust".to_owned(),
+            version: 0,
+            text: "pub fn scratch() {}".to_owned(),
+        },
+    });
+    wait_for_crates_of_scratch(&["scratch"]);
+
+    server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: server.doc_id("src/lib.rs").uri,
+            language_id: "rust".to_owned(),
+            version: 0,
+            text: "pub fn foo() {}\nmod scratch;".to_owned(),
+        },
+    });
+    wait_for_crates_of_scratch(&["foo"]);
+}
@@ -408,6 +408,8 @@ pub mod mem {
     // region:size_of
     #[rustc_intrinsic]
     pub fn size_of<T>() -> usize;
+    #[rustc_intrinsic]
+    pub fn align_of<T>() -> usize;
     // endregion:size_of
 
     // region:discriminant
@@ -1310,6 +1312,12 @@ pub mod option {
         }
         // endregion:result
         // region:fn
+        pub fn map<U, F>(self, _f: F) -> Option<U>
+        where
+            F: FnOnce(T) -> U,
+        {
+            loop {}
+        }
         pub fn and_then<U, F>(self, _f: F) -> Option<U>
         where
             F: FnOnce(T) -> Option<U>,
@@ -1348,6 +1356,17 @@ pub mod result {
         #[lang = "Err"]
         Err(E),
     }
+
+    // region:fn
+    impl<T, E> Result<T, E> {
+        pub fn map_err<F, O>(self, _op: O) -> Result<T, F>
+        where
+            O: FnOnce(E) -> F,
+        {
+            loop {}
+        }
+    }
+    // endregion:fn
 }
 // endregion:result
 
@@ -1459,8 +1478,24 @@ pub mod iter {
                 loop {}
             }
         }
+
+        pub struct Map<I, F> {
+            iter: I,
+            f: F,
+        }
+        impl<B, I: Iterator, F> Iterator for Map<I, F>
+        where
+            F: FnMut(I::Item) -> B,
+        {
+            type Item = B;
+
+            #[inline]
+            fn next(&mut self) -> Option<B> {
+                loop {}
+            }
+        }
     }
-    pub use self::adapters::{FilterMap, Take};
+    pub use self::adapters::{FilterMap, Map, Take};
 
     mod sources {
         mod repeat {
@@ -1516,6 +1551,13 @@ pub mod iter {
                 {
                     loop {}
                 }
+                fn map<B, F>(self, _f: F) -> crate::iter::Map<Self, F>
+                where
+                    Self: Sized,
+                    F: FnMut(Self::Item) -> B,
+                {
+                    loop {}
+                }
                 // endregion:iterators
             }
             impl<I: Iterator + ?Sized> Iterator for &mut I {
@@ -1780,6 +1822,18 @@ mod macros {
     macro_rules! include {
         ($file:expr $(,)?) => {{ /* compiler built-in */ }};
     }
+
+    #[rustc_builtin_macro]
+    #[macro_export]
+    macro_rules! include_bytes {
+        ($file:expr $(,)?) => {{ /* compiler built-in */ }};
+    }
+
+    #[rustc_builtin_macro]
+    #[macro_export]
+    macro_rules! include_str {
+        ($file:expr $(,)?) => {{ /* compiler built-in */ }};
+    }
     // endregion:include
 
     // region:concat
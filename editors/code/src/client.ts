@@ -321,7 +321,9 @@ class ExperimentalFeatures implements lc.StaticFeature {
                     "rust-analyzer.debugSingle",
                     "rust-analyzer.showReferences",
                     "rust-analyzer.gotoLocation",
+                    "rust-analyzer.explainType",
                     "rust-analyzer.triggerParameterHints",
+                    "rust-analyzer.completionAccepted",
                     "rust-analyzer.rename",
                 ],
             },
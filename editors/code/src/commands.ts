@@ -100,6 +100,15 @@ export function memoryUsage(ctx: CtxInit): Cmd {
     };
 }
 
+export function completionAccepted(ctx: CtxInit): Cmd {
+    return async (context: string, label: string, triggerParameterHints: boolean) => {
+        await ctx.client.sendNotification(ra.completionAccepted, { context, label });
+        if (triggerParameterHints) {
+            await vscode.commands.executeCommand("rust-analyzer.triggerParameterHints");
+        }
+    };
+}
+
 export function triggerParameterHints(_: CtxInit): Cmd {
     return async () => {
         const parameterHintsEnabled = vscode.workspace
@@ -909,6 +918,34 @@ export function gotoLocation(ctx: CtxInit): Cmd {
     };
 }
 
+export function explainType(ctx: CtxInit): Cmd {
+    return async (uri?: string, range?: lc.Range) => {
+        const client = ctx.client;
+        if (uri === undefined || range === undefined) {
+            const editor = ctx.activeRustEditor;
+            if (!editor) return;
+            uri = editor.document.uri.toString();
+            range = client.code2ProtocolConverter.asRange(editor.selection);
+        }
+        const explanation = await client.sendRequest(ra.explainType, {
+            textDocument: { uri },
+            range,
+        });
+        if (!explanation) {
+            await vscode.window.showInformationMessage("No expression to explain");
+            return;
+        }
+        const document = await vscode.workspace.openTextDocument({
+            language: "markdown",
+            content: explanation,
+        });
+        await vscode.window.showTextDocument(document, {
+            viewColumn: vscode.ViewColumn.Two,
+            preserveFocus: true,
+        });
+    };
+}
+
 export function openDocs(ctx: CtxInit): Cmd {
     return async () => {
         const editor = vscode.window.activeTextEditor;
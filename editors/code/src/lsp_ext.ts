@@ -32,6 +32,9 @@ export const analyzerStatus = new lc.RequestType<AnalyzerStatusParams, string, v
 );
 export const cancelFlycheck = new lc.NotificationType0("rust-analyzer/cancelFlycheck");
 export const clearFlycheck = new lc.NotificationType0("rust-analyzer/clearFlycheck");
+export const completionAccepted = new lc.NotificationType<CompletionAcceptedParams>(
+    "rust-analyzer/completionAccepted",
+);
 export const expandMacro = new lc.RequestType<ExpandMacroParams, ExpandedMacro | null, void>(
     "rust-analyzer/expandMacro",
 );
@@ -67,6 +70,9 @@ export const viewMir = new lc.RequestType<lc.TextDocumentPositionParams, string,
 export const interpretFunction = new lc.RequestType<lc.TextDocumentPositionParams, string, void>(
     "rust-analyzer/interpretFunction",
 );
+export const explainType = new lc.RequestType<ExplainTypeParams, string | null, void>(
+    "rust-analyzer/explainType",
+);
 export const viewItemTree = new lc.RequestType<ViewItemTreeParams, string, void>(
     "rust-analyzer/viewItemTree",
 );
@@ -164,6 +170,8 @@ export type SyntaxTreeParams = {
 export type ViewSyntaxTreeParams = { textDocument: lc.TextDocumentIdentifier };
 export type ViewCrateGraphParams = { full: boolean };
 export type ViewItemTreeParams = { textDocument: lc.TextDocumentIdentifier };
+export type ExplainTypeParams = { textDocument: lc.TextDocumentIdentifier; range: lc.Range };
+export type CompletionAcceptedParams = { context: string; label: string };
 
 // experimental extensions
 
@@ -193,6 +193,8 @@ fn check_test_attrs(path: &Path, text: &str) {
         "test-utils/src/fixture.rs",
         // Generated code from lints contains doc tests in string literals.
         "ide-db/src/generated/lints.rs",
+        // Fixtures contain tests that are run with the mir interpreter.
+        "ide/src/test_explorer.rs",
     ];
     if text.contains("#[should_panic") && !need_panic.iter().any(|p| path.ends_with(p)) {
         panic!(